@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::call_tracer::CallTracerLog;
 use crate::{
 	listeners::call_list::Listener,
 	types::{
@@ -91,4 +92,11 @@ pub struct BlockscoutCall {
 	pub gas_used: U256,
 	#[serde(flatten)]
 	pub inner: BlockscoutCallInner,
+	/// Data returned by the context if it reverted.
+	/// Not needed for Blockscout, but decoded into the revert reason of the callTracer.
+	#[serde(skip)]
+	pub revert_data: Vec<u8>,
+	/// Logs emitted by the context, only captured for the callTracer `withLog` option.
+	#[serde(skip)]
+	pub logs: Vec<CallTracerLog>,
 }
@@ -25,7 +25,7 @@ use crate::listeners::call_list::Listener;
 use crate::types::serialization::*;
 use serde::Serialize;
 
-use ethereum_types::{H160, U256};
+use ethereum_types::{H160, H256, U256};
 use parity_scale_codec::{Decode, Encode};
 use sp_std::{cmp::Ordering, vec::Vec};
 
@@ -36,13 +36,12 @@ impl super::ResponseFormatter for Formatter {
 	type Response = Vec<TransactionTrace>;
 
 	fn format(mut listener: Listener) -> Option<Vec<TransactionTrace>> {
-		// Remove empty BTreeMaps pushed to `entries`.
-		// I.e. InvalidNonce or other pallet_evm::runner exits
-		listener.entries.retain(|x| !x.is_empty());
 		let mut traces = Vec::new();
-		for entry in listener.entries.iter() {
+		for entry in listener.take_block_entries() {
 			let mut result: Vec<Call> = entry
+				.calls
 				.iter()
+				.filter(|(_, it)| !listener.only_top_call || it.trace_address.is_empty())
 				.map(|(_, it)| {
 					let from = it.from;
 					let trace_address = it.trace_address.clone();
@@ -50,11 +49,14 @@ impl super::ResponseFormatter for Formatter {
 					let gas = it.gas;
 					let gas_used = it.gas_used;
 					let inner = it.inner.clone();
+					let revert_data = (!it.revert_data.is_empty()).then(|| it.revert_data.clone());
+					let revert_reason = decode_revert_reason(&it.revert_data);
 					Call::CallTracer(CallTracerCall {
 						from,
 						gas,
 						gas_used,
 						trace_address: Some(trace_address),
+						extrinsic_index: None,
 						inner: match inner {
 							BlockscoutCallInner::Call { input, to, res, call_type } =>
 								CallTracerInner::Call {
@@ -68,6 +70,8 @@ impl super::ResponseFormatter for Formatter {
 									to,
 									input,
 									res,
+									revert_data,
+									revert_reason,
 									value: Some(value),
 								},
 							BlockscoutCallInner::Create { init, res } => CallTracerInner::Create {
@@ -85,8 +89,9 @@ impl super::ResponseFormatter for Formatter {
 								output: match res {
 									CreateResult::Success { created_contract_code, .. } =>
 										Some(created_contract_code),
-									CreateResult::Error { .. } => None,
+									CreateResult::Error { .. } => revert_data,
 								},
+								revert_reason,
 								value,
 								call_type: "CREATE".as_bytes().to_vec(),
 							},
@@ -97,6 +102,7 @@ impl super::ResponseFormatter for Formatter {
 									call_type: "SELFDESTRUCT".as_bytes().to_vec(),
 								},
 						},
+						logs: if listener.with_log { it.logs.clone() } else { Vec::new() },
 						calls: Vec::new(),
 					})
 				})
@@ -206,11 +212,16 @@ impl super::ResponseFormatter for Formatter {
 				*trace_address = None;
 			}
 			if result.len() == 1 {
-				traces.push(TransactionTrace::CallListNested(
-					result
-						.pop()
-						.expect("result.len() == 1, so pop() necessarily returns this element"),
-				));
+				let mut root = result
+					.pop()
+					.expect("result.len() == 1, so pop() necessarily returns this element");
+				if listener.with_log {
+					clear_failed_logs(&mut root, false);
+				}
+				if let Call::CallTracer(CallTracerCall { extrinsic_index, .. }) = &mut root {
+					*extrinsic_index = entry.origin.and_then(|origin| origin.extrinsic_index());
+				}
+				traces.push(TransactionTrace::CallListNested(root));
 			}
 		}
 		if traces.is_empty() {
@@ -220,6 +231,86 @@ impl super::ResponseFormatter for Formatter {
 	}
 }
 
+/// Like Geth, the logs of a call which failed are discarded along with the logs of its
+/// subcalls, since they are reverted.
+fn clear_failed_logs(call: &mut Call, parent_failed: bool) {
+	if let Call::CallTracer(CallTracerCall { inner, logs, calls, .. }) = call {
+		let failed = parent_failed ||
+			match inner {
+				CallTracerInner::Call { res, .. } => matches!(res, CallResult::Error(_)),
+				CallTracerInner::Create { error, .. } => error.is_some(),
+				CallTracerInner::SelfDestruct { .. } => false,
+			};
+		if failed {
+			logs.clear();
+		}
+		for call in calls.iter_mut() {
+			clear_failed_logs(call, failed);
+		}
+	}
+}
+
+/// Selector of the `Error(string)` revert of Solidity.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of the `Panic(uint256)` revert of Solidity.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes the revert data of a call into a reason, for the `Error(string)` and
+/// `Panic(uint256)` reverts of Solidity.
+pub fn decode_revert_reason(data: &[u8]) -> Option<Vec<u8>> {
+	let to_usize = |word: &[u8]| {
+		let value = U256::from_big_endian(word);
+		(value <= U256::from(u32::MAX)).then(|| value.low_u64() as usize)
+	};
+
+	if data.len() < 4 {
+		return None;
+	}
+	let (selector, args) = data.split_at(4);
+	if selector == ERROR_SELECTOR {
+		// Offset of the string, then its length and bytes.
+		let offset = to_usize(args.get(0..32)?)?;
+		let len = to_usize(args.get(offset..offset + 32)?)?;
+		let reason = args.get(offset + 32..offset + 32 + len)?;
+		// The reason is serialized as a string.
+		sp_std::str::from_utf8(reason).ok()?;
+		Some(reason.to_vec())
+	} else if selector == PANIC_SELECTOR {
+		let code = U256::from_big_endian(args.get(0..32)?);
+		let reason = match code.low_u64() {
+			_ if code > U256::from(u8::MAX) => None,
+			0x00 => Some("generic panic"),
+			0x01 => Some("assert(false)"),
+			0x11 => Some("arithmetic underflow or overflow"),
+			0x12 => Some("division or modulo by zero"),
+			0x21 => Some("enum overflow"),
+			0x22 => Some("invalid encoded storage byte array accessed"),
+			0x31 => Some("out-of-bounds array access; popping on an empty array"),
+			0x32 => Some("out-of-bounds access of an array or bytesN"),
+			0x41 => Some("out of memory"),
+			0x51 => Some("uninitialized function"),
+			_ => None,
+		};
+		Some(match reason {
+			Some(reason) => reason.as_bytes().to_vec(),
+			None => format!("unknown panic code: {:#x}", code).into_bytes(),
+		})
+	} else {
+		None
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTracerLog {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	#[serde(serialize_with = "bytes_0x_serialize")]
+	pub data: Vec<u8>,
+	/// Number of subcalls made by the call before emitting the log.
+	pub position: U256,
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerCall {
@@ -229,6 +320,10 @@ pub struct CallTracerCall {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub trace_address: Option<Vec<u32>>,
 
+	/// Index of the extrinsic which executed the EVM, set on the root call of block traces.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub extrinsic_index: Option<u32>,
+
 	/// Remaining gas in the runtime.
 	pub gas: U256,
 	/// Gas used by this context.
@@ -237,6 +332,9 @@ pub struct CallTracerCall {
 	#[serde(flatten)]
 	pub inner: CallTracerInner,
 
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub logs: Vec<CallTracerLog>,
+
 	#[serde(skip_serializing_if = "Vec::is_empty")]
 	pub calls: Vec<Call>,
 }
@@ -253,6 +351,18 @@ pub enum CallTracerInner {
 		/// "output" or "error" field
 		#[serde(flatten)]
 		res: CallResult,
+		/// Data returned along the "error" field when the call reverted.
+		#[serde(
+			rename = "output",
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "option_bytes_0x_serialize"
+		)]
+		revert_data: Option<Vec<u8>>,
+		#[serde(
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "option_string_serialize"
+		)]
+		revert_reason: Option<Vec<u8>>,
 
 		#[serde(skip_serializing_if = "Option::is_none")]
 		value: Option<U256>,
@@ -274,6 +384,11 @@ pub enum CallTracerInner {
 			serialize_with = "option_string_serialize"
 		)]
 		error: Option<Vec<u8>>,
+		#[serde(
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "option_string_serialize"
+		)]
+		revert_reason: Option<Vec<u8>>,
 		value: U256,
 	},
 	SelfDestruct {
@@ -283,3 +398,145 @@ pub enum CallTracerInner {
 		value: U256,
 	},
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn error_revert(reason: &str) -> Vec<u8> {
+		let mut data = ERROR_SELECTOR.to_vec();
+		data.extend_from_slice(H256::from_low_u64_be(32).as_bytes());
+		data.extend_from_slice(H256::from_low_u64_be(reason.len() as u64).as_bytes());
+		let mut padded = reason.as_bytes().to_vec();
+		padded.resize(reason.len().div_ceil(32) * 32, 0);
+		data.extend_from_slice(&padded);
+		data
+	}
+
+	fn panic_revert(code: u64) -> Vec<u8> {
+		let mut data = PANIC_SELECTOR.to_vec();
+		data.extend_from_slice(H256::from_low_u64_be(code).as_bytes());
+		data
+	}
+
+	#[test]
+	fn decodes_revert_reasons() {
+		assert_eq!(
+			decode_revert_reason(&error_revert("Ownable: caller is not the owner")),
+			Some(b"Ownable: caller is not the owner".to_vec())
+		);
+		assert_eq!(
+			decode_revert_reason(&panic_revert(0x11)),
+			Some(b"arithmetic underflow or overflow".to_vec())
+		);
+		assert_eq!(
+			decode_revert_reason(&panic_revert(0x99)),
+			Some(b"unknown panic code: 0x99".to_vec())
+		);
+		// Custom errors and truncated data are not decoded.
+		assert_eq!(decode_revert_reason(&[0xde, 0xad, 0xbe, 0xef]), None);
+		assert_eq!(decode_revert_reason(&error_revert("reason")[..40]), None);
+		assert_eq!(decode_revert_reason(&[]), None);
+	}
+
+	#[test]
+	fn serializes_reverted_call_with_logs() {
+		let revert_data = error_revert("nope");
+		let call = CallTracerCall {
+			from: H160::repeat_byte(1),
+			trace_address: None,
+			extrinsic_index: None,
+			gas: 100.into(),
+			gas_used: 50.into(),
+			inner: CallTracerInner::Call {
+				call_type: b"CALL".to_vec(),
+				to: H160::repeat_byte(2),
+				input: vec![0x12, 0x34],
+				res: CallResult::Error(b"execution reverted".to_vec()),
+				revert_reason: decode_revert_reason(&revert_data),
+				revert_data: Some(revert_data.clone()),
+				value: Some(0.into()),
+			},
+			logs: vec![CallTracerLog {
+				address: H160::repeat_byte(2),
+				topics: vec![H256::repeat_byte(3)],
+				data: vec![0xff],
+				position: 0.into(),
+			}],
+			calls: vec![],
+		};
+
+		assert_eq!(
+			serde_json::to_value(&call).unwrap(),
+			serde_json::json!({
+				"from": "0x0101010101010101010101010101010101010101",
+				"gas": "0x64",
+				"gasUsed": "0x32",
+				"type": "CALL",
+				"to": "0x0202020202020202020202020202020202020202",
+				"input": "0x1234",
+				"error": "execution reverted",
+				"output": format!("0x{}", hex::encode(&revert_data)),
+				"revertReason": "nope",
+				"value": "0x0",
+				"logs": [{
+					"address": "0x0202020202020202020202020202020202020202",
+					"topics": ["0x0303030303030303030303030303030303030303030303030303030303030303"],
+					"data": "0xff",
+					"position": "0x0",
+				}],
+			})
+		);
+	}
+
+	#[test]
+	fn logs_of_failed_calls_are_cleared() {
+		let log = CallTracerLog {
+			address: H160::repeat_byte(2),
+			topics: vec![],
+			data: vec![],
+			position: 0.into(),
+		};
+		let call = |res: CallResult, calls: Vec<Call>| {
+			Call::CallTracer(CallTracerCall {
+				from: H160::zero(),
+				trace_address: None,
+				extrinsic_index: None,
+				gas: 0.into(),
+				gas_used: 0.into(),
+				inner: CallTracerInner::Call {
+					call_type: b"CALL".to_vec(),
+					to: H160::zero(),
+					input: vec![],
+					res,
+					revert_data: None,
+					revert_reason: None,
+					value: None,
+				},
+				logs: vec![log.clone()],
+				calls,
+			})
+		};
+		let logs = |call: &Call| match call {
+			Call::CallTracer(call) => call.logs.len(),
+			_ => unreachable!(),
+		};
+
+		let mut root = call(
+			CallResult::Output(vec![]),
+			vec![call(
+				CallResult::Error(b"execution reverted".to_vec()),
+				vec![call(CallResult::Output(vec![]), vec![])],
+			)],
+		);
+		clear_failed_logs(&mut root, false);
+
+		let Call::CallTracer(CallTracerCall { calls, .. }) = &root else { unreachable!() };
+		let Call::CallTracer(CallTracerCall { calls: inner_calls, .. }) = &calls[0] else {
+			unreachable!()
+		};
+		assert_eq!(logs(&root), 1);
+		assert_eq!(logs(&calls[0]), 0);
+		assert_eq!(logs(&inner_calls[0]), 0);
+	}
+}
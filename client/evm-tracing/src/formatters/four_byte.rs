@@ -0,0 +1,20 @@
+use crate::{listeners::four_byte::Listener, types::single::TransactionTrace};
+
+pub struct Formatter;
+
+impl super::ResponseFormatter for Formatter {
+	type Listener = Listener;
+	type Response = TransactionTrace;
+
+	fn format(listener: Listener) -> Option<TransactionTrace> {
+		Some(TransactionTrace::FourByte(
+			listener
+				.selectors
+				.into_iter()
+				.map(|((selector, size), count)| {
+					(format!("0x{}-{}", hex::encode(selector), size), count)
+				})
+				.collect(),
+		))
+	}
+}
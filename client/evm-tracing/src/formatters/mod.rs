@@ -16,12 +16,18 @@
 
 pub mod blockscout;
 pub mod call_tracer;
+pub mod four_byte;
+pub mod prestate;
 pub mod raw;
+pub mod state_diff;
 pub mod trace_filter;
 
 pub use blockscout::Formatter as Blockscout;
 pub use call_tracer::Formatter as CallTracer;
+pub use four_byte::Formatter as FourByte;
+pub use prestate::Formatter as Prestate;
 pub use raw::Formatter as Raw;
+pub use state_diff::Formatter as StateDiff;
 pub use trace_filter::Formatter as TraceFilter;
 
 use evm_tracing_events::Listener;
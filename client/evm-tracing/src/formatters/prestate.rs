@@ -0,0 +1,191 @@
+use crate::{listeners::prestate::Listener, types::single::TransactionTrace};
+
+use crate::types::serialization::*;
+use serde::Serialize;
+
+use ethereum_types::{H160, H256, U256};
+use parity_scale_codec::{Decode, Encode};
+use sp_std::collections::btree_map::BTreeMap;
+
+pub struct Formatter;
+
+impl super::ResponseFormatter for Formatter {
+	type Listener = Listener;
+	type Response = TransactionTrace;
+
+	fn format(listener: Listener) -> Option<TransactionTrace> {
+		if !listener.diff_mode {
+			return Some(TransactionTrace::Prestate(PrestateTrace::State(listener.pre)))
+		}
+
+		// In diff mode `pre` holds the modified accounts, with the slots modified by the
+		// transaction, and `post` only the modified fields.
+		let mut pre = BTreeMap::new();
+		let mut post = BTreeMap::new();
+		for (address, before) in listener.pre {
+			let after = listener.post.get(&address).cloned().unwrap_or_default();
+			let changes = PrestateAccount {
+				balance: modified(&before.balance, after.balance),
+				nonce: modified(&before.nonce, after.nonce),
+				code: modified(&before.code, after.code),
+				storage: after
+					.storage
+					.into_iter()
+					.filter(|(slot, value)| before.storage.get(slot) != Some(value))
+					.collect(),
+			};
+			if changes == PrestateAccount::default() {
+				continue;
+			}
+
+			let previous = PrestateAccount {
+				storage: before
+					.storage
+					.iter()
+					.filter(|(slot, value)| !value.is_zero() && changes.storage.contains_key(slot))
+					.map(|(slot, value)| (*slot, *value))
+					.collect(),
+				..before
+			};
+			if !previous.is_empty() {
+				pre.insert(address, previous);
+			}
+			post.insert(
+				address,
+				PrestateAccount {
+					storage: changes
+						.storage
+						.into_iter()
+						.filter(|(_, value)| !value.is_zero())
+						.collect(),
+					..changes
+				},
+			);
+		}
+
+		Some(TransactionTrace::Prestate(PrestateTrace::Diff { pre, post }))
+	}
+}
+
+/// The value of a field after the transaction, if it modified it.
+fn modified<T: PartialEq>(before: &Option<T>, after: Option<T>) -> Option<T> {
+	if *before != after {
+		after
+	} else {
+		None
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+	/// The state read by the transaction.
+	State(BTreeMap<H160, PrestateAccount>),
+	/// The state modified by the transaction (`diffMode`).
+	Diff { pre: BTreeMap<H160, PrestateAccount>, post: BTreeMap<H160, PrestateAccount> },
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, Encode, Decode, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	#[serde(serialize_with = "option_bytes_0x_serialize", skip_serializing_if = "Option::is_none")]
+	pub code: Option<Vec<u8>>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<H256, H256>,
+}
+
+impl PrestateAccount {
+	/// Whether the account does not exist, i.e. has no balance, nonce, code nor storage.
+	pub fn is_empty(&self) -> bool {
+		self.balance.unwrap_or_default().is_zero() &&
+			self.nonce.unwrap_or_default() == 0 &&
+			self.code.as_ref().map_or(true, |code| code.is_empty()) &&
+			self.storage.values().all(|value| value.is_zero())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::formatters::ResponseFormatter;
+
+	fn account(balance: u64, nonce: u64, storage: &[(u8, u8)]) -> PrestateAccount {
+		PrestateAccount {
+			balance: Some(balance.into()),
+			nonce: Some(nonce),
+			code: Some(vec![]),
+			storage: storage
+				.iter()
+				.map(|(slot, value)| (H256::repeat_byte(*slot), H256::repeat_byte(*value)))
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn diff_mode_only_keeps_modified_fields() {
+		let sender = H160::repeat_byte(1);
+		let contract = H160::repeat_byte(2);
+		let untouched = H160::repeat_byte(3);
+		let created = H160::repeat_byte(4);
+
+		let mut listener = Listener::new(true);
+		listener.pre = [
+			(sender, account(100, 1, &[])),
+			(contract, account(0, 1, &[(1, 1), (2, 2)])),
+			(untouched, account(10, 0, &[])),
+			(created, account(0, 0, &[])),
+		]
+		.into_iter()
+		.collect();
+		listener.post = [
+			(sender, account(90, 2, &[])),
+			(contract, account(0, 1, &[(1, 1), (2, 3)])),
+			(untouched, account(10, 0, &[])),
+			(created, account(10, 0, &[])),
+		]
+		.into_iter()
+		.collect();
+
+		let Some(TransactionTrace::Prestate(PrestateTrace::Diff { pre, post })) =
+			Formatter::format(listener)
+		else {
+			panic!("expected a diff");
+		};
+
+		assert_eq!(
+			pre,
+			[(sender, account(100, 1, &[])), (contract, account(0, 1, &[(2, 2)]))]
+				.into_iter()
+				.collect()
+		);
+		assert_eq!(
+			post,
+			[
+				(
+					sender,
+					PrestateAccount {
+						balance: Some(90.into()),
+						nonce: Some(2),
+						..Default::default()
+					}
+				),
+				(
+					contract,
+					PrestateAccount {
+						storage: [(H256::repeat_byte(2), H256::repeat_byte(3))]
+							.into_iter()
+							.collect(),
+						..Default::default()
+					}
+				),
+				(created, PrestateAccount { balance: Some(10.into()), ..Default::default() }),
+			]
+			.into_iter()
+			.collect()
+		);
+	}
+}
@@ -0,0 +1,216 @@
+use crate::{formatters::prestate::PrestateAccount, listeners::state_diff::Listener};
+
+use crate::types::serialization::*;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use ethereum_types::{H160, H256, U256};
+use sp_std::collections::btree_map::BTreeMap;
+
+pub struct Formatter;
+
+impl super::ResponseFormatter for Formatter {
+	type Listener = Listener;
+	type Response = Vec<StateDiff>;
+
+	fn format(listener: Listener) -> Option<Vec<StateDiff>> {
+		Some(
+			listener
+				.transactions
+				.into_iter()
+				.map(|transaction| {
+					transaction
+						.pre
+						.into_iter()
+						.filter_map(|(address, before)| {
+							let after = transaction.post.get(&address).cloned().unwrap_or_default();
+							let diff = AccountDiff::new(before, after);
+							(!diff.is_same()).then_some((address, diff))
+						})
+						.collect()
+				})
+				.collect(),
+		)
+	}
+}
+
+/// Changes made by a transaction to the state, by account.
+pub type StateDiff = BTreeMap<H160, AccountDiff>;
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+	pub balance: Diff<U256>,
+	pub nonce: Diff<U256>,
+	pub code: Diff<Code>,
+	pub storage: BTreeMap<H256, Diff<H256>>,
+}
+
+impl AccountDiff {
+	/// Diff of an account from its state before and after a transaction. An empty account is
+	/// considered as not existing, so that accounts created or destroyed are born or died.
+	pub fn new(before: PrestateAccount, after: PrestateAccount) -> Self {
+		let fields = |account: &PrestateAccount| {
+			(
+				account.balance.unwrap_or_default(),
+				U256::from(account.nonce.unwrap_or_default()),
+				Code(account.code.clone().unwrap_or_default()),
+			)
+		};
+		let (balance_before, nonce_before, code_before) = fields(&before);
+		let (balance_after, nonce_after, code_after) = fields(&after);
+
+		match (before.is_empty(), after.is_empty()) {
+			(false, true) => Self {
+				balance: Diff::Died(balance_before),
+				nonce: Diff::Died(nonce_before),
+				code: Diff::Died(code_before),
+				storage: non_zero(before.storage)
+					.map(|(slot, value)| (slot, Diff::Died(value)))
+					.collect(),
+			},
+			(true, false) => Self {
+				balance: Diff::Born(balance_after),
+				nonce: Diff::Born(nonce_after),
+				code: Diff::Born(code_after),
+				storage: non_zero(after.storage)
+					.map(|(slot, value)| (slot, Diff::Born(value)))
+					.collect(),
+			},
+			_ => Self {
+				balance: Diff::new(balance_before, balance_after),
+				nonce: Diff::new(nonce_before, nonce_after),
+				code: Diff::new(code_before, code_after),
+				storage: after
+					.storage
+					.into_iter()
+					.filter_map(|(slot, value)| {
+						let diff = Diff::new(
+							before.storage.get(&slot).cloned().unwrap_or_default(),
+							value,
+						);
+						(diff != Diff::Same).then_some((slot, diff))
+					})
+					.collect(),
+			},
+		}
+	}
+
+	/// Whether the transaction did not modify the account.
+	pub fn is_same(&self) -> bool {
+		self.balance == Diff::Same &&
+			self.nonce == Diff::Same &&
+			self.code == Diff::Same &&
+			self.storage.is_empty()
+	}
+}
+
+fn non_zero(storage: BTreeMap<H256, H256>) -> impl Iterator<Item = (H256, H256)> {
+	storage.into_iter().filter(|(_, value)| !value.is_zero())
+}
+
+/// Change of a value, serialized as Parity does: `"="`, `{"+": value}`, `{"-": value}` or
+/// `{"*": {"from": value, "to": value}}`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Diff<T> {
+	Same,
+	Born(T),
+	Died(T),
+	Changed { from: T, to: T },
+}
+
+impl<T: PartialEq> Diff<T> {
+	pub fn new(from: T, to: T) -> Self {
+		if from == to {
+			Self::Same
+		} else {
+			Self::Changed { from, to }
+		}
+	}
+}
+
+impl<T: Serialize> Serialize for Diff<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		#[derive(Serialize)]
+		struct Changed<'a, T> {
+			from: &'a T,
+			to: &'a T,
+		}
+
+		match self {
+			Self::Same => serializer.serialize_str("="),
+			Self::Born(value) => {
+				let mut map = serializer.serialize_map(Some(1))?;
+				map.serialize_entry("+", value)?;
+				map.end()
+			},
+			Self::Died(value) => {
+				let mut map = serializer.serialize_map(Some(1))?;
+				map.serialize_entry("-", value)?;
+				map.end()
+			},
+			Self::Changed { from, to } => {
+				let mut map = serializer.serialize_map(Some(1))?;
+				map.serialize_entry("*", &Changed { from, to })?;
+				map.end()
+			},
+		}
+	}
+}
+
+/// Code of an account, serialized as `0x` prefixed hex.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize)]
+pub struct Code(#[serde(serialize_with = "bytes_0x_serialize")] pub Vec<u8>);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn account(balance: u64, nonce: u64, storage: &[(u8, u8)]) -> PrestateAccount {
+		PrestateAccount {
+			balance: Some(balance.into()),
+			nonce: Some(nonce),
+			code: Some(vec![]),
+			storage: storage
+				.iter()
+				.map(|(slot, value)| (H256::repeat_byte(*slot), H256::repeat_byte(*value)))
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn serializes_like_parity() {
+		let diff =
+			AccountDiff::new(account(100, 1, &[(1, 1), (2, 2)]), account(90, 1, &[(1, 1), (2, 3)]));
+		assert_eq!(
+			serde_json::to_value(&diff).unwrap(),
+			json!({
+				"balance": { "*": { "from": "0x64", "to": "0x5a" } },
+				"nonce": "=",
+				"code": "=",
+				"storage": {
+					"0x0202020202020202020202020202020202020202020202020202020202020202": {
+						"*": { "from": H256::repeat_byte(2), "to": H256::repeat_byte(3) }
+					}
+				}
+			})
+		);
+	}
+
+	#[test]
+	fn created_accounts_are_born() {
+		let diff = AccountDiff::new(account(0, 0, &[]), account(10, 1, &[(1, 1)]));
+		assert_eq!(
+			diff,
+			AccountDiff {
+				balance: Diff::Born(10.into()),
+				nonce: Diff::Born(1.into()),
+				code: Diff::Born(Code(vec![])),
+				storage: [(H256::repeat_byte(1), Diff::Born(H256::repeat_byte(1)))]
+					.into_iter()
+					.collect(),
+			}
+		);
+		assert!(AccountDiff::new(account(10, 0, &[]), account(10, 0, &[])).is_same());
+	}
+}
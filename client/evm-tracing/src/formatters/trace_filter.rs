@@ -34,12 +34,11 @@ impl super::ResponseFormatter for Formatter {
 	type Response = Vec<TransactionTrace>;
 
 	fn format(mut listener: Listener) -> Option<Vec<TransactionTrace>> {
-		// Remove empty BTreeMaps pushed to `entries`.
-		// I.e. InvalidNonce or other pallet_evm::runner exits
-		listener.entries.retain(|x| !x.is_empty());
 		let mut traces = Vec::new();
-		for (eth_tx_index, entry) in listener.entries.iter().enumerate() {
+		for entry in listener.take_block_entries() {
+			let extrinsic_index = entry.origin.and_then(|origin| origin.extrinsic_index());
 			let mut tx_traces: Vec<_> = entry
+				.calls
 				.iter()
 				.map(|(_, trace)| match trace.inner.clone() {
 					CallInner::Call { input, to, res, call_type } => TransactionTrace {
@@ -66,8 +65,9 @@ impl super::ResponseFormatter for Formatter {
 						subtraces: trace.subtraces,
 						trace_address: trace.trace_address.clone(),
 						// Can't be known here, must be inserted upstream.
-						transaction_hash: H256::default(),
-						transaction_position: eth_tx_index as u32,
+						transaction_hash: None,
+						transaction_position: entry.transaction_position,
+						extrinsic_index,
 					},
 					CallInner::Create { init, res } => {
 						TransactionTrace {
@@ -98,8 +98,9 @@ impl super::ResponseFormatter for Formatter {
 							subtraces: trace.subtraces,
 							trace_address: trace.trace_address.clone(),
 							// Can't be known here, must be inserted upstream.
-							transaction_hash: H256::default(),
-							transaction_position: eth_tx_index as u32,
+							transaction_hash: None,
+							transaction_position: entry.transaction_position,
+							extrinsic_index,
 						}
 					},
 					CallInner::SelfDestruct { balance, to } => TransactionTrace {
@@ -116,8 +117,9 @@ impl super::ResponseFormatter for Formatter {
 						subtraces: trace.subtraces,
 						trace_address: trace.trace_address.clone(),
 						// Can't be known here, must be inserted upstream.
-						transaction_hash: H256::default(),
-						transaction_position: eth_tx_index as u32,
+						transaction_hash: None,
+						transaction_position: entry.transaction_position,
+						extrinsic_index,
 					},
 				})
 				.collect();
@@ -18,4 +18,6 @@
 
 pub mod formatters;
 pub mod listeners;
+pub mod permits;
+pub mod state;
 pub mod types;
@@ -15,13 +15,17 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	formatters::blockscout::{BlockscoutCall as Call, BlockscoutCallInner as CallInner},
+	formatters::{
+		blockscout::{BlockscoutCall as Call, BlockscoutCallInner as CallInner},
+		call_tracer::CallTracerLog,
+	},
 	types::{CallResult, CallType, ContextType, CreateResult},
 };
-use ethereum_types::{H160, U256};
+use ethereum_types::{H160, H256, U256};
 use evm_tracing_events::{
-	runtime::{Capture, ExitError, ExitReason, ExitSucceed},
+	runtime::{Capture, ExitError, ExitReason, ExitSucceed, Memory, Stack},
 	Event, EvmEvent, GasometerEvent, Listener as ListenerT, RuntimeEvent, StepEventFilter,
+	TraceOrigin,
 };
 use std::{collections::btree_map::BTreeMap, vec, vec::Vec};
 
@@ -48,6 +52,9 @@ pub struct Listener {
 
 	// Final logs.
 	pub entries: Vec<BTreeMap<u32, Call>>,
+	/// Origin of each of the `entries`, when received with `Event::CallListNewOrigin`. Older
+	/// runtimes only trace Ethereum transactions and leave it empty.
+	pub origins: Vec<TraceOrigin>,
 	// Next index to use.
 	entries_next_index: u32,
 	// Stack of contexts with data to keep between events.
@@ -80,6 +87,23 @@ pub struct Listener {
 	/// True if only the `GasometerEvent::RecordTransaction` event has been received.
 	/// Allow to correctly handle transactions that cannot pay for the tx data in Legacy mode.
 	record_transaction_event_only: bool,
+
+	/// Capture the logs emitted by each context (callTracer `withLog` option).
+	/// Requires the stack and memory of each step, which is expensive.
+	/// Logs are read from the `LOGn` steps, so the logs emitted by precompiles, which do not
+	/// execute any opcode and are not reported to the EVM tracer, are not captured.
+	pub with_log: bool,
+	/// Only format the root call of each transaction (callTracer `onlyTopCall` option).
+	pub only_top_call: bool,
+}
+
+/// Calls of a traced block originating from the same part of the block.
+pub struct BlockEntry {
+	/// Part of the block the calls originate from, unknown with older runtimes.
+	pub origin: Option<TraceOrigin>,
+	/// Position of the Ethereum transaction in the block, if the calls originate from one.
+	pub transaction_position: Option<u32>,
+	pub calls: BTreeMap<u32, Call>,
 }
 
 struct Context {
@@ -99,6 +123,9 @@ struct Context {
 	data: Vec<u8>,
 	// to / create address
 	to: H160,
+
+	// logs emitted so far, if captured
+	logs: Vec<CallTracerLog>,
 }
 
 impl Default for Listener {
@@ -108,6 +135,7 @@ impl Default for Listener {
 			transaction_cost: 0,
 
 			entries: vec![],
+			origins: vec![],
 			entries_next_index: 0,
 
 			context_stack: vec![],
@@ -117,6 +145,9 @@ impl Default for Listener {
 			skip_next_context: false,
 			call_list_first_transaction: true,
 			record_transaction_event_only: false,
+
+			with_log: false,
+			only_top_call: false,
 		}
 	}
 }
@@ -161,6 +192,8 @@ impl Listener {
 							input: context.data,
 							res,
 						},
+						revert_data: vec![],
+						logs: context.logs,
 					}
 				},
 				ContextType::Create => {
@@ -176,6 +209,8 @@ impl Listener {
 						gas_used: gas_used.into(),
 						from: context.from,
 						inner: CallInner::Create { init: context.data, res },
+						revert_data: vec![],
+						logs: context.logs,
 					}
 				},
 			};
@@ -206,6 +241,8 @@ impl Listener {
 					input: vec![],
 					res,
 				},
+				revert_data: vec![],
+				logs: vec![],
 			};
 
 			self.insert_entry(self.entries_next_index, entry);
@@ -237,6 +274,21 @@ impl Listener {
 
 	pub fn runtime_event(&mut self, event: RuntimeEvent) {
 		match event {
+			RuntimeEvent::Step {
+				context,
+				opcode,
+				stack: Some(stack),
+				memory: Some(memory),
+				..
+			} if self.with_log =>
+				if let Some(parent_context) = self.context_stack.last_mut() {
+					if let Some(log) = step_log(context.address, &opcode, &stack, &memory) {
+						parent_context.logs.push(CallTracerLog {
+							position: parent_context.subtraces.into(),
+							..log
+						});
+					}
+				},
 			RuntimeEvent::StepResult { result: Err(Capture::Trap(opcode)), .. } => {
 				if let Some(ContextType::Call(call_type)) = ContextType::from(opcode) {
 					self.call_type = Some(call_type)
@@ -286,6 +338,8 @@ impl Listener {
 
 					data,
 					to: address,
+
+					logs: vec![],
 				});
 
 				self.entries_next_index += 1;
@@ -310,6 +364,8 @@ impl Listener {
 
 					data: init_code,
 					to: address,
+
+					logs: vec![],
 				});
 
 				self.entries_next_index += 1;
@@ -334,6 +390,8 @@ impl Listener {
 
 					data: init_code,
 					to: address,
+
+					logs: vec![],
 				});
 
 				self.entries_next_index += 1;
@@ -383,6 +441,8 @@ impl Listener {
 
 						data: input.to_vec(),
 						to: code_address,
+
+						logs: vec![],
 					});
 
 					self.entries_next_index += 1;
@@ -426,6 +486,8 @@ impl Listener {
 
 						data: init_code.to_vec(),
 						to: address,
+
+						logs: vec![],
 					});
 
 					self.entries_next_index += 1;
@@ -453,6 +515,8 @@ impl Listener {
 						gas: 0.into(),
 						gas_used: 0.into(),
 						inner: CallInner::SelfDestruct { to: target, balance },
+						revert_data: vec![],
+						logs: vec![],
 					},
 				);
 				self.entries_next_index += 1;
@@ -484,6 +548,45 @@ impl Listener {
 		}
 	}
 
+	/// Take the non empty entries of a traced block, along with their origin and the position of
+	/// their Ethereum transaction, if any. Empty entries come from InvalidNonce or other
+	/// pallet_evm::runner exits.
+	pub fn take_block_entries(&mut self) -> Vec<BlockEntry> {
+		let entries = core::mem::take(&mut self.entries);
+		let origins = core::mem::take(&mut self.origins);
+
+		// Older runtimes only emit `Event::CallListNew` before each Ethereum transaction.
+		if origins.is_empty() {
+			return entries
+				.into_iter()
+				.filter(|calls| !calls.is_empty())
+				.enumerate()
+				.map(|(position, calls)| BlockEntry {
+					origin: None,
+					transaction_position: Some(position as u32),
+					calls,
+				})
+				.collect();
+		}
+
+		let mut next_transaction_position = 0;
+		entries
+			.into_iter()
+			.zip(origins)
+			.filter_map(|(calls, origin)| {
+				let transaction_position = origin.is_ethereum_transaction().then(|| {
+					next_transaction_position += 1;
+					next_transaction_position - 1
+				});
+				(!calls.is_empty()).then_some(BlockEntry {
+					origin: Some(origin),
+					transaction_position,
+					calls,
+				})
+			})
+			.collect()
+	}
+
 	fn insert_entry(&mut self, key: u32, entry: Call) {
 		if let Some(ref mut last) = self.entries.last_mut() {
 			last.insert(key, entry);
@@ -504,6 +607,10 @@ impl Listener {
 			if context.entries_index == 0 {
 				gas_used += self.transaction_cost;
 			}
+			let revert_data = match reason {
+				ExitReason::Revert(_) => return_value.clone(),
+				_ => vec![],
+			};
 
 			Some((
 				context.entries_index,
@@ -533,6 +640,8 @@ impl Listener {
 								input: context.data,
 								res,
 							},
+							revert_data,
+							logs: context.logs,
 						}
 					},
 					ContextType::Create => {
@@ -556,6 +665,8 @@ impl Listener {
 							gas_used: gas_used.into(),
 							from: context.from,
 							inner: CallInner::Create { init: context.data, res },
+							revert_data,
+							logs: context.logs,
 						}
 					},
 				},
@@ -566,6 +677,44 @@ impl Listener {
 	}
 }
 
+/// Memory beyond this size cannot be paid for with a block gas limit, the log would run out of
+/// gas.
+const MAX_LOG_MEMORY: usize = 16 * 1024 * 1024;
+
+/// Reads the log emitted by a `LOGn` step from its stack and memory, which holds the offset and
+/// size of the log data followed by the topics.
+fn step_log(address: H160, opcode: &[u8], stack: &Stack, memory: &Memory) -> Option<CallTracerLog> {
+	let topics_count = match opcode {
+		b"Log0" => 0,
+		b"Log1" => 1,
+		b"Log2" => 2,
+		b"Log3" => 3,
+		b"Log4" => 4,
+		_ => return None,
+	};
+	// The top of the stack is its last item.
+	let mut items = stack.data.iter().rev();
+	let mut next_usize = || {
+		let value = U256::from_big_endian(items.next()?.as_bytes());
+		(value <= U256::from(MAX_LOG_MEMORY)).then(|| value.low_u64() as usize)
+	};
+	let offset = next_usize()?;
+	let size = next_usize()?;
+	let topics: Vec<H256> = stack.data.iter().rev().skip(2).take(topics_count).copied().collect();
+	if topics.len() != topics_count {
+		return None;
+	}
+
+	// Memory is expanded by the log itself, missing bytes are zeros.
+	let mut data = vec![0u8; size];
+	if size > 0 && offset < memory.data.len() {
+		let available = &memory.data[offset..memory.data.len().min(offset + size)];
+		data[..available.len()].copy_from_slice(available);
+	}
+
+	Some(CallTracerLog { address, topics, data, position: U256::zero() })
+}
+
 fn error_message(error: &ExitError) -> Vec<u8> {
 	match error {
 		ExitError::StackUnderflow => "stack underflow",
@@ -600,11 +749,18 @@ impl ListenerT for Listener {
 				} else {
 					self.call_list_first_transaction = false;
 				},
+			Event::CallListNewOrigin(origin) => {
+				self.finish_transaction();
+				self.skip_next_context = false;
+				self.call_list_first_transaction = false;
+				self.entries.push(BTreeMap::new());
+				self.origins.push(origin);
+			},
 		};
 	}
 
 	fn step_event_filter(&self) -> StepEventFilter {
-		StepEventFilter { enable_memory: false, enable_stack: false }
+		StepEventFilter { enable_memory: self.with_log, enable_stack: self.with_log }
 	}
 }
 
@@ -1050,4 +1206,112 @@ mod tests {
 		// nested.
 		assert_eq!(listener.entries[0].len(), (depth * (subdepth + 1)) + 1);
 	}
+
+	// Block traced by a runtime annotating the origin of each part of the block.
+	#[test]
+	fn block_entries_are_annotated_with_their_origin() {
+		let mut listener = Listener::default();
+		listener.event(Event::CallListNewOrigin(TraceOrigin::Initialization));
+		// Ethereum transaction exiting before any EVM execution (e.g. invalid nonce).
+		listener.event(Event::CallListNewOrigin(TraceOrigin::EthereumTransaction(1)));
+		listener.event(Event::CallListNewOrigin(TraceOrigin::EthereumTransaction(2)));
+		do_transact_call_event(&mut listener);
+		do_gasometer_event(&mut listener);
+		do_exit_event(&mut listener);
+		// Substrate extrinsic executing the EVM.
+		listener.event(Event::CallListNewOrigin(TraceOrigin::Extrinsic(3)));
+		do_transact_call_event(&mut listener);
+		do_gasometer_event(&mut listener);
+		do_exit_event(&mut listener);
+		listener.event(Event::CallListNewOrigin(TraceOrigin::Finalization));
+		listener.finish_transaction();
+
+		let entries: Vec<_> = listener
+			.take_block_entries()
+			.into_iter()
+			.map(|entry| (entry.origin, entry.transaction_position, entry.calls.len()))
+			.collect();
+		assert_eq!(
+			entries,
+			vec![
+				(Some(TraceOrigin::EthereumTransaction(2)), Some(1), 1),
+				(Some(TraceOrigin::Extrinsic(3)), None, 1),
+			]
+		);
+	}
+
+	fn do_runtime_log_step_event(listener: &mut Listener, opcode: &[u8], stack: Vec<H256>) {
+		listener.runtime_event(RuntimeEvent::Step {
+			context: EvmContext { address: H160::repeat_byte(0xaa), ..test_context() },
+			opcode: opcode.to_vec(),
+			position: Ok(0u64),
+			stack: Some(Stack { data: stack, limit: 1024 }),
+			memory: Some(Memory {
+				data: b"hello".to_vec(),
+				effective_len: U256::from(32),
+				limit: u64::MAX,
+			}),
+		});
+	}
+
+	fn u256_word(value: u64) -> H256 {
+		H256::from_low_u64_be(value)
+	}
+
+	// Logs are read from the stack and memory of the `LOGn` steps.
+	#[test]
+	fn with_log_captures_log_steps() {
+		let topic = H256::repeat_byte(0x11);
+		let mut listener = Listener { with_log: true, ..Default::default() };
+		do_transact_call_event(&mut listener);
+		do_gasometer_event(&mut listener);
+		do_evm_call_event(&mut listener);
+		// The top of the stack is its last item: offset 0, size 5, then the topic.
+		do_runtime_log_step_event(&mut listener, b"Log1", vec![topic, u256_word(5), u256_word(0)]);
+		// A subcall, then a log reading past the memory.
+		do_evm_call_event(&mut listener);
+		do_exit_event(&mut listener);
+		do_runtime_log_step_event(&mut listener, b"Log0", vec![u256_word(8), u256_word(3)]);
+		// Other opcodes are ignored.
+		do_runtime_log_step_event(&mut listener, b"Add", vec![u256_word(1), u256_word(2)]);
+		do_exit_event(&mut listener);
+		listener.finish_transaction();
+
+		assert_eq!(listener.entries.len(), 1);
+		let root = listener.entries[0]
+			.values()
+			.find(|call| call.trace_address.is_empty())
+			.expect("root call is traced");
+		assert_eq!(
+			root.logs,
+			vec![
+				CallTracerLog {
+					address: H160::repeat_byte(0xaa),
+					topics: vec![topic],
+					data: b"hello".to_vec(),
+					position: U256::zero(),
+				},
+				CallTracerLog {
+					address: H160::repeat_byte(0xaa),
+					topics: vec![],
+					data: b"lo\0\0\0\0\0\0".to_vec(),
+					position: U256::one(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn log_steps_are_ignored_without_with_log() {
+		let mut listener = Listener::default();
+		do_transact_call_event(&mut listener);
+		do_gasometer_event(&mut listener);
+		do_evm_call_event(&mut listener);
+		do_runtime_log_step_event(&mut listener, b"Log0", vec![u256_word(5), u256_word(0)]);
+		do_exit_event(&mut listener);
+		listener.finish_transaction();
+
+		assert_eq!(listener.entries.len(), 1);
+		assert!(listener.entries[0].values().all(|call| call.logs.is_empty()));
+	}
 }
@@ -0,0 +1,80 @@
+//! Listener of Geth's `4byteTracer`, counting the calls made by a transaction by function
+//! selector and input size.
+
+use evm_tracing_events::{Event, EvmEvent, Listener as ListenerT, StepEventFilter};
+use std::collections::btree_map::BTreeMap;
+
+#[derive(Debug, Default)]
+pub struct Listener {
+	/// Number of calls for each selector and size of the input following it.
+	pub selectors: BTreeMap<([u8; 4], usize), u64>,
+}
+
+impl Listener {
+	pub fn using<R, F: FnOnce() -> R>(&mut self, f: F) -> R {
+		evm_tracing_events::using(self, f)
+	}
+
+	pub fn evm_event(&mut self, event: EvmEvent) {
+		match event {
+			// The root call of a transaction emits both `TransactCall` and `Call`, and subcalls
+			// of precompiles both `PrecompileSubcall` and `Call`: only `Call` is counted.
+			EvmEvent::Call { input, .. } if input.len() >= 4 => {
+				let mut selector = [0u8; 4];
+				selector.copy_from_slice(&input[..4]);
+				*self.selectors.entry((selector, input.len() - 4)).or_default() += 1;
+			},
+			// We ignore other kinds of message if any (new ones may be added in the future).
+			_ => (),
+		}
+	}
+}
+
+impl ListenerT for Listener {
+	fn event(&mut self, event: Event) {
+		if let Event::Evm(e) = event {
+			self.evm_event(e);
+		}
+	}
+
+	fn step_event_filter(&self) -> StepEventFilter {
+		StepEventFilter { enable_memory: false, enable_stack: false }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::{H160, U256};
+	use evm_tracing_events::Context;
+
+	fn call(input: Vec<u8>) -> Event {
+		Event::Evm(EvmEvent::Call {
+			code_address: H160::default(),
+			transfer: None,
+			input,
+			target_gas: None,
+			is_static: false,
+			context: Context {
+				address: H160::default(),
+				caller: H160::default(),
+				apparent_value: U256::zero(),
+			},
+		})
+	}
+
+	#[test]
+	fn counts_calls_by_selector_and_size() {
+		let mut listener = Listener::default();
+		listener.event(call(vec![0xa9, 0x05, 0x9c, 0xbb, 0x01, 0x02]));
+		listener.event(call(vec![0xa9, 0x05, 0x9c, 0xbb, 0x03, 0x04]));
+		listener.event(call(vec![0xa9, 0x05, 0x9c, 0xbb]));
+		// Too short to hold a selector.
+		listener.event(call(vec![0x01]));
+
+		assert_eq!(
+			listener.selectors.into_iter().collect::<Vec<_>>(),
+			vec![(([0xa9, 0x05, 0x9c, 0xbb], 0), 1), (([0xa9, 0x05, 0x9c, 0xbb], 2), 2)]
+		);
+	}
+}
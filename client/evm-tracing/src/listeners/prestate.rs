@@ -0,0 +1,91 @@
+//! Listener of Geth's `prestateTracer`.
+//!
+//! The EVM events do not carry the balance, nonce or code of the accounts, so the listener only
+//! collects the accounts and storage slots touched by the transaction. The caller then reads
+//! their state before (and, in diff mode, after) the transaction into `pre` and `post`.
+
+use crate::formatters::prestate::PrestateAccount;
+use ethereum_types::{H160, H256};
+use evm_tracing_events::{Event, EvmEvent, Listener as ListenerT, RuntimeEvent, StepEventFilter};
+use std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+
+#[derive(Debug, Default)]
+pub struct Listener {
+	/// Whether to return the changes made by the transaction rather than the state it read.
+	pub diff_mode: bool,
+	/// Accounts touched by the transaction, with the storage slots it read or wrote.
+	pub touched: BTreeMap<H160, BTreeSet<H256>>,
+	/// State of the touched accounts before the transaction.
+	pub pre: BTreeMap<H160, PrestateAccount>,
+	/// State of the touched accounts after the transaction, only read in diff mode.
+	pub post: BTreeMap<H160, PrestateAccount>,
+}
+
+impl Listener {
+	pub fn new(diff_mode: bool) -> Self {
+		Self { diff_mode, ..Default::default() }
+	}
+
+	pub fn using<R, F: FnOnce() -> R>(&mut self, f: F) -> R {
+		evm_tracing_events::using(self, f)
+	}
+
+	fn touch(&mut self, address: H160) {
+		self.touched.entry(address).or_default();
+	}
+
+	pub fn evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall { caller, address, .. } |
+			EvmEvent::TransactCreate { caller, address, .. } |
+			EvmEvent::TransactCreate2 { caller, address, .. } |
+			EvmEvent::Create { caller, address, .. } => {
+				self.touch(caller);
+				self.touch(address);
+			},
+			EvmEvent::Call { code_address, transfer, context, .. } |
+			EvmEvent::PrecompileSubcall { code_address, transfer, context, .. } => {
+				self.touch(code_address);
+				self.touch(context.address);
+				self.touch(context.caller);
+				if let Some(transfer) = transfer {
+					self.touch(transfer.source);
+					self.touch(transfer.target);
+				}
+			},
+			EvmEvent::Suicide { address, target, .. } => {
+				self.touch(address);
+				self.touch(target);
+			},
+			// We ignore other kinds of message if any (new ones may be added in the future).
+			#[allow(unreachable_patterns)]
+			_ => (),
+		}
+	}
+
+	pub fn runtime_event(&mut self, event: RuntimeEvent) {
+		match event {
+			RuntimeEvent::SLoad { address, index, .. } |
+			RuntimeEvent::SStore { address, index, .. } => {
+				self.touched.entry(address).or_default().insert(index);
+			},
+			// We ignore other kinds of message if any (new ones may be added in the future).
+			#[allow(unreachable_patterns)]
+			_ => (),
+		}
+	}
+}
+
+impl ListenerT for Listener {
+	fn event(&mut self, event: Event) {
+		match event {
+			Event::Evm(e) => self.evm_event(e),
+			Event::Runtime(e) => self.runtime_event(e),
+			_ => {},
+		};
+	}
+
+	fn step_event_filter(&self) -> StepEventFilter {
+		StepEventFilter { enable_memory: false, enable_stack: false }
+	}
+}
@@ -0,0 +1,119 @@
+//! Listener of Parity's `stateDiff` trace type.
+//!
+//! Like the `prestateTracer` listener it only collects the accounts and storage slots touched,
+//! for each Ethereum transaction of the replayed block. The caller then reads their state before
+//! and after each transaction into the `pre` and `post` of the matching `Prestate` listener.
+
+use super::Prestate;
+use evm_tracing_events::{Event, Listener as ListenerT, StepEventFilter};
+
+#[derive(Debug, Default)]
+pub struct Listener {
+	/// Accounts touched by each Ethereum transaction of the block, in order.
+	pub transactions: Vec<Prestate>,
+	/// Whether the EVM is executed by something else than an Ethereum transaction.
+	skip: bool,
+}
+
+impl Listener {
+	pub fn using<R, F: FnOnce() -> R>(&mut self, f: F) -> R {
+		evm_tracing_events::using(self, f)
+	}
+
+	fn current(&mut self) -> &mut Prestate {
+		if self.transactions.is_empty() {
+			self.transactions.push(Prestate::new(true));
+		}
+		self.transactions.last_mut().expect("a transaction was pushed above; qed")
+	}
+}
+
+impl ListenerT for Listener {
+	fn event(&mut self, event: Event) {
+		match event {
+			// Emitted before each Ethereum transaction of a traced block.
+			Event::CallListNew() => self.transactions.push(Prestate::new(true)),
+			// Emitted before each part of a traced block by newer runtimes.
+			Event::CallListNewOrigin(origin) => {
+				self.skip = !origin.is_ethereum_transaction();
+				if !self.skip {
+					self.transactions.push(Prestate::new(true));
+				}
+			},
+			Event::Evm(e) if !self.skip => self.current().evm_event(e),
+			Event::Runtime(e) if !self.skip => self.current().runtime_event(e),
+			_ => {},
+		};
+	}
+
+	fn step_event_filter(&self) -> StepEventFilter {
+		StepEventFilter { enable_memory: false, enable_stack: false }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::{H160, H256, U256};
+	use evm_tracing_events::{EvmEvent, RuntimeEvent, TraceOrigin};
+
+	fn transact(caller: u8, address: u8) -> Event {
+		Event::Evm(EvmEvent::TransactCall {
+			caller: H160::repeat_byte(caller),
+			address: H160::repeat_byte(address),
+			value: U256::zero(),
+			data: vec![],
+			gas_limit: 21_000,
+		})
+	}
+
+	#[test]
+	fn splits_touched_accounts_by_transaction() {
+		let mut listener = Listener::default();
+		listener.event(Event::CallListNew());
+		listener.event(transact(1, 2));
+		listener.event(Event::Runtime(RuntimeEvent::SLoad {
+			address: H160::repeat_byte(2),
+			index: H256::repeat_byte(7),
+			value: H256::zero(),
+		}));
+		listener.event(Event::CallListNew());
+		listener.event(transact(3, 4));
+
+		let touched: Vec<Vec<_>> = listener
+			.transactions
+			.into_iter()
+			.map(|transaction| transaction.touched.into_iter().collect())
+			.collect();
+		assert_eq!(
+			touched,
+			vec![
+				vec![
+					(H160::repeat_byte(1), Default::default()),
+					(H160::repeat_byte(2), [H256::repeat_byte(7)].into_iter().collect()),
+				],
+				vec![
+					(H160::repeat_byte(3), Default::default()),
+					(H160::repeat_byte(4), Default::default()),
+				],
+			]
+		);
+	}
+
+	#[test]
+	fn ignores_executions_out_of_ethereum_transactions() {
+		let mut listener = Listener::default();
+		listener.event(Event::CallListNewOrigin(TraceOrigin::Initialization));
+		listener.event(transact(1, 2));
+		listener.event(Event::CallListNewOrigin(TraceOrigin::EthereumTransaction(1)));
+		listener.event(transact(3, 4));
+		listener.event(Event::CallListNewOrigin(TraceOrigin::Extrinsic(2)));
+		listener.event(transact(5, 6));
+
+		assert_eq!(listener.transactions.len(), 1);
+		assert_eq!(
+			listener.transactions[0].touched.keys().copied().collect::<Vec<_>>(),
+			vec![H160::repeat_byte(3), H160::repeat_byte(4)]
+		);
+	}
+}
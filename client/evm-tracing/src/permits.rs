@@ -0,0 +1,451 @@
+//! Permits shared by the tracing RPCs, bounding the number of concurrent tracing tasks, the
+//! number of requests waiting for one and the duration of each request.
+//!
+//! The deadline of a request covers both the wait for a permit and the traced runtime execution,
+//! which is told to abort itself once the deadline is exceeded. Runtimes which predate this
+//! keep executing in the background until completion, holding their permit.
+//!
+//! A request tracing several blocks, such as `trace_filter`, takes a single slot of the queue and
+//! a single deadline for all its blocks, of which only one waits for a permit at a time, so a
+//! large request does not line up all its blocks ahead of the other requests.
+//!
+//! The permits are shared by all the connections: jsonrpsee does not expose the connection a
+//! method call comes from, so there is no per-connection quota. A client filling the queue gets
+//! the requests of every client rejected until it drains, which only lasts up to the timeout;
+//! operators exposing tracing publicly should rate-limit clients in front of the node.
+
+use std::{
+	fmt,
+	future::Future,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	time::{Duration, Instant},
+};
+use substrate_prometheus_endpoint::{
+	exponential_buckets, register, CounterVec, Gauge, HistogramOpts, HistogramVec, Opts,
+	PrometheusError, Registry as PrometheusRegistry, U64,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Reasons a tracing request is not served.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TracingError {
+	/// Too many requests are already waiting for a permit.
+	QueueFull,
+	/// The request has not completed before its deadline.
+	TimedOut(Duration),
+	/// The blocking task tracing the request panicked.
+	Panicked(String),
+}
+
+impl fmt::Display for TracingError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::QueueFull => write!(f, "Too many tracing requests queued, retry later"),
+			Self::TimedOut(timeout) => write!(f, "Tracing timed out after {:?}", timeout),
+			Self::Panicked(e) => write!(f, "Internal error on spawned task : {}", e),
+		}
+	}
+}
+
+pub struct TracingPermits {
+	semaphore: Arc<Semaphore>,
+	queued: AtomicUsize,
+	max_queued: usize,
+	max_timeout: Duration,
+	metrics: Option<Metrics>,
+}
+
+impl TracingPermits {
+	/// `permits` tracing tasks run concurrently, `max_queued` requests may wait for a permit, and
+	/// no request lasts more than `max_timeout`.
+	pub fn new(
+		permits: usize,
+		max_queued: usize,
+		max_timeout: Duration,
+		prometheus: Option<&PrometheusRegistry>,
+	) -> Self {
+		let metrics = prometheus.and_then(|registry| match Metrics::register(registry) {
+			Ok(metrics) => Some(metrics),
+			Err(err) => {
+				log::error!(target: "tracing", "Failed to register metrics {err:?}");
+				None
+			},
+		});
+
+		Self {
+			semaphore: Arc::new(Semaphore::new(permits)),
+			queued: AtomicUsize::new(0),
+			max_queued,
+			max_timeout,
+			metrics,
+		}
+	}
+
+	pub fn available_permits(&self) -> usize {
+		self.semaphore.available_permits()
+	}
+
+	/// Waits for a permit for the `request` (used as metrics label), which must complete within
+	/// `timeout`, capped to the maximum timeout.
+	pub async fn acquire(
+		&self,
+		request: &'static str,
+		timeout: Option<Duration>,
+	) -> Result<TracingPermit, TracingError> {
+		let timeout = timeout.map_or(self.max_timeout, |timeout| timeout.min(self.max_timeout));
+		let deadline = Instant::now() + timeout;
+
+		let queued = Queued::new(self, request)?;
+
+		let permit =
+			tokio::time::timeout_at(deadline.into(), Arc::clone(&self.semaphore).acquire_owned())
+				.await;
+		drop(queued);
+
+		match permit {
+			Ok(Ok(permit)) => Ok(TracingPermit {
+				_permit: permit,
+				request,
+				timeout,
+				deadline,
+				metrics: self.metrics.clone(),
+			}),
+			// The semaphore is never closed.
+			Ok(Err(_)) | Err(_) => Err(self.timed_out(request, timeout)),
+		}
+	}
+
+	/// Starts a batch of tasks traced for the `request`, such as the blocks of a `trace_filter`,
+	/// which takes a single slot of the queue until all its handles are dropped, and must complete
+	/// within the maximum timeout.
+	pub fn acquire_batch(
+		self: &Arc<Self>,
+		request: &'static str,
+	) -> Result<TracingBatch, TracingError> {
+		self.enqueue(request)?;
+
+		Ok(TracingBatch(Arc::new(Batch {
+			permits: Arc::clone(self),
+			waiting: Semaphore::new(1),
+			request,
+			timeout: self.max_timeout,
+			deadline: Instant::now() + self.max_timeout,
+		})))
+	}
+
+	/// Takes a slot of the queue, unless it is full.
+	fn enqueue(&self, request: &'static str) -> Result<(), TracingError> {
+		if self.queued.fetch_add(1, Ordering::SeqCst) >= self.max_queued {
+			self.queued.fetch_sub(1, Ordering::SeqCst);
+			if let Some(metrics) = &self.metrics {
+				metrics.rejected_requests.with_label_values(&[request]).inc();
+			}
+			return Err(TracingError::QueueFull)
+		}
+		if let Some(metrics) = &self.metrics {
+			metrics.queued_requests.inc();
+		}
+		Ok(())
+	}
+
+	fn dequeue(&self) {
+		self.queued.fetch_sub(1, Ordering::SeqCst);
+		if let Some(metrics) = &self.metrics {
+			metrics.queued_requests.dec();
+		}
+	}
+
+	fn timed_out(&self, request: &'static str, timeout: Duration) -> TracingError {
+		if let Some(metrics) = &self.metrics {
+			metrics.timed_out_requests.with_label_values(&[request]).inc();
+		}
+		TracingError::TimedOut(timeout)
+	}
+}
+
+/// A request waiting for a permit.
+struct Queued<'a>(&'a TracingPermits);
+
+impl<'a> Queued<'a> {
+	fn new(permits: &'a TracingPermits, request: &'static str) -> Result<Self, TracingError> {
+		permits.enqueue(request)?;
+		Ok(Self(permits))
+	}
+}
+
+impl Drop for Queued<'_> {
+	fn drop(&mut self) {
+		self.0.dequeue();
+	}
+}
+
+/// Handle on a batch of tasks sharing a slot of the queue and a deadline.
+#[derive(Clone)]
+pub struct TracingBatch(Arc<Batch>);
+
+struct Batch {
+	permits: Arc<TracingPermits>,
+	/// Lets a single task of the batch wait for a permit at a time.
+	waiting: Semaphore,
+	request: &'static str,
+	timeout: Duration,
+	deadline: Instant,
+}
+
+impl Drop for Batch {
+	fn drop(&mut self) {
+		self.permits.dequeue();
+	}
+}
+
+impl TracingBatch {
+	/// Waits for a permit for a task of the batch, which must complete before the deadline of the
+	/// batch. The tasks of the batch wait for a permit one at a time.
+	pub async fn acquire(&self) -> Result<TracingPermit, TracingError> {
+		let batch = &self.0;
+		let permit = tokio::time::timeout_at(batch.deadline.into(), async {
+			let _waiting = batch.waiting.acquire().await?;
+			Arc::clone(&batch.permits.semaphore).acquire_owned().await
+		})
+		.await;
+
+		match permit {
+			Ok(Ok(permit)) => Ok(TracingPermit {
+				_permit: permit,
+				request: batch.request,
+				timeout: batch.timeout,
+				deadline: batch.deadline,
+				metrics: batch.permits.metrics.clone(),
+			}),
+			// The semaphores are never closed.
+			Ok(Err(_)) | Err(_) => Err(batch.permits.timed_out(batch.request, batch.timeout)),
+		}
+	}
+
+	/// Waits for `future`, giving up at the deadline of the batch.
+	pub async fn until_deadline<F: Future>(&self, future: F) -> Result<F::Output, TracingError> {
+		tokio::time::timeout_at(self.0.deadline.into(), future)
+			.await
+			.map_err(|_| TracingError::TimedOut(self.0.timeout))
+	}
+}
+
+/// Permit to trace a request before its deadline.
+pub struct TracingPermit {
+	_permit: OwnedSemaphorePermit,
+	request: &'static str,
+	timeout: Duration,
+	deadline: Instant,
+	metrics: Option<Metrics>,
+}
+
+impl TracingPermit {
+	/// Traces the request with `f` in a blocking task holding the permit, and aborts the traced
+	/// runtime execution at the deadline.
+	pub async fn run<R, F>(self, f: F) -> Result<R, TracingError>
+	where
+		R: Send + 'static,
+		F: FnOnce() -> R + Send + 'static,
+	{
+		let Self { request, timeout, deadline, .. } = self;
+		let metrics = self.metrics.clone();
+
+		let task = tokio::task::spawn_blocking(move || {
+			let started = Instant::now();
+			if let Some(metrics) = &self.metrics {
+				metrics.running_requests.inc();
+			}
+
+			let result = evm_tracing_events::using_deadline(deadline, f);
+
+			if let Some(metrics) = &self.metrics {
+				metrics.running_requests.dec();
+				metrics
+					.request_duration
+					.with_label_values(&[request])
+					.observe(started.elapsed().as_secs_f64());
+			}
+			// The permit is only released once the runtime execution is over.
+			drop(self);
+			result
+		});
+
+		let result = match tokio::time::timeout_at(deadline.into(), task).await {
+			Ok(Ok((result, false))) => return Ok(result),
+			Ok(Err(e)) => return Err(TracingError::Panicked(format!("{:?}", e))),
+			Ok(Ok((_, true))) | Err(_) => Err(TracingError::TimedOut(timeout)),
+		};
+		if let Some(metrics) = metrics {
+			metrics.timed_out_requests.with_label_values(&[request]).inc();
+		}
+		result
+	}
+}
+
+/// Prometheus metrics of the tracing requests.
+#[derive(Clone)]
+struct Metrics {
+	queued_requests: Gauge<U64>,
+	running_requests: Gauge<U64>,
+	rejected_requests: CounterVec<U64>,
+	timed_out_requests: CounterVec<U64>,
+	request_duration: HistogramVec,
+}
+
+impl Metrics {
+	fn register(registry: &PrometheusRegistry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			queued_requests: register(
+				Gauge::new(
+					"tracing_queued_requests",
+					"Number of tracing requests waiting for a permit, or tracing a batch of blocks.",
+				)?,
+				registry,
+			)?,
+			running_requests: register(
+				Gauge::new("tracing_running_requests", "Number of tracing requests running.")?,
+				registry,
+			)?,
+			rejected_requests: register(
+				CounterVec::new(
+					Opts::new(
+						"tracing_rejected_requests",
+						"Number of tracing requests rejected as too many were queued.",
+					),
+					&["request"],
+				)?,
+				registry,
+			)?,
+			timed_out_requests: register(
+				CounterVec::new(
+					Opts::new(
+						"tracing_timed_out_requests",
+						"Number of tracing requests which did not complete before their deadline.",
+					),
+					&["request"],
+				)?,
+				registry,
+			)?,
+			request_duration: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"tracing_request_duration_seconds",
+						"Time spent tracing requests, once they got a permit.",
+					)
+					.buckets(exponential_buckets(0.01, 2.0, 14)?),
+					&["request"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn rejects_requests_above_the_queue_limit() {
+		let permits = Arc::new(TracingPermits::new(1, 1, Duration::from_secs(10), None));
+
+		let permit = permits.acquire("test", None).await.unwrap();
+		let queued = {
+			let permits = Arc::clone(&permits);
+			tokio::spawn(async move { permits.acquire("test", None).await.map(|_| ()) })
+		};
+		while permits.queued.load(Ordering::SeqCst) == 0 {
+			tokio::task::yield_now().await;
+		}
+
+		assert_eq!(permits.acquire("test", None).await.err(), Some(TracingError::QueueFull));
+		drop(permit);
+		assert_eq!(queued.await.unwrap(), Ok(()));
+	}
+
+	#[tokio::test]
+	async fn times_out_waiting_for_a_permit() {
+		let permits = TracingPermits::new(1, 10, Duration::from_secs(10), None);
+
+		let _permit = permits.acquire("test", None).await.unwrap();
+		let timeout = Duration::from_millis(10);
+		assert_eq!(
+			permits.acquire("test", Some(timeout)).await.err(),
+			Some(TracingError::TimedOut(timeout))
+		);
+	}
+
+	#[tokio::test]
+	async fn batches_take_a_single_queue_slot() {
+		let permits = Arc::new(TracingPermits::new(1, 1, Duration::from_secs(10), None));
+
+		let batch = permits.acquire_batch("test").unwrap();
+		let _permit = batch.acquire().await.unwrap();
+		assert_eq!(permits.queued.load(Ordering::SeqCst), 1);
+		assert_eq!(permits.acquire_batch("test").err(), Some(TracingError::QueueFull));
+		assert_eq!(permits.acquire("test", None).await.err(), Some(TracingError::QueueFull));
+
+		drop(batch);
+		assert_eq!(permits.queued.load(Ordering::SeqCst), 0);
+	}
+
+	#[tokio::test]
+	async fn batch_tasks_wait_for_a_permit_one_at_a_time() {
+		let permits = Arc::new(TracingPermits::new(1, 10, Duration::from_secs(10), None));
+		let batch = permits.acquire_batch("test").unwrap();
+		let permit = permits.acquire("test", None).await.unwrap();
+
+		let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+		for task in ["block", "block"] {
+			let (batch, tx) = (batch.clone(), tx.clone());
+			tokio::spawn(async move {
+				let _permit = batch.acquire().await.unwrap();
+				tx.send(task).unwrap();
+				tokio::time::sleep(Duration::from_millis(10)).await;
+			});
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+		{
+			let (permits, tx) = (Arc::clone(&permits), tx.clone());
+			tokio::spawn(async move {
+				let _permit = permits.acquire("test", None).await.unwrap();
+				tx.send("request").unwrap();
+			});
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+
+		// The second block waits behind the request queued after the first one.
+		drop(permit);
+		let mut order = Vec::new();
+		for _ in 0..3 {
+			order.push(rx.recv().await.unwrap());
+		}
+		assert_eq!(order, ["block", "request", "block"]);
+	}
+
+	#[tokio::test]
+	async fn batches_time_out() {
+		let timeout = Duration::from_millis(10);
+		let permits = Arc::new(TracingPermits::new(1, 10, timeout, None));
+
+		let batch = permits.acquire_batch("test").unwrap();
+		let _permit = permits.acquire("test", None).await.unwrap();
+		assert_eq!(batch.acquire().await.err(), Some(TracingError::TimedOut(timeout)));
+		assert_eq!(
+			batch.until_deadline(tokio::time::sleep(Duration::from_millis(100))).await,
+			Err(TracingError::TimedOut(timeout))
+		);
+	}
+
+	#[tokio::test]
+	async fn times_out_running() {
+		let permits = TracingPermits::new(1, 10, Duration::from_millis(10), None);
+
+		let permit = permits.acquire("test", Some(Duration::from_secs(10))).await.unwrap();
+		let result = permit.run(|| std::thread::sleep(Duration::from_millis(100))).await;
+		assert_eq!(result, Err(TracingError::TimedOut(Duration::from_millis(10))));
+	}
+}
@@ -0,0 +1,46 @@
+//! Reads the state of the accounts touched by a traced transaction, shared by the prestate and
+//! state diff tracers.
+
+use crate::formatters::prestate::PrestateAccount;
+use ethereum_types::{H160, H256, U256};
+use fp_rpc::EthereumRuntimeRPCApi;
+use sp_api::ApiError;
+use sp_runtime::traits::Block as BlockT;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Reads the balance, nonce, code and given storage slots of the accounts at `at`, through the
+/// runtime API `api`, whose state may have been changed by the extrinsics it applied.
+pub fn accounts_state<B, Api>(
+	api: &Api,
+	at: B::Hash,
+	touched: &BTreeMap<H160, BTreeSet<H256>>,
+) -> Result<BTreeMap<H160, PrestateAccount>, ApiError>
+where
+	B: BlockT,
+	Api: EthereumRuntimeRPCApi<B> + ?Sized,
+{
+	touched
+		.iter()
+		.map(|(address, slots)| {
+			let account = api.account_basic(at, *address)?;
+			let code = api.account_code_at(at, *address)?;
+			let storage = slots
+				.iter()
+				.map(|slot| {
+					let index = U256::from_big_endian(slot.as_bytes());
+					Ok((*slot, api.storage_at(at, *address, index)?))
+				})
+				.collect::<Result<_, ApiError>>()?;
+
+			Ok((
+				*address,
+				PrestateAccount {
+					balance: Some(account.balance),
+					nonce: Some(account.nonce.low_u64()),
+					code: Some(code),
+					storage,
+				},
+			))
+		})
+		.collect()
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Types for tracing all Ethereum transactions of a block.
+//! Types for tracing all EVM executions of a block.
 
 use super::serialization::*;
 use serde::Serialize;
@@ -35,9 +35,15 @@ pub struct TransactionTrace {
 	pub output: TransactionTraceOutput,
 	pub subtraces: u32,
 	pub trace_address: Vec<u32>,
-	#[serde(serialize_with = "h256_0x_serialize")]
-	pub transaction_hash: H256,
-	pub transaction_position: u32,
+	/// Hash of the Ethereum transaction, `None` if the EVM was executed by something else.
+	pub transaction_hash: Option<H256>,
+	/// Position of the Ethereum transaction in the block, `None` if the EVM was executed by
+	/// something else.
+	pub transaction_position: Option<u32>,
+	/// Index of the extrinsic which executed the EVM, `None` during the block initialization or
+	/// finalization (e.g. XCM messages), or if unknown.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub extrinsic_index: Option<u32>,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
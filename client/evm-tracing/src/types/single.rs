@@ -41,6 +41,10 @@ pub enum TraceType {
 	CallList,
 	/// A single block trace. Use in `debug_traceTransactionByNumber` / `traceTransactionByHash`.
 	Block,
+	/// State of the accounts touched by the transaction, used by Geth's prestateTracer.
+	Prestate { diff_mode: bool },
+	/// Calls counted by function selector, used by Geth's 4byteTracer.
+	FourByte,
 }
 
 /// Single transaction trace.
@@ -61,6 +65,10 @@ pub enum TransactionTrace {
 	CallList(Vec<Call>),
 	/// Used by Geth's callTracer.
 	CallListNested(Call),
+	/// Used by Geth's prestateTracer.
+	Prestate(crate::formatters::prestate::PrestateTrace),
+	/// Used by Geth's 4byteTracer.
+	FourByte(BTreeMap<String, u64>),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, Serialize)]
@@ -13,11 +13,13 @@
 
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
-use ethereum_types::H256;
+use ethereum_types::{H160, H256, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use peaq_client_evm_tracing::types::single;
 use peaq_rpc_core_types::RequestBlockId;
 use serde::Deserialize;
+use sp_core::Bytes;
+use std::time::Duration;
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,11 +27,98 @@ pub struct TraceParams {
 	pub disable_storage: Option<bool>,
 	pub disable_memory: Option<bool>,
 	pub disable_stack: Option<bool>,
-	/// Javascript tracer (we just check if it's Blockscout tracer string)
+	/// Javascript tracer (we just check if it's Blockscout tracer string) or built-in tracer name
 	pub tracer: Option<String>,
+	pub tracer_config: Option<TracerConfig>,
+	/// Maximum duration of the request, as a Go duration string, e.g. `"5s"` or `"1m30s"`.
 	pub timeout: Option<String>,
 }
 
+impl TraceParams {
+	/// The requested timeout, if any.
+	pub fn timeout(&self) -> Result<Option<Duration>, String> {
+		self.timeout.as_deref().map(parse_duration).transpose()
+	}
+}
+
+/// Parses a Go duration string, a sequence of decimal numbers with a unit suffix.
+fn parse_duration(duration: &str) -> Result<Duration, String> {
+	let invalid = || format!("invalid timeout {:?}", duration);
+	if duration.is_empty() {
+		return Err(invalid())
+	}
+	if duration == "0" {
+		return Ok(Duration::ZERO)
+	}
+
+	let mut rest = duration;
+	let mut nanos = 0f64;
+	while !rest.is_empty() {
+		let number_len =
+			rest.find(|c: char| !c.is_ascii_digit() && c != '.').ok_or_else(invalid)?;
+		let number: f64 = rest[..number_len].parse().map_err(|_| invalid())?;
+		rest = &rest[number_len..];
+
+		let unit_len = rest.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(rest.len());
+		let unit = match &rest[..unit_len] {
+			"ns" => 1.0,
+			"us" | "µs" => 1e3,
+			"ms" => 1e6,
+			"s" => 1e9,
+			"m" => 60e9,
+			"h" => 3600e9,
+			_ => return Err(invalid()),
+		};
+		rest = &rest[unit_len..];
+
+		nanos += number * unit;
+	}
+
+	if nanos >= u64::MAX as f64 {
+		return Err(invalid())
+	}
+	Ok(Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Options of the built-in tracers.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerConfig {
+	/// `prestateTracer`: return the state modified by the transaction instead of the state
+	/// it read.
+	pub diff_mode: Option<bool>,
+	/// `callTracer`: only trace the top-level call of the transaction.
+	pub only_top_call: Option<bool>,
+	/// `callTracer`: include the logs emitted by each call. The logs emitted by precompiles
+	/// are not captured.
+	pub with_log: Option<bool>,
+}
+
+/// The call to trace with `debug_traceCall`, in the format of an `eth_call` request. Without
+/// `to`, the data is the init code of a contract creation.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallParams {
+	pub from: Option<H160>,
+	pub to: Option<H160>,
+	pub gas_price: Option<U256>,
+	pub max_fee_per_gas: Option<U256>,
+	pub max_priority_fee_per_gas: Option<U256>,
+	pub gas: Option<U256>,
+	pub value: Option<U256>,
+	#[serde(alias = "input")]
+	pub data: Option<Bytes>,
+	pub nonce: Option<U256>,
+	pub access_list: Option<Vec<AccessListItem>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+	pub address: H160,
+	pub storage_keys: Vec<H256>,
+}
+
 #[rpc(server)]
 #[jsonrpsee::core::async_trait]
 pub trait Debug {
@@ -45,4 +134,49 @@ pub trait Debug {
 		id: RequestBlockId,
 		params: Option<TraceParams>,
 	) -> RpcResult<Vec<single::TransactionTrace>>;
+	/// Traces a call on top of the state the block `id` is built on, that is the state of its
+	/// parent, in the environment (number, timestamp, author) of the block. Unlike geth, the
+	/// transactions of the block itself are not applied before the call.
+	#[method(name = "debug_traceCall")]
+	async fn trace_call(
+		&self,
+		call_params: TraceCallParams,
+		id: RequestBlockId,
+		params: Option<TraceParams>,
+	) -> RpcResult<single::TransactionTrace>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_go_durations() {
+		assert_eq!(parse_duration("0"), Ok(Duration::ZERO));
+		assert_eq!(parse_duration("300ms"), Ok(Duration::from_millis(300)));
+		assert_eq!(parse_duration("5s"), Ok(Duration::from_secs(5)));
+		assert_eq!(parse_duration("1m30s"), Ok(Duration::from_secs(90)));
+		assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(5400)));
+
+		for invalid in ["", "5", "s", "5 s", "-5s", "5d", "1..5s"] {
+			assert!(parse_duration(invalid).is_err(), "{invalid:?}");
+		}
+	}
+
+	#[test]
+	fn parses_call_params_without_to() {
+		let params: TraceCallParams = serde_json::from_str(
+			r#"{"from":"0x0000000000000000000000000000000000000001","input":"0x6080"}"#,
+		)
+		.unwrap();
+		assert_eq!(params.from, Some(H160::from_low_u64_be(1)));
+		assert_eq!(params.to, None);
+		assert_eq!(params.data, Some(Bytes(vec![0x60, 0x80])));
+
+		let params: TraceCallParams = serde_json::from_str(
+			r#"{"to":"0x0000000000000000000000000000000000000002","data":"0x"}"#,
+		)
+		.unwrap();
+		assert_eq!(params.to, Some(H160::from_low_u64_be(2)));
+	}
 }
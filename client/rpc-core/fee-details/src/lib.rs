@@ -0,0 +1,28 @@
+use ethereum_types::H256;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+mod types;
+
+pub use crate::types::{ExtrinsicFeeDetails, FeePayment, FeeSwap, RewardDistribution};
+
+#[rpc(server)]
+#[jsonrpsee::core::async_trait]
+pub trait FeeDetails<AccountId, AssetId> {
+	/// How the fee of the extrinsic at `extrinsic_index` of the block `block_hash` was paid and
+	/// distributed, `null` if there is no such extrinsic. Replaying the block takes one of the
+	/// tracing permits of the node.
+	#[method(name = "peaq_feeDetails")]
+	async fn fee_details(
+		&self,
+		block_hash: H256,
+		extrinsic_index: u32,
+	) -> RpcResult<Option<ExtrinsicFeeDetails<AccountId, AssetId>>>;
+
+	/// How the fee of the extrinsic applying the given Ethereum transaction was paid and
+	/// distributed, `null` if the transaction is unknown.
+	#[method(name = "peaq_ethereumFeeDetails")]
+	async fn ethereum_fee_details(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<ExtrinsicFeeDetails<AccountId, AssetId>>>;
+}
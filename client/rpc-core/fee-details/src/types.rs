@@ -0,0 +1,147 @@
+use ethereum_types::{H256, U256};
+use peaq_primitives_xcm::Balance;
+use serde::Serialize;
+
+/// How the fee of an extrinsic was paid and distributed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicFeeDetails<AccountId, AssetId> {
+	/// Hash of the Substrate block of the extrinsic.
+	pub block_hash: H256,
+	/// Index of the extrinsic in its block.
+	pub extrinsic_index: u32,
+	/// Fee paid through the transaction payment pallet, `null` for the extrinsics which do not
+	/// pay through it, e.g. inherents and Ethereum transactions.
+	pub payment: Option<FeePayment<AccountId, AssetId>>,
+	/// Fees distributed by the block reward pallet while applying the extrinsic.
+	pub distributed: U256,
+	/// Split of the distributed fees between the beneficiaries.
+	pub distribution: RewardDistribution,
+}
+
+/// Fee paid through the transaction payment pallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePayment<AccountId, AssetId> {
+	pub payer: AccountId,
+	/// Asset the fee was paid with.
+	pub fee_asset: AssetId,
+	/// Swap of the fee asset into the native token, if it is not the native token.
+	pub swap: Option<FeeSwap>,
+	/// Fee withdrawn before dispatch, including the tip and the EoT fee.
+	pub withdrawn: U256,
+	/// Fee charged after dispatch, including the tip but not the EoT fee.
+	pub fee: U256,
+	pub tip: U256,
+	/// Economy-of-Things fee charged on top of the fee.
+	pub eot_fee: U256,
+	/// Part of the withdrawn fee refunded after dispatch.
+	pub refund: U256,
+}
+
+/// Swap of the fee asset into the native token through Zenlink.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeSwap {
+	pub amount_in: U256,
+	pub amount_out: U256,
+}
+
+/// Split of an amount between the beneficiaries of the block reward pallet.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardDistribution {
+	pub treasury: U256,
+	pub collators_delegators: U256,
+	pub coretime: U256,
+	pub subsidization_pool: U256,
+	pub depin_staking: U256,
+	pub depin_incentivization: U256,
+}
+
+impl<AccountId, AssetId> ExtrinsicFeeDetails<AccountId, AssetId> {
+	pub fn new(
+		block_hash: H256,
+		details: peaq_primitives_fee::FeeDetails<AccountId, AssetId, Balance>,
+	) -> Self {
+		Self {
+			block_hash,
+			extrinsic_index: details.extrinsic_index,
+			payment: details.payment.map(Into::into),
+			distributed: details.distributed.into(),
+			distribution: details.distribution.into(),
+		}
+	}
+}
+
+impl<AccountId, AssetId> From<peaq_primitives_fee::FeePayment<AccountId, AssetId, Balance>>
+	for FeePayment<AccountId, AssetId>
+{
+	fn from(payment: peaq_primitives_fee::FeePayment<AccountId, AssetId, Balance>) -> Self {
+		Self {
+			payer: payment.payer,
+			fee_asset: payment.fee_asset,
+			swap: payment.swap.map(|swap| FeeSwap {
+				amount_in: swap.amount_in.into(),
+				amount_out: swap.amount_out.into(),
+			}),
+			withdrawn: payment.withdrawn.into(),
+			fee: payment.fee.into(),
+			tip: payment.tip.into(),
+			eot_fee: payment.eot_fee.into(),
+			refund: payment.refund.into(),
+		}
+	}
+}
+
+impl From<peaq_primitives_fee::RewardDistribution<Balance>> for RewardDistribution {
+	fn from(distribution: peaq_primitives_fee::RewardDistribution<Balance>) -> Self {
+		Self {
+			treasury: distribution.treasury.into(),
+			collators_delegators: distribution.collators_delegators.into(),
+			coretime: distribution.coretime.into(),
+			subsidization_pool: distribution.subsidization_pool.into(),
+			depin_staking: distribution.depin_staking.into(),
+			depin_incentivization: distribution.depin_incentivization.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn serializes_balances_as_hex_in_camel_case() {
+		let details = ExtrinsicFeeDetails::new(
+			H256::zero(),
+			peaq_primitives_fee::FeeDetails {
+				extrinsic_index: 2,
+				payment: Some(peaq_primitives_fee::FeePayment {
+					payer: "alice",
+					fee_asset: 1u64,
+					swap: Some(peaq_primitives_fee::FeeSwap { amount_in: 30, amount_out: 11 }),
+					withdrawn: 11,
+					fee: 8,
+					tip: 1,
+					eot_fee: 0,
+					refund: 3,
+				}),
+				distributed: 8,
+				distribution: peaq_primitives_fee::RewardDistribution {
+					treasury: 8,
+					..Default::default()
+				},
+			},
+		);
+
+		let json = serde_json::to_value(&details).unwrap();
+		assert_eq!(json["extrinsicIndex"], 2);
+		assert_eq!(json["payment"]["feeAsset"], 1);
+		assert_eq!(json["payment"]["swap"]["amountIn"], "0x1e");
+		assert_eq!(json["payment"]["eotFee"], "0x0");
+		assert_eq!(json["distributed"], "0x8");
+		assert_eq!(json["distribution"]["treasury"], "0x8");
+		assert_eq!(json["distribution"]["collatorsDelegators"], "0x0");
+	}
+}
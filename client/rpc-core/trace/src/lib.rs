@@ -14,17 +14,55 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-use ethereum_types::H160;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use peaq_client_evm_tracing::types::block::TransactionTrace;
+use ethereum_types::{H160, H256, U256};
+use jsonrpsee::{
+	core::{RpcResult, SubscriptionResult},
+	proc_macros::rpc,
+};
+use peaq_client_evm_tracing::{
+	formatters::state_diff::StateDiff,
+	types::{block::TransactionTrace, serialization::*},
+};
 use peaq_rpc_core_types::RequestBlockId;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[rpc(server)]
 #[jsonrpsee::core::async_trait]
 pub trait Trace {
+	/// Returns the traces of the blocks in the range matching the filter. Ranges of more blocks
+	/// than the maximum of the node are rejected.
 	#[method(name = "trace_filter")]
 	async fn filter(&self, filter: FilterRequest) -> RpcResult<Vec<TransactionTrace>>;
+
+	#[method(name = "trace_block")]
+	async fn block(&self, block: RequestBlockId) -> RpcResult<Option<Vec<TransactionTrace>>>;
+
+	#[method(name = "trace_transaction")]
+	async fn transaction(&self, transaction_hash: H256)
+		-> RpcResult<Option<Vec<TransactionTrace>>>;
+
+	#[method(name = "trace_get")]
+	async fn get(
+		&self,
+		transaction_hash: H256,
+		indices: Vec<U256>,
+	) -> RpcResult<Option<TransactionTrace>>;
+
+	#[method(name = "trace_replayBlockTransactions")]
+	async fn replay_block_transactions(
+		&self,
+		block: RequestBlockId,
+		trace_types: Vec<TraceType>,
+	) -> RpcResult<Vec<TransactionReplay>>;
+
+	/// Pushes the traces of each new best (or finalized) block, filtered by address. Subscriptions
+	/// above the concurrent limit of the node are rejected.
+	#[subscription(
+		name = "trace_subscribe" => "trace_subscription",
+		unsubscribe = "trace_unsubscribe",
+		item = Vec<TransactionTrace>
+	)]
+	async fn subscribe(&self, request: Option<SubscribeRequest>) -> SubscriptionResult;
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
@@ -48,3 +86,42 @@ pub struct FilterRequest {
 	/// (optional) Integer number of traces to display in a batch.
 	pub count: Option<u32>,
 }
+
+#[derive(Clone, Eq, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeRequest {
+	/// (optional) Sent from these addresses.
+	pub from_address: Option<Vec<H160>>,
+
+	/// (optional) Sent to these addresses.
+	pub to_address: Option<Vec<H160>>,
+
+	/// (optional) Push the traces of finalized blocks rather than of new best blocks.
+	#[serde(default)]
+	pub finalized: bool,
+}
+
+/// Kind of traces returned when replaying transactions.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+	/// The calls made by the transaction, as in `trace_filter`.
+	Trace,
+	/// The VM execution of the transaction, which is not supported.
+	VmTrace,
+	/// The changes made by the transaction to the state.
+	StateDiff,
+}
+
+/// Result of the replay of a transaction by `trace_replayBlockTransactions`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReplay {
+	#[serde(serialize_with = "bytes_0x_serialize")]
+	pub output: Vec<u8>,
+	pub state_diff: Option<StateDiff>,
+	pub trace: Vec<TransactionTrace>,
+	pub vm_trace: Option<()>,
+	#[serde(serialize_with = "h256_0x_serialize")]
+	pub transaction_hash: H256,
+}
@@ -14,14 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-use ethereum_types::U256;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use ethereum_types::{H160, U256};
+use jsonrpsee::{
+	core::{RpcResult, SubscriptionResult},
+	proc_macros::rpc,
+};
+use std::collections::HashMap;
 
 mod types;
 
-pub use crate::types::{Get as GetT, Summary, Transaction, TransactionMap, TxPoolResult};
+pub use crate::types::{
+	Get as GetT, NonceGaps, NonceRange, Summary, Transaction, TransactionMap, TxPoolResult,
+};
 
 #[rpc(server)]
+#[jsonrpsee::core::async_trait]
 pub trait TxPool {
 	#[method(name = "txpool_content")]
 	fn content(&self) -> RpcResult<TxPoolResult<TransactionMap<Transaction>>>;
@@ -31,4 +38,20 @@ pub trait TxPool {
 
 	#[method(name = "txpool_status")]
 	fn status(&self) -> RpcResult<TxPoolResult<U256>>;
+
+	/// Pending and queued transactions of the given sender, by nonce.
+	#[method(name = "txpool_contentFrom")]
+	fn content_from(&self, address: H160) -> RpcResult<TxPoolResult<HashMap<U256, Transaction>>>;
+
+	/// Nonces missing for the queued transactions of the given sender to become pending.
+	#[method(name = "peaq_txpoolNonceGaps")]
+	fn nonce_gaps(&self, address: H160) -> RpcResult<NonceGaps>;
+
+	/// Pushes the Ethereum transactions becoming pending, optionally only from the given senders.
+	#[subscription(
+		name = "peaq_subscribePendingTransactions" => "peaq_pendingTransaction",
+		unsubscribe = "peaq_unsubscribePendingTransactions",
+		item = Transaction
+	)]
+	async fn subscribe_pending(&self, from_address: Option<Vec<H160>>) -> SubscriptionResult;
 }
@@ -0,0 +1,66 @@
+use ethereum_types::U256;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Nonces missing for the queued transactions of an account to become pending.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceGaps {
+	/// Nonce of the account at the best block.
+	pub nonce: U256,
+	/// Nonce following the pending transactions of the account.
+	pub next_nonce: U256,
+	/// Ranges of missing nonces, below the highest nonce in the pool.
+	pub gaps: Vec<NonceRange>,
+}
+
+/// Inclusive range of nonces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NonceRange {
+	pub from: U256,
+	pub to: U256,
+}
+
+impl NonceGaps {
+	/// Gaps between the nonce of the account and the nonces of its transactions in the pool.
+	pub fn new(nonce: U256, pool_nonces: impl IntoIterator<Item = U256>) -> Self {
+		let pool_nonces: BTreeSet<U256> =
+			pool_nonces.into_iter().filter(|pool_nonce| *pool_nonce >= nonce).collect();
+
+		let mut next_nonce = nonce;
+		let mut gaps = vec![];
+		for pool_nonce in pool_nonces {
+			if pool_nonce > next_nonce {
+				gaps.push(NonceRange { from: next_nonce, to: pool_nonce - 1 });
+			}
+			next_nonce = pool_nonce + 1;
+		}
+
+		let next_nonce = gaps.first().map_or(next_nonce, |gap| gap.from);
+		Self { nonce, next_nonce, gaps }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn range(from: u64, to: u64) -> NonceRange {
+		NonceRange { from: from.into(), to: to.into() }
+	}
+
+	#[test]
+	fn reports_missing_nonces() {
+		let gaps = NonceGaps::new(5.into(), [3, 5, 6, 9, 10, 13].map(U256::from));
+		assert_eq!(gaps.next_nonce, 7.into());
+		assert_eq!(gaps.gaps, vec![range(7, 8), range(11, 12)]);
+
+		let gaps = NonceGaps::new(5.into(), [7].map(U256::from));
+		assert_eq!(gaps.next_nonce, 5.into());
+		assert_eq!(gaps.gaps, vec![range(5, 6)]);
+
+		let gaps = NonceGaps::new(5.into(), [5, 6].map(U256::from));
+		assert_eq!(gaps.next_nonce, 7.into());
+		assert!(gaps.gaps.is_empty());
+	}
+}
@@ -18,22 +18,21 @@
 
 use futures::StreamExt;
 use jsonrpsee::core::{async_trait, RpcResult};
-pub use peaq_rpc_core_debug::{DebugServer, TraceParams};
+pub use peaq_rpc_core_debug::{DebugServer, TraceCallParams, TraceParams, TracerConfig};
 
-use tokio::{
-	self,
-	sync::{oneshot, Semaphore},
-};
+use tokio::{self, sync::oneshot};
 
 use ethereum_types::H256;
 use fc_rpc::{frontier_backend_client, internal_err, OverrideHandle};
 use fp_rpc::EthereumRuntimeRPCApi;
-use peaq_client_evm_tracing::{formatters::ResponseFormatter, types::single};
+use peaq_client_evm_tracing::{
+	formatters::ResponseFormatter, permits::TracingPermits, state::accounts_state, types::single,
+};
 use peaq_rpc_core_types::{RequestBlockId, RequestBlockTag};
 use peaq_rpc_primitives_debug::{DebugRuntimeApi, TracerInput};
 use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
 use sc_utils::mpsc::TracingUnboundedSender;
-use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_api::{ApiExt, ApiRef, Core, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{
 	Backend as BlockchainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
@@ -42,11 +41,12 @@ use sp_runtime::{
 	generic::BlockId,
 	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
 };
-use std::{future::Future, marker::PhantomData, sync::Arc};
+use std::{future::Future, marker::PhantomData, sync::Arc, time::Duration};
 
 pub enum RequesterInput {
 	Transaction(H256),
 	Block(RequestBlockId),
+	Call(TraceCallParams, RequestBlockId),
 }
 
 pub enum Response {
@@ -119,6 +119,33 @@ impl DebugServer for Debug {
 				_ => unreachable!(),
 			})
 	}
+
+	/// Handler for `debug_traceCall` request. Communicates with the service-defined task
+	/// using channels.
+	async fn trace_call(
+		&self,
+		call_params: TraceCallParams,
+		id: RequestBlockId,
+		params: Option<TraceParams>,
+	) -> RpcResult<single::TransactionTrace> {
+		let requester = self.requester.clone();
+
+		let (tx, rx) = oneshot::channel();
+		// Send a message from the rpc handler to the service level task.
+		requester
+			.unbounded_send(((RequesterInput::Call(call_params, id), params), tx))
+			.map_err(|err| {
+				internal_err(format!("failed to send request to debug service : {:?}", err))
+			})?;
+
+		// Receive a message from the service level task and send the rpc response.
+		rx.await
+			.map_err(|err| internal_err(format!("debug service dropped the channel : {:?}", err)))?
+			.map(|res| match res {
+				Response::Single(res) => res,
+				_ => unreachable!(),
+			})
+	}
 }
 
 pub struct DebugHandler<B: BlockT, C, BE>(PhantomData<(B, C, BE)>);
@@ -143,7 +170,7 @@ where
 		client: Arc<C>,
 		backend: Arc<BE>,
 		frontier_backend: Arc<dyn fc_api::Backend<B> + Send + Sync>,
-		permit_pool: Arc<Semaphore>,
+		permits: Arc<TracingPermits>,
 		overrides: Arc<OverrideHandle<B>>,
 		raw_max_memory_usage: usize,
 	) -> (impl Future<Output = ()>, DebugRequester) {
@@ -160,69 +187,72 @@ where
 						let client = client.clone();
 						let backend = backend.clone();
 						let frontier_backend = frontier_backend.clone();
-						let permit_pool = permit_pool.clone();
 						let overrides = overrides.clone();
 
-						tokio::task::spawn(async move {
-							let _ = response_tx.send(
-								async {
-									let _permit = permit_pool.acquire().await;
-									tokio::task::spawn_blocking(move || {
-										Self::handle_transaction_request(
-											client.clone(),
-											backend.clone(),
-											frontier_backend.clone(),
-											transaction_hash,
-											params,
-											overrides.clone(),
-											raw_max_memory_usage,
-										)
-									})
-									.await
-									.map_err(|e| {
-										internal_err(format!(
-											"Internal error on spawned task : {:?}",
-											e
-										))
-									})?
-								}
-								.await,
-							);
-						});
+						Self::spawn_request(
+							Arc::clone(&permits),
+							"debug_traceTransaction",
+							Self::handle_timeout(&params),
+							response_tx,
+							move || {
+								Self::handle_transaction_request(
+									client,
+									backend,
+									frontier_backend,
+									transaction_hash,
+									params,
+									overrides,
+									raw_max_memory_usage,
+								)
+							},
+						);
 					},
 					Some(((RequesterInput::Block(request_block_id), params), response_tx)) => {
 						let client = client.clone();
 						let backend = backend.clone();
 						let frontier_backend = frontier_backend.clone();
-						let permit_pool = permit_pool.clone();
 						let overrides = overrides.clone();
 
-						tokio::task::spawn(async move {
-							let _ = response_tx.send(
-								async {
-									let _permit = permit_pool.acquire().await;
-
-									tokio::task::spawn_blocking(move || {
-										Self::handle_block_request(
-											client.clone(),
-											backend.clone(),
-											frontier_backend.clone(),
-											request_block_id,
-											params,
-											overrides.clone(),
-										)
-									})
-									.await
-									.map_err(|e| {
-										internal_err(format!(
-											"Internal error on spawned task : {:?}",
-											e
-										))
-									})?
-								}
-								.await,
-							);
-						});
+						Self::spawn_request(
+							Arc::clone(&permits),
+							"debug_traceBlock",
+							Self::handle_timeout(&params),
+							response_tx,
+							move || {
+								Self::handle_block_request(
+									client,
+									backend,
+									frontier_backend,
+									request_block_id,
+									params,
+									overrides,
+								)
+							},
+						);
+					},
+					Some((
+						(RequesterInput::Call(call_params, request_block_id), params),
+						response_tx,
+					)) => {
+						let client = client.clone();
+						let frontier_backend = frontier_backend.clone();
+
+						Self::spawn_request(
+							Arc::clone(&permits),
+							"debug_traceCall",
+							Self::handle_timeout(&params),
+							response_tx,
+							move || {
+								Self::handle_call_request(
+									client,
+									frontier_backend,
+									call_params,
+									request_block_id,
+									params,
+									raw_max_memory_usage,
+								)
+							},
+						);
 					},
 					_ => {},
 				}
@@ -231,9 +261,45 @@ where
 		(fut, tx)
 	}
 
-	fn handle_params(params: Option<TraceParams>) -> RpcResult<(TracerInput, single::TraceType)> {
-		// Set trace input and type
+	/// Waits for a permit, then handles the `request` in a blocking task before the timeout and
+	/// sends the response.
+	fn spawn_request(
+		permits: Arc<TracingPermits>,
+		request: &'static str,
+		timeout: RpcResult<Option<Duration>>,
+		response_tx: Responder,
+		handler: impl FnOnce() -> RpcResult<Response> + Send + 'static,
+	) {
+		tokio::task::spawn(async move {
+			let _ = response_tx.send(
+				async {
+					let permit = permits
+						.acquire(request, timeout?)
+						.await
+						.map_err(|e| internal_err(e.to_string()))?;
+					permit.run(handler).await.map_err(|e| internal_err(e.to_string()))?
+				}
+				.await,
+			);
+		});
+	}
+
+	fn handle_timeout(params: &Option<TraceParams>) -> RpcResult<Option<Duration>> {
 		match params {
+			Some(params) => params.timeout().map_err(internal_err),
+			None => Ok(None),
+		}
+	}
+
+	fn handle_params(
+		params: Option<TraceParams>,
+	) -> RpcResult<(TracerInput, single::TraceType, TracerConfig)> {
+		let tracer_config = params
+			.as_ref()
+			.and_then(|params| params.tracer_config.clone())
+			.unwrap_or_default();
+		// Set trace input and type
+		let (tracer_input, trace_type) = match params {
 			Some(TraceParams { tracer: Some(tracer), .. }) => {
 				const BLOCKSCOUT_JS_CODE_HASH: [u8; 16] =
 					hex_literal::hex!("94d9f08796f91eb13a2e82a6066882f7");
@@ -242,51 +308,66 @@ where
 				let hash = sp_io::hashing::twox_128(tracer.as_bytes());
 				let tracer =
 					if hash == BLOCKSCOUT_JS_CODE_HASH || hash == BLOCKSCOUT_JS_CODE_HASH_V2 {
-						Some(TracerInput::Blockscout)
+						Some((TracerInput::Blockscout, single::TraceType::CallList))
 					} else if tracer == "callTracer" {
-						Some(TracerInput::CallTracer)
+						Some((TracerInput::CallTracer, single::TraceType::CallList))
+					} else if tracer == "prestateTracer" {
+						Some((
+							TracerInput::Prestate,
+							single::TraceType::Prestate {
+								diff_mode: tracer_config.diff_mode.unwrap_or(false),
+							},
+						))
+					} else if tracer == "4byteTracer" {
+						Some((TracerInput::FourByte, single::TraceType::FourByte))
 					} else {
 						None
 					};
 				if let Some(tracer) = tracer {
-					Ok((tracer, single::TraceType::CallList))
+					tracer
 				} else {
-					Err(internal_err(format!(
+					return Err(internal_err(format!(
 						"javascript based tracing is not available (hash :{:?})",
 						hash
 					)))
 				}
 			},
-			Some(params) => Ok((
+			Some(params) => (
 				TracerInput::None,
 				single::TraceType::Raw {
 					disable_storage: params.disable_storage.unwrap_or(false),
 					disable_memory: params.disable_memory.unwrap_or(false),
 					disable_stack: params.disable_stack.unwrap_or(false),
 				},
-			)),
-			_ => Ok((
+			),
+			_ => (
 				TracerInput::None,
 				single::TraceType::Raw {
 					disable_storage: false,
 					disable_memory: false,
 					disable_stack: false,
 				},
-			)),
-		}
+			),
+		};
+		Ok((tracer_input, trace_type, tracer_config))
 	}
 
-	fn handle_block_request(
-		client: Arc<C>,
-		backend: Arc<BE>,
-		frontier_backend: Arc<dyn fc_api::Backend<B> + Send + Sync>,
-		request_block_id: RequestBlockId,
-		params: Option<TraceParams>,
-		overrides: Arc<OverrideHandle<B>>,
-	) -> RpcResult<Response> {
-		let (tracer_input, trace_type) = Self::handle_params(params)?;
+	/// The call list listener, configured with the `callTracer` options.
+	fn call_list_listener(
+		tracer_config: &TracerConfig,
+	) -> peaq_client_evm_tracing::listeners::CallList {
+		let mut proxy = peaq_client_evm_tracing::listeners::CallList::default();
+		proxy.with_log = tracer_config.with_log.unwrap_or(false);
+		proxy.only_top_call = tracer_config.only_top_call.unwrap_or(false);
+		proxy
+	}
 
-		let reference_id: BlockId<B> = match request_block_id {
+	fn resolve_block_id(
+		client: &C,
+		frontier_backend: &(dyn fc_api::Backend<B> + Send + Sync),
+		request_block_id: RequestBlockId,
+	) -> RpcResult<BlockId<B>> {
+		match request_block_id {
 			RequestBlockId::Number(n) => Ok(BlockId::Number(n.unique_saturated_into())),
 			RequestBlockId::Tag(RequestBlockTag::Latest) =>
 				Ok(BlockId::Number(client.info().best_number)),
@@ -296,8 +377,8 @@ where
 				Err(internal_err("'pending' blocks are not supported")),
 			RequestBlockId::Hash(eth_hash) => {
 				match futures::executor::block_on(frontier_backend_client::load_hash::<B, C>(
-					client.as_ref(),
-					frontier_backend.as_ref(),
+					client,
+					frontier_backend,
 					eth_hash,
 				)) {
 					Ok(Some(hash)) => Ok(BlockId::Hash(hash)),
@@ -305,7 +386,21 @@ where
 					Err(e) => Err(e),
 				}
 			},
-		}?;
+		}
+	}
+
+	fn handle_block_request(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		frontier_backend: Arc<dyn fc_api::Backend<B> + Send + Sync>,
+		request_block_id: RequestBlockId,
+		params: Option<TraceParams>,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> RpcResult<Response> {
+		let (tracer_input, trace_type, tracer_config) = Self::handle_params(params)?;
+
+		let reference_id =
+			Self::resolve_block_id(client.as_ref(), frontier_backend.as_ref(), request_block_id)?;
 
 		// Get ApiRef. This handle allow to keep changes between txs in an internal buffer.
 		let api = client.runtime_api();
@@ -335,11 +430,6 @@ where
 		// Known ethereum transaction hashes.
 		let eth_tx_hashes: Vec<_> = statuses.iter().map(|t| t.transaction_hash).collect();
 
-		// If there are no ethereum transactions in the block return empty trace right away.
-		if eth_tx_hashes.is_empty() {
-			return Ok(Response::Block(vec![]));
-		}
-
 		// Get block extrinsics.
 		let exts = blockchain
 			.body(hash)
@@ -355,6 +445,12 @@ where
 			return Err(internal_err("Runtime api version call failed (trace)".to_string()));
 		};
 
+		// If there are no ethereum transactions in the block return empty trace right away, unless
+		// the runtime traces the EVM executions of substrate extrinsics and XCM messages too.
+		if eth_tx_hashes.is_empty() && trace_api_version < 7 {
+			return Ok(Response::Block(vec![]));
+		}
+
 		// Trace the block.
 		let f = || -> RpcResult<_> {
 			let result = if trace_api_version >= 5 {
@@ -389,14 +485,14 @@ where
 
 		match trace_type {
 			single::TraceType::CallList => {
-				let mut proxy = peaq_client_evm_tracing::listeners::CallList::default();
+				let mut proxy = Self::call_list_listener(&tracer_config);
 				proxy.using(f)?;
 				proxy.finish_transaction();
 				let response = match tracer_input {
+					// A block may have no EVM execution to trace.
 					TracerInput::CallTracer =>
-						peaq_client_evm_tracing::formatters::CallTracer::format(proxy)
-							.ok_or("Trace result is empty.")
-							.map_err(|e| internal_err(format!("{:?}", e))),
+						Ok(peaq_client_evm_tracing::formatters::CallTracer::format(proxy)
+							.unwrap_or_default()),
 					_ => Err(internal_err("Bug: failed to resolve the tracer format.".to_string())),
 				}?;
 
@@ -426,7 +522,7 @@ where
 		overrides: Arc<OverrideHandle<B>>,
 		raw_max_memory_usage: usize,
 	) -> RpcResult<Response> {
-		let (tracer_input, trace_type) = Self::handle_params(params)?;
+		let (tracer_input, trace_type, tracer_config) = Self::handle_params(params)?;
 
 		let (hash, index) =
 			match futures::executor::block_on(frontier_backend_client::load_transactions::<B, C>(
@@ -494,6 +590,18 @@ where
 		if let Some(block) = reference_block {
 			let transactions = block.transactions;
 			if let Some(transaction) = transactions.get(index) {
+				// The `prestateTracer` reads the state the transaction is executed on.
+				let pre_api = match trace_type {
+					single::TraceType::Prestate { .. } => Some(Self::state_before_transaction(
+						client.as_ref(),
+						parent_block_hash,
+						&header,
+						&exts,
+						transaction,
+					)?),
+					_ => None,
+				};
+
 				let f = || -> RpcResult<_> {
 					let result = if trace_api_version >= 5 {
 						// The block is initialized inside "trace_transaction"
@@ -564,7 +672,7 @@ where
 						))
 					},
 					single::TraceType::CallList => {
-						let mut proxy = peaq_client_evm_tracing::listeners::CallList::default();
+						let mut proxy = Self::call_list_listener(&tracer_config);
 						proxy.using(f)?;
 						proxy.finish_transaction();
 						let response = match tracer_input {
@@ -585,6 +693,26 @@ where
 						}?;
 						Ok(Response::Single(response))
 					},
+					single::TraceType::Prestate { diff_mode } => {
+						let mut proxy =
+							peaq_client_evm_tracing::listeners::Prestate::new(diff_mode);
+						proxy.using(f)?;
+						if let Some(pre_api) = pre_api {
+							Self::read_prestate(&mut proxy, &pre_api, &api, parent_block_hash)?;
+						}
+						Ok(Response::Single(
+							peaq_client_evm_tracing::formatters::Prestate::format(proxy)
+								.ok_or(internal_err("Trace result is empty."))?,
+						))
+					},
+					single::TraceType::FourByte => {
+						let mut proxy = peaq_client_evm_tracing::listeners::FourByte::default();
+						proxy.using(f)?;
+						Ok(Response::Single(
+							peaq_client_evm_tracing::formatters::FourByte::format(proxy)
+								.ok_or(internal_err("Trace result is empty."))?,
+						))
+					},
 					not_supported => Err(internal_err(format!(
 						"Bug: `handle_transaction_request` does not support {:?}.",
 						not_supported
@@ -594,4 +722,224 @@ where
 		}
 		Err(internal_err("Runtime block call failed".to_string()))
 	}
+
+	/// Executes a call in the Runtime on top of the state a given block is built on, without
+	/// a transaction.
+	///
+	/// Like `handle_transaction_request`, the block is initialized before the call so that it
+	/// sees the same environment (number, timestamp, author) as the block transactions. The
+	/// call runs on the state of the parent block: re-initializing the block on top of its own
+	/// state would replay its `on_initialize` hooks, which Aura rejects for a non-increasing
+	/// slot.
+	fn handle_call_request(
+		client: Arc<C>,
+		frontier_backend: Arc<dyn fc_api::Backend<B> + Send + Sync>,
+		call_params: TraceCallParams,
+		request_block_id: RequestBlockId,
+		params: Option<TraceParams>,
+		raw_max_memory_usage: usize,
+	) -> RpcResult<Response> {
+		let (tracer_input, trace_type, tracer_config) = Self::handle_params(params)?;
+
+		let reference_id =
+			Self::resolve_block_id(client.as_ref(), frontier_backend.as_ref(), request_block_id)?;
+
+		// Get ApiRef. This handle allow to keep changes between txs in an internal buffer.
+		let api = client.runtime_api();
+		// Get the header I want to work with.
+		let Ok(hash) = client.expect_block_hash_from_id(&reference_id) else {
+			return Err(internal_err("Block header not found"));
+		};
+		let header = match client.header(hash) {
+			Ok(Some(h)) => h,
+			_ => return Err(internal_err("Block header not found")),
+		};
+		// Get parent blockid.
+		let parent_block_hash = *header.parent_hash();
+
+		// Get DebugRuntimeApi version
+		let trace_api_version = if let Ok(Some(api_version)) =
+			api.api_version::<dyn DebugRuntimeApi<B>>(parent_block_hash)
+		{
+			api_version
+		} else {
+			return Err(internal_err("Runtime api version call failed (trace)".to_string()));
+		};
+
+		if trace_api_version < 6 {
+			return Err(internal_err("debug_traceCall is not supported by the runtime".to_string()))
+		}
+
+		let TraceCallParams {
+			from,
+			to,
+			gas_price,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			gas,
+			value,
+			data,
+			nonce,
+			access_list,
+		} = call_params;
+
+		// Without an explicit gas limit, the call may use the whole block gas limit.
+		let gas_limit = match gas {
+			Some(gas) => gas,
+			None => match api
+				.current_block(hash)
+				.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?
+			{
+				Some(block) => block.header.gas_limit,
+				None => return Err(internal_err("Block unavailable, cannot query gas limit")),
+			},
+		};
+
+		// The `prestateTracer` reads the state the call is executed on.
+		let pre_api = match trace_type {
+			single::TraceType::Prestate { .. } => {
+				let pre_api = client.runtime_api();
+				pre_api
+					.initialize_block(parent_block_hash, &header)
+					.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
+				Some(pre_api)
+			},
+			_ => None,
+		};
+
+		let f = || -> RpcResult<_> {
+			// The block is initialized inside "trace_call"
+			api.trace_call(
+				parent_block_hash,
+				&header,
+				from.unwrap_or_default(),
+				to,
+				data.map(|data| data.to_vec()).unwrap_or_default(),
+				value.unwrap_or_default(),
+				gas_limit,
+				max_fee_per_gas.or(gas_price),
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.map(|list| {
+					list.into_iter().map(|item| (item.address, item.storage_keys)).collect()
+				}),
+			)
+			.map_err(|e| {
+				internal_err(format!(
+					"Runtime api access error (version {:?}): {:?}",
+					trace_api_version, e
+				))
+			})?
+			.map_err(|e| internal_err(format!("DispatchError: {:?}", e)))?;
+
+			Ok(peaq_rpc_primitives_debug::Response::Single)
+		};
+
+		match trace_type {
+			single::TraceType::Raw { disable_storage, disable_memory, disable_stack } => {
+				let mut proxy = peaq_client_evm_tracing::listeners::Raw::new(
+					disable_storage,
+					disable_memory,
+					disable_stack,
+					raw_max_memory_usage,
+				);
+				proxy.using(f)?;
+				Ok(Response::Single(
+					peaq_client_evm_tracing::formatters::Raw::format(proxy).ok_or(internal_err(
+						"replayed call generated too much data. try disabling memory or storage?",
+					))?,
+				))
+			},
+			single::TraceType::CallList => {
+				let mut proxy = Self::call_list_listener(&tracer_config);
+				proxy.using(f)?;
+				proxy.finish_transaction();
+				let response = match tracer_input {
+					TracerInput::Blockscout =>
+						peaq_client_evm_tracing::formatters::Blockscout::format(proxy)
+							.ok_or("Trace result is empty.")
+							.map_err(|e| internal_err(format!("{:?}", e))),
+					TracerInput::CallTracer => {
+						let mut res =
+							peaq_client_evm_tracing::formatters::CallTracer::format(proxy)
+								.ok_or("Trace result is empty.")
+								.map_err(|e| internal_err(format!("{:?}", e)))?;
+						res.pop().ok_or(internal_err("Trace result is empty."))
+					},
+					_ => Err(internal_err("Bug: failed to resolve the tracer format.".to_string())),
+				}?;
+				Ok(Response::Single(response))
+			},
+			single::TraceType::Prestate { diff_mode } => {
+				let mut proxy = peaq_client_evm_tracing::listeners::Prestate::new(diff_mode);
+				proxy.using(f)?;
+				if let Some(pre_api) = pre_api {
+					Self::read_prestate(&mut proxy, &pre_api, &api, parent_block_hash)?;
+				}
+				Ok(Response::Single(
+					peaq_client_evm_tracing::formatters::Prestate::format(proxy)
+						.ok_or(internal_err("Trace result is empty."))?,
+				))
+			},
+			single::TraceType::FourByte => {
+				let mut proxy = peaq_client_evm_tracing::listeners::FourByte::default();
+				proxy.using(f)?;
+				Ok(Response::Single(
+					peaq_client_evm_tracing::formatters::FourByte::format(proxy)
+						.ok_or(internal_err("Trace result is empty."))?,
+				))
+			},
+			not_supported => Err(internal_err(format!(
+				"Bug: `handle_call_request` does not support {:?}.",
+				not_supported
+			))),
+		}
+	}
+
+	/// Returns a runtime api handle holding the state `transaction` is executed on: the block is
+	/// initialized and the extrinsics preceding the transaction are applied.
+	fn state_before_transaction<'a>(
+		client: &'a C,
+		parent_block_hash: B::Hash,
+		header: &B::Header,
+		exts: &[B::Extrinsic],
+		transaction: &ethereum::TransactionV2,
+	) -> RpcResult<ApiRef<'a, C::Api>> {
+		let api = client.runtime_api();
+		api.initialize_block(parent_block_hash, header)
+			.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
+
+		for ext in exts {
+			let ext_transactions = api
+				.extrinsic_filter(parent_block_hash, vec![ext.clone()])
+				.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
+			if ext_transactions.first() == Some(transaction) {
+				return Ok(api)
+			}
+
+			let _ = api
+				.apply_extrinsic(parent_block_hash, ext.clone())
+				.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
+		}
+
+		Err(internal_err("Failed to find Ethereum transaction among the extrinsics."))
+	}
+
+	/// Reads the state of the accounts touched by the transaction traced by `proxy`, before it
+	/// from `pre_api` and, in diff mode, after it from `post_api`, the handle it was traced with.
+	fn read_prestate(
+		proxy: &mut peaq_client_evm_tracing::listeners::Prestate,
+		pre_api: &C::Api,
+		post_api: &C::Api,
+		at: B::Hash,
+	) -> RpcResult<()> {
+		let api_err =
+			|e: sp_api::ApiError| internal_err(format!("Runtime api access error: {:?}", e));
+
+		proxy.pre = accounts_state::<B, _>(pre_api, at, &proxy.touched).map_err(api_err)?;
+		if proxy.diff_mode {
+			proxy.post = accounts_state::<B, _>(post_api, at, &proxy.touched).map_err(api_err)?;
+		}
+		Ok(())
+	}
 }
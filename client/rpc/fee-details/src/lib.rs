@@ -0,0 +1,137 @@
+use ethereum_types::H256;
+use fc_rpc::{frontier_backend_client, internal_err};
+use jsonrpsee::core::RpcResult;
+pub use peaq_rpc_core_fee_details::{
+	ExtrinsicFeeDetails, FeeDetailsServer, FeePayment, FeeSwap, RewardDistribution,
+};
+use sc_client_api::BlockBackend;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{marker::PhantomData, sync::Arc};
+
+use peaq_client_evm_tracing::permits::TracingPermits;
+use peaq_primitives_fee::{FeeDetails as FeeDetailsT, FeeDetailsApi};
+use peaq_primitives_xcm::{AccountId, Balance, StorageAssetId};
+
+type FeeDetailsResult = RpcResult<Option<ExtrinsicFeeDetails<AccountId, StorageAssetId>>>;
+
+pub struct FeeDetails<B, C> {
+	client: Arc<C>,
+	frontier_backend: Arc<dyn fc_api::Backend<B>>,
+	/// Permits shared with the tracing RPCs, as replaying a block is as expensive as tracing it.
+	permits: Arc<TracingPermits>,
+	_marker: PhantomData<B>,
+}
+
+impl<B, C> FeeDetails<B, C>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: FeeDetailsApi<B, AccountId, StorageAssetId, Balance>,
+{
+	pub fn new(
+		client: Arc<C>,
+		frontier_backend: Arc<dyn fc_api::Backend<B>>,
+		permits: Arc<TracingPermits>,
+	) -> Self {
+		Self { client, frontier_backend, permits, _marker: PhantomData }
+	}
+
+	/// Waits for a permit for the `request`, then replays the block `block_hash` on top of its
+	/// parent with `replay`, which is given the extrinsics and the header of the block.
+	async fn replay(
+		&self,
+		request: &'static str,
+		block_hash: H256,
+		replay: impl FnOnce(
+				&C::Api,
+				H256,
+				Vec<B::Extrinsic>,
+				&B::Header,
+			) -> Result<
+				Option<FeeDetailsT<AccountId, StorageAssetId, Balance>>,
+				sp_api::ApiError,
+			> + Send
+			+ 'static,
+	) -> FeeDetailsResult {
+		let permit = self
+			.permits
+			.acquire(request, None)
+			.await
+			.map_err(|e| internal_err(e.to_string()))?;
+
+		let client = Arc::clone(&self.client);
+		permit
+			.run(move || {
+				let Some(header) = client
+					.header(block_hash)
+					.map_err(|e| internal_err(format!("Fail to read header: {:?}", e)))?
+				else {
+					return Ok(None)
+				};
+				let Some(extrinsics) = client
+					.block_body(block_hash)
+					.map_err(|e| internal_err(format!("Fail to read blockchain db: {:?}", e)))?
+				else {
+					return Ok(None)
+				};
+
+				let api = client.runtime_api();
+				let details = replay(&api, *header.parent_hash(), extrinsics, &header)
+					.map_err(|e| internal_err(format!("Runtime api access error: {:?}", e)))?;
+
+				Ok(details.map(|details| ExtrinsicFeeDetails::new(block_hash, details)))
+			})
+			.await
+			.map_err(|e| internal_err(e.to_string()))?
+	}
+}
+
+#[jsonrpsee::core::async_trait]
+impl<B, C> FeeDetailsServer<AccountId, StorageAssetId> for FeeDetails<B, C>
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B>,
+	C: Send + Sync + 'static,
+	C::Api: FeeDetailsApi<B, AccountId, StorageAssetId, Balance>,
+{
+	async fn fee_details(&self, block_hash: H256, extrinsic_index: u32) -> FeeDetailsResult {
+		self.replay("peaq_feeDetails", block_hash, move |api, parent_hash, extrinsics, header| {
+			api.fee_details(parent_hash, extrinsics, extrinsic_index, header)
+		})
+		.await
+	}
+
+	async fn ethereum_fee_details(&self, transaction_hash: H256) -> FeeDetailsResult {
+		let Some((eth_block_hash, _)) = frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.frontier_backend.as_ref(),
+			transaction_hash,
+			true,
+		)
+		.await?
+		else {
+			return Ok(None)
+		};
+		let Some(block_hash) = frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.frontier_backend.as_ref(),
+			eth_block_hash,
+		)
+		.await?
+		else {
+			return Ok(None)
+		};
+
+		self.replay(
+			"peaq_ethereumFeeDetails",
+			block_hash,
+			move |api, parent_hash, extrinsics, header| {
+				api.ethereum_fee_details(parent_hash, extrinsics, transaction_hash, header)
+			},
+		)
+		.await
+	}
+}
@@ -14,25 +14,44 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-//! `trace_filter` RPC handler and its associated service task.
-//! The RPC handler rely on `CacheTask` which provides a future that must be run inside a tokio
+//! `trace_filter`, `trace_block`, `trace_transaction`, `trace_get`,
+//! `trace_replayBlockTransactions` and `trace_subscribe` RPC handlers and their associated service
+//! task.
+//! The RPC handlers rely on `CacheTask` which provides a future that must be run inside a tokio
 //! executor.
 //!
 //! The implementation is composed of multiple tasks :
-//! - Many calls the the RPC handler `Trace::filter`, communicating with the main task.
+//! - Many calls the the RPC handlers of `Trace`, communicating with the main task.
 //! - A main `CacheTask` managing the cache and the communication between tasks.
 //! - For each traced block an async task responsible to wait for a permit, spawn a blocking task
 //!   and waiting for the result, then send it to the main `CacheTask`.
+//!
+//! Traces can also be persisted in an optional on-disk `TraceStore`, read before tracing a block
+//! and written once it is traced.
+
+mod store;
 
-use futures::{select, stream::FuturesUnordered, FutureExt, StreamExt};
-use std::{collections::BTreeMap, future::Future, marker::PhantomData, sync::Arc, time::Duration};
+pub use store::{index_on_import, reindex, TraceStore, TraceStoreConfig};
+
+use futures::{
+	future, select,
+	stream::{FuturesUnordered, Stream},
+	FutureExt, StreamExt,
+};
+use std::{
+	collections::BTreeMap, future::Future, iter, marker::PhantomData, pin::Pin, sync::Arc,
+	time::Duration,
+};
 use tokio::{
 	sync::{mpsc, oneshot, Semaphore},
 	time::sleep,
 };
 use tracing::{instrument, Instrument};
 
-use sc_client_api::backend::{Backend, StateBackend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StateBackend, StorageProvider},
+	BlockchainEvents,
+};
 use sc_utils::mpsc::TracingUnboundedSender;
 use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder;
@@ -44,26 +63,39 @@ use substrate_prometheus_endpoint::{
 	register, Counter, PrometheusError, Registry as PrometheusRegistry, U64,
 };
 
-use ethereum_types::H256;
-use fc_rpc::OverrideHandle;
+use ethereum_types::{H160, H256, U256};
+use fc_rpc::{frontier_backend_client, internal_err, OverrideHandle};
 use fp_rpc::EthereumRuntimeRPCApi;
+use jsonrpsee::{
+	core::{RpcResult, SubscriptionResult},
+	PendingSubscriptionSink, SubscriptionMessage,
+};
 
 use peaq_client_evm_tracing::{
-	formatters::ResponseFormatter,
+	formatters::{state_diff::StateDiff, ResponseFormatter},
+	permits::{TracingBatch, TracingError, TracingPermits},
+	state::accounts_state,
 	types::block::{self, TransactionTrace},
 };
-pub use peaq_rpc_core_trace::{FilterRequest, TraceServer};
+pub use peaq_rpc_core_trace::{
+	FilterRequest, SubscribeRequest, TraceServer, TraceType, TransactionReplay,
+};
 use peaq_rpc_core_types::{RequestBlockId, RequestBlockTag};
 use peaq_rpc_primitives_debug::DebugRuntimeApi;
 
 type TxsTraceRes = Result<Vec<TransactionTrace>, String>;
+type StateDiffsRes = Result<Vec<StateDiff>, String>;
 
 /// RPC handler. Will communicate with a `CacheTask` through a `CacheRequester`.
 pub struct Trace<B, C> {
 	_phantom: PhantomData<B>,
 	client: Arc<C>,
+	frontier_backend: Arc<dyn fc_api::Backend<B>>,
 	requester: CacheRequester,
 	max_count: u32,
+	max_block_range: u32,
+	/// Bounds the number of concurrent `trace_subscribe` subscriptions.
+	subscriptions: Arc<Semaphore>,
 }
 
 impl<B, C> Clone for Trace<B, C> {
@@ -71,8 +103,11 @@ impl<B, C> Clone for Trace<B, C> {
 		Self {
 			_phantom: PhantomData,
 			client: Arc::clone(&self.client),
+			frontier_backend: Arc::clone(&self.frontier_backend),
 			requester: self.requester.clone(),
 			max_count: self.max_count,
+			max_block_range: self.max_block_range,
+			subscriptions: Arc::clone(&self.subscriptions),
 		}
 	}
 }
@@ -85,8 +120,23 @@ where
 	C: Send + Sync + 'static,
 {
 	/// Create a new RPC handler.
-	pub fn new(client: Arc<C>, requester: CacheRequester, max_count: u32) -> Self {
-		Self { client, requester, max_count, _phantom: PhantomData }
+	pub fn new(
+		client: Arc<C>,
+		frontier_backend: Arc<dyn fc_api::Backend<B>>,
+		requester: CacheRequester,
+		max_count: u32,
+		max_block_range: u32,
+		max_subscriptions: usize,
+	) -> Self {
+		Self {
+			client,
+			frontier_backend,
+			requester,
+			max_count,
+			max_block_range,
+			subscriptions: Arc::new(Semaphore::new(max_subscriptions)),
+			_phantom: PhantomData,
+		}
 	}
 
 	/// Convert an optional block ID (number or tag) to a block height.
@@ -108,6 +158,14 @@ where
 		let to_block = self.block_id(req.to_block)?;
 		let block_heights = from_block..=to_block;
 
+		let block_range = to_block.saturating_sub(from_block).saturating_add(1);
+		if !block_heights.is_empty() && block_range > self.max_block_range {
+			return Err(format!(
+				"block range ({}) can't be greater than maximum ({})",
+				block_range, self.max_block_range
+			));
+		}
+
 		let count = req.count.unwrap_or(self.max_count);
 		if count > self.max_count {
 			return Err(format!(
@@ -135,11 +193,16 @@ where
 		}
 
 		// Start a batch with these blocks.
-		let batch_id = self.requester.start_batch(block_hashes.clone()).await?;
-		// Fetch all the traces. It is done in another function to simplify error handling and allow
-		// to call the following `stop_batch` regardless of the result. This is important for the
-		// cache cleanup to work properly.
-		let res = self.fetch_traces(req, &block_hashes, count as usize).await;
+		let (batch_id, batch) =
+			self.requester.start_batch("trace_filter", block_hashes.clone()).await?;
+		// Fetch all the traces before the deadline of the batch. It is done in another function to
+		// simplify error handling and allow to call the following `stop_batch` regardless of the
+		// result. This is important for the cache cleanup to work properly.
+		let res = batch
+			.until_deadline(self.fetch_traces(req, &block_hashes, count as usize))
+			.await
+			.map_err(|e| e.to_string())
+			.and_then(|res| res);
 		// Stop the batch, allowing the cache task to remove useless non-started block traces and
 		// start the expiration delay.
 		self.requester.stop_batch(batch_id).await;
@@ -168,17 +231,7 @@ where
 			// Filter addresses.
 			let mut block_traces: Vec<_> = block_traces
 				.iter()
-				.filter(|trace| match trace.action {
-					block::TransactionTraceAction::Call { from, to, .. } =>
-						(from_address.is_empty() || from_address.contains(&from)) &&
-							(to_address.is_empty() || to_address.contains(&to)),
-					block::TransactionTraceAction::Create { from, .. } =>
-						(from_address.is_empty() || from_address.contains(&from)) &&
-							to_address.is_empty(),
-					block::TransactionTraceAction::Suicide { address, .. } =>
-						(from_address.is_empty() || from_address.contains(&address)) &&
-							to_address.is_empty(),
-				})
+				.filter(|trace| matches_addresses(trace, &from_address, &to_address))
 				.cloned()
 				.collect();
 
@@ -214,6 +267,148 @@ where
 
 		Ok(traces)
 	}
+
+	/// Convert a block ID (number, hash or tag) to a Substrate block hash, if the block exists.
+	async fn substrate_hash(&self, id: RequestBlockId) -> RpcResult<Option<H256>> {
+		if let RequestBlockId::Hash(eth_hash) = id {
+			return frontier_backend_client::load_hash::<B, C>(
+				self.client.as_ref(),
+				self.frontier_backend.as_ref(),
+				eth_hash,
+			)
+			.await
+		}
+
+		let block_height = self.block_id(Some(id)).map_err(internal_err)?;
+		self.client.hash(block_height).map_err(|e| {
+			internal_err(format!("Error when fetching block {} header : {:?}", block_height, e))
+		})
+	}
+
+	/// Traces of all the EVM executions of a block, fetched in their own batch.
+	async fn block_traces(&self, block_hash: H256) -> TxsTraceRes {
+		let (batch_id, batch) = self.requester.start_batch("trace_block", vec![block_hash]).await?;
+		let res = batch
+			.until_deadline(self.requester.get_traces(block_hash))
+			.await
+			.map_err(|e| e.to_string())
+			.and_then(|res| res);
+		self.requester.stop_batch(batch_id).await;
+
+		res
+	}
+
+	/// Traces of an Ethereum transaction, or `None` if the transaction is unknown.
+	async fn transaction_traces(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<Vec<TransactionTrace>>> {
+		let Some((eth_block_hash, index)) = frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.frontier_backend.as_ref(),
+			transaction_hash,
+			true,
+		)
+		.await?
+		else {
+			return Ok(None)
+		};
+		let Some(block_hash) = frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.frontier_backend.as_ref(),
+			eth_block_hash,
+		)
+		.await?
+		else {
+			return Ok(None)
+		};
+
+		let traces = self.block_traces(block_hash).await.map_err(internal_err)?;
+		Ok(Some(
+			traces
+				.into_iter()
+				.filter(|trace| trace.transaction_position == Some(index))
+				.collect(),
+		))
+	}
+
+	/// `trace_replayBlockTransactions` endpoint.
+	async fn replay_block_transactions(
+		self,
+		block: RequestBlockId,
+		trace_types: Vec<TraceType>,
+	) -> RpcResult<Vec<TransactionReplay>> {
+		if trace_types.contains(&TraceType::VmTrace) {
+			return Err(internal_err("'vmTrace' is not supported"))
+		}
+
+		let block_hash = self
+			.substrate_hash(block)
+			.await?
+			.ok_or_else(|| internal_err("Block not found"))?;
+
+		let traces = self.block_traces(block_hash).await.map_err(internal_err)?;
+		let mut state_diffs = if trace_types.contains(&TraceType::StateDiff) {
+			Some(
+				self.requester
+					.get_state_diffs(block_hash)
+					.await
+					.map_err(internal_err)?
+					.into_iter(),
+			)
+		} else {
+			None
+		};
+
+		// Traces are ordered by transaction, each starting with its root call. Only Ethereum
+		// transactions are replayed.
+		let mut replays: Vec<TransactionReplay> = vec![];
+		for trace in traces {
+			let Some(transaction_hash) = trace.transaction_hash else { continue };
+			if trace.trace_address.is_empty() {
+				let output = match &trace.output {
+					block::TransactionTraceOutput::Result(
+						block::TransactionTraceResult::Call { output, .. },
+					) => output.clone(),
+					block::TransactionTraceOutput::Result(
+						block::TransactionTraceResult::Create { code, .. },
+					) => code.clone(),
+					_ => vec![],
+				};
+				replays.push(TransactionReplay {
+					output,
+					state_diff: state_diffs
+						.as_mut()
+						.map(|state_diffs| state_diffs.next().unwrap_or_default()),
+					trace: vec![],
+					vm_trace: None,
+					transaction_hash,
+				});
+			}
+
+			if let Some(replay) = replays.last_mut() {
+				if trace_types.contains(&TraceType::Trace) {
+					replay.trace.push(trace);
+				}
+			}
+		}
+
+		Ok(replays)
+	}
+}
+
+/// Whether the trace matches the sender and recipient filters, an empty filter matching any
+/// address.
+fn matches_addresses(trace: &TransactionTrace, from_address: &[H160], to_address: &[H160]) -> bool {
+	match trace.action {
+		block::TransactionTraceAction::Call { from, to, .. } =>
+			(from_address.is_empty() || from_address.contains(&from)) &&
+				(to_address.is_empty() || to_address.contains(&to)),
+		block::TransactionTraceAction::Create { from, .. } =>
+			(from_address.is_empty() || from_address.contains(&from)) && to_address.is_empty(),
+		block::TransactionTraceAction::Suicide { address, .. } =>
+			(from_address.is_empty() || from_address.contains(&address)) && to_address.is_empty(),
+	}
 }
 
 #[jsonrpsee::core::async_trait]
@@ -222,6 +417,7 @@ where
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
 	B::Header: HeaderT<Number = u32>,
 	C: HeaderMetadata<B, Error = BlockChainError> + HeaderBackend<B>,
+	C: BlockchainEvents<B>,
 	C: Send + Sync + 'static,
 {
 	async fn filter(
@@ -230,6 +426,114 @@ where
 	) -> jsonrpsee::core::RpcResult<Vec<TransactionTrace>> {
 		self.clone().filter(filter).await.map_err(fc_rpc::internal_err)
 	}
+
+	async fn block(&self, block: RequestBlockId) -> RpcResult<Option<Vec<TransactionTrace>>> {
+		let Some(block_hash) = self.substrate_hash(block).await? else { return Ok(None) };
+		if block_hash == self.client.info().genesis_hash {
+			return Ok(Some(vec![])) // no traces for genesis block.
+		}
+
+		self.block_traces(block_hash).await.map(Some).map_err(internal_err)
+	}
+
+	async fn transaction(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<Vec<TransactionTrace>>> {
+		self.transaction_traces(transaction_hash).await
+	}
+
+	async fn get(
+		&self,
+		transaction_hash: H256,
+		indices: Vec<U256>,
+	) -> RpcResult<Option<TransactionTrace>> {
+		Ok(self.transaction_traces(transaction_hash).await?.and_then(|traces| {
+			traces.into_iter().find(|trace| {
+				trace
+					.trace_address
+					.iter()
+					.map(|index| U256::from(*index))
+					.eq(indices.iter().copied())
+			})
+		}))
+	}
+
+	async fn replay_block_transactions(
+		&self,
+		block: RequestBlockId,
+		trace_types: Vec<TraceType>,
+	) -> RpcResult<Vec<TransactionReplay>> {
+		self.clone().replay_block_transactions(block, trace_types).await
+	}
+
+	async fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		request: Option<SubscribeRequest>,
+	) -> SubscriptionResult {
+		// Held until the subscription ends.
+		let Ok(_permit) = Arc::clone(&self.subscriptions).try_acquire_owned() else {
+			pending.reject(internal_err("Too many trace subscriptions, retry later")).await;
+			return Ok(())
+		};
+
+		let request = request.unwrap_or_default();
+		let from_address = request.from_address.unwrap_or_default();
+		let to_address = request.to_address.unwrap_or_default();
+
+		// Hashes of the new best blocks, or of the finalized blocks including the ones finalized
+		// implicitly by each notification.
+		let mut blocks: Pin<Box<dyn Stream<Item = Vec<H256>> + Send>> = if request.finalized {
+			Box::pin(self.client.finality_notification_stream().map(|notification| {
+				notification
+					.tree_route
+					.iter()
+					.copied()
+					.chain(iter::once(notification.hash))
+					.collect()
+			}))
+		} else {
+			Box::pin(self.client.import_notification_stream().filter_map(|notification| {
+				future::ready(notification.is_new_best.then(|| vec![notification.hash]))
+			}))
+		};
+
+		let sink = pending.accept().await?;
+		loop {
+			let block_hashes = select! {
+				block_hashes = blocks.next().fuse() => match block_hashes {
+					Some(block_hashes) => block_hashes,
+					None => break,
+				},
+				_ = sink.closed().fuse() => break,
+			};
+
+			for block_hash in block_hashes {
+				let traces = match self.block_traces(block_hash).await {
+					Ok(traces) => traces,
+					Err(e) => {
+						tracing::warn!(
+							"Failed to trace block {} for a subscription : {}",
+							block_hash,
+							e
+						);
+						continue
+					},
+				};
+
+				let traces: Vec<_> = traces
+					.into_iter()
+					.filter(|trace| matches_addresses(trace, &from_address, &to_address))
+					.collect();
+				if !traces.is_empty() {
+					sink.send(SubscriptionMessage::from_json(&traces)?).await?;
+				}
+			}
+		}
+
+		Ok(())
+	}
 }
 
 /// An opaque batch ID.
@@ -241,8 +545,11 @@ enum CacheRequest {
 	/// Request to start caching the provided range of blocks.
 	/// The task will add to blocks to its pool and immediately return a new batch ID.
 	StartBatch {
-		/// Returns the ID of the batch for cancellation.
-		sender: oneshot::Sender<CacheBatchId>,
+		/// Returns the ID of the batch for cancellation and its permits, or an error if too many
+		/// requests are queued.
+		sender: oneshot::Sender<Result<(CacheBatchId, TracingBatch), TracingError>>,
+		/// Label of the request in the metrics.
+		request: &'static str,
 		/// List of block hash to trace.
 		blocks: Vec<H256>,
 	},
@@ -257,6 +564,14 @@ enum CacheRequest {
 	/// Notify the cache that it can stop the batch with that ID. Any block contained only in
 	/// this batch and still not started will be discarded.
 	StopBatch { batch_id: CacheBatchId },
+	/// Replay the given block to compute the state diff of each of its Ethereum transactions.
+	/// State diffs are not cached, the block is replayed for each request.
+	GetStateDiffs {
+		/// Returns the array of state diffs or an error.
+		sender: oneshot::Sender<StateDiffsRes>,
+		/// Hash of the block.
+		block: H256,
+	},
 }
 
 /// Allows to interact with the cache task.
@@ -264,22 +579,28 @@ enum CacheRequest {
 pub struct CacheRequester(TracingUnboundedSender<CacheRequest>);
 
 impl CacheRequester {
-	/// Request to start caching the provided range of blocks.
-	/// The task will add to blocks to its pool and immediately return the batch ID.
+	/// Request to start caching the provided range of blocks for the `request`.
+	/// The task will add to blocks to its pool and immediately return the batch ID, along with
+	/// the batch permits whose deadline bounds the request.
 	#[instrument(skip(self))]
-	pub async fn start_batch(&self, blocks: Vec<H256>) -> Result<CacheBatchId, String> {
+	pub async fn start_batch(
+		&self,
+		request: &'static str,
+		blocks: Vec<H256>,
+	) -> Result<(CacheBatchId, TracingBatch), String> {
 		let (response_tx, response_rx) = oneshot::channel();
 		let sender = self.0.clone();
 
 		sender
-			.unbounded_send(CacheRequest::StartBatch { sender: response_tx, blocks })
+			.unbounded_send(CacheRequest::StartBatch { sender: response_tx, request, blocks })
 			.map_err(|e| {
 				format!("Failed to send request to the trace cache task. Error : {:?}", e)
 			})?;
 
 		response_rx
 			.await
-			.map_err(|e| format!("Trace cache task closed the response channel. Error : {:?}", e))
+			.map_err(|e| format!("Trace cache task closed the response channel. Error : {:?}", e))?
+			.map_err(|e| e.to_string())
 	}
 
 	/// Fetch the traces for given block hash.
@@ -303,6 +624,24 @@ impl CacheRequester {
 			.map_err(|e| format!("Failed to replay block. Error : {:?}", e))
 	}
 
+	/// Replay the given block to compute the state diff of each of its Ethereum transactions.
+	#[instrument(skip(self))]
+	pub async fn get_state_diffs(&self, block: H256) -> StateDiffsRes {
+		let (response_tx, response_rx) = oneshot::channel();
+		let sender = self.0.clone();
+
+		sender
+			.unbounded_send(CacheRequest::GetStateDiffs { sender: response_tx, block })
+			.map_err(|e| {
+				format!("Failed to send request to the trace cache task. Error : {:?}", e)
+			})?;
+
+		response_rx
+			.await
+			.map_err(|e| format!("Trace cache task closed the response channel. Error : {:?}", e))?
+			.map_err(|e| format!("Failed to replay block. Error : {:?}", e))
+	}
+
 	/// Notify the cache that it can stop the batch with that ID. Any block contained only in
 	/// this batch and still in the waiting pool will be discarded.
 	#[instrument(skip(self))]
@@ -366,7 +705,8 @@ enum BlockingTaskMessage {
 pub struct CacheTask<B, C, BE> {
 	client: Arc<C>,
 	backend: Arc<BE>,
-	blocking_permits: Arc<Semaphore>,
+	store: Option<Arc<TraceStore>>,
+	blocking_permits: Arc<TracingPermits>,
 	cached_blocks: BTreeMap<H256, CacheBlock>,
 	batches: BTreeMap<u64, Vec<H256>>,
 	next_batch_id: u64,
@@ -397,8 +737,9 @@ where
 		client: Arc<C>,
 		backend: Arc<BE>,
 		cache_duration: Duration,
-		blocking_permits: Arc<Semaphore>,
+		blocking_permits: Arc<TracingPermits>,
 		overrides: Arc<OverrideHandle<B>>,
+		store: Option<Arc<TraceStore>>,
 		prometheus: Option<PrometheusRegistry>,
 	) -> (impl Future<Output = ()>, CacheRequester) {
 		// Communication with the outside world :
@@ -429,6 +770,7 @@ where
 			let mut inner = Self {
 				client,
 				backend,
+				store,
 				blocking_permits,
 				cached_blocks: BTreeMap::new(),
 				batches: BTreeMap::new(),
@@ -444,8 +786,8 @@ where
 					request = requester_rx.next() => {
 						match request {
 							None => break,
-							Some(CacheRequest::StartBatch {sender, blocks})
-								=> inner.request_start_batch(&blocking_tx, sender, blocks, overrides.clone()),
+							Some(CacheRequest::StartBatch {sender, request, blocks})
+								=> inner.request_start_batch(&blocking_tx, sender, request, blocks, overrides.clone()),
 							Some(CacheRequest::GetTraces {sender, block})
 								=> inner.request_get_traces(sender, block),
 							Some(CacheRequest::StopBatch {batch_id}) => {
@@ -458,6 +800,8 @@ where
 
 								inner.request_stop_batch(batch_id);
 							},
+							Some(CacheRequest::GetStateDiffs {sender, block})
+								=> inner.request_get_state_diffs(sender, block, overrides.clone()),
 						}
 					},
 					message = blocking_rx.recv().fuse() => {
@@ -484,54 +828,102 @@ where
 	}
 
 	/// Handle the creation of a batch.
-	/// Will start the tracing process for blocks that are not already in the cache.
+	/// Will start the tracing process for blocks that are not already in the cache, unless too
+	/// many requests are queued.
 	#[instrument(skip(self, blocking_tx, sender, blocks, overrides))]
 	fn request_start_batch(
 		&mut self,
 		blocking_tx: &mpsc::Sender<BlockingTaskMessage>,
-		sender: oneshot::Sender<CacheBatchId>,
+		sender: oneshot::Sender<Result<(CacheBatchId, TracingBatch), TracingError>>,
+		request: &'static str,
 		blocks: Vec<H256>,
 		overrides: Arc<OverrideHandle<B>>,
 	) {
+		// The whole batch takes a single slot of the queue, held until the request and the
+		// tracing of its blocks are over.
+		let batch = match self.blocking_permits.acquire_batch(request) {
+			Ok(batch) => batch,
+			Err(e) => {
+				let _ = sender.send(Err(e));
+				return
+			},
+		};
+
 		tracing::trace!("Starting batch {}", self.next_batch_id);
 		self.batches.insert(self.next_batch_id, blocks.clone());
 
 		for block in blocks {
-			// The block is already in the cache, awesome !
-			if let Some(block_cache) = self.cached_blocks.get_mut(&block) {
-				block_cache.active_batch_count += 1;
-				tracing::trace!(
-					"Cache hit for block {}, now used by {} batches.",
-					block,
-					block_cache.active_batch_count
+			let active_batch_count = match self.cached_blocks.get_mut(&block) {
+				// The tracing of the block failed, e.g. it timed out with the batch which pooled
+				// it, so it is traced again for this batch.
+				Some(block_cache)
+					if matches!(block_cache.state, CacheBlockState::Cached { traces: Err(_) }) =>
+				{
+					tracing::trace!("Cached error for block {}, pooling it for tracing.", block);
+					block_cache.active_batch_count + 1
+				},
+				// The block is already in the cache, awesome !
+				Some(block_cache) => {
+					block_cache.active_batch_count += 1;
+					tracing::trace!(
+						"Cache hit for block {}, now used by {} batches.",
+						block,
+						block_cache.active_batch_count
+					);
+					continue
+				},
+				// Otherwise we need to queue this block for tracing.
+				None => {
+					tracing::trace!("Cache miss for block {}, pooling it for tracing.", block);
+					1
+				},
+			};
+
+			let state = self.pool_block(blocking_tx, &batch, block, overrides.clone());
+			self.cached_blocks.insert(block, CacheBlock { active_batch_count, state });
+		}
+
+		// Respond with the batch ID.
+		let _ = sender.send(Ok((CacheBatchId(self.next_batch_id), batch)));
+
+		// Increase batch ID for next request.
+		self.next_batch_id = self.next_batch_id.overflowing_add(1).0;
+	}
+
+	/// Spawn the tracing of a block of the batch, returning its pooled state.
+	fn pool_block(
+		&self,
+		blocking_tx: &mpsc::Sender<BlockingTaskMessage>,
+		batch: &TracingBatch,
+		block: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> CacheBlockState {
+		let batch = batch.clone();
+		let (unqueue_sender, unqueue_receiver) = oneshot::channel();
+		let client = Arc::clone(&self.client);
+		let backend = Arc::clone(&self.backend);
+		let store = self.store.clone();
+		let blocking_tx = blocking_tx.clone();
+
+		// Spawn all block caching asynchronously.
+		// It will wait to obtain a permit, then spawn a blocking task.
+		// When the blocking task returns its result, it is send
+		// thought a channel to the main task loop.
+		tokio::spawn(
+			async move {
+				tracing::trace!("Waiting for blocking permit or task cancellation");
+				// The blocks of the batch wait for a permit one at a time, and must be
+				// traced before the deadline of the batch.
+				let permit = select!(
+					_ = unqueue_receiver.fuse() => {
+					tracing::trace!("Tracing of the block has been cancelled.");
+						return;
+					},
+					permit = batch.acquire().fuse() => permit,
 				);
-			}
-			// Otherwise we need to queue this block for tracing.
-			else {
-				tracing::trace!("Cache miss for block {}, pooling it for tracing.", block);
-
-				let blocking_permits = Arc::clone(&self.blocking_permits);
-				let (unqueue_sender, unqueue_receiver) = oneshot::channel();
-				let client = Arc::clone(&self.client);
-				let backend = Arc::clone(&self.backend);
-				let blocking_tx = blocking_tx.clone();
-				let overrides = overrides.clone();
-
-				// Spawn all block caching asynchronously.
-				// It will wait to obtain a permit, then spawn a blocking task.
-				// When the blocking task returns its result, it is send
-				// thought a channel to the main task loop.
-				tokio::spawn(
-					async move {
-						tracing::trace!("Waiting for blocking permit or task cancellation");
-						let _permit = select!(
-							_ = unqueue_receiver.fuse() => {
-							tracing::trace!("Tracing of the block has been cancelled.");
-								return;
-							},
-							permit = blocking_permits.acquire().fuse() => permit,
-						);
 
+				let result = match permit {
+					Ok(permit) => {
 						// Warn the main task that block tracing as started, and
 						// this block cache entry should not be removed.
 						let _ = blocking_tx
@@ -540,49 +932,38 @@ where
 
 						tracing::trace!("Start block tracing in a blocking task.");
 
-						// Perform block tracing in a tokio blocking task.
-						let result = async {
-							tokio::task::spawn_blocking(move || {
-								Self::cache_block(client, backend, block, overrides.clone())
+						// Perform block tracing in a tokio blocking task, aborted at the
+						// deadline.
+						permit
+							.run(move || {
+								Self::load_or_cache_block(
+									client,
+									backend,
+									store,
+									block,
+									overrides.clone(),
+								)
 							})
 							.await
-							.map_err(|e| {
-								format!("Tracing Substrate block {} panicked : {:?}", block, e)
-							})?
-						}
-						.await
-						.map_err(|e| e.to_string());
-
-						tracing::trace!("Block tracing finished, sending result to main task.");
+					},
+					Err(e) => Err(e),
+				}
+				.map_err(|e: TracingError| {
+					format!("Tracing Substrate block {} failed : {}", block, e)
+				})
+				.and_then(|result| result);
 
-						// Send response to main task.
-						let _ = blocking_tx
-							.send(BlockingTaskMessage::Finished { block_hash: block, result })
-							.await;
-					}
-					.instrument(tracing::trace_span!("Block tracing", block = %block)),
-				);
+				tracing::trace!("Block tracing finished, sending result to main task.");
 
-				// Insert the block in the cache.
-				self.cached_blocks.insert(
-					block,
-					CacheBlock {
-						active_batch_count: 1,
-						state: CacheBlockState::Pooled {
-							started: false,
-							waiting_requests: vec![],
-							unqueue_sender,
-						},
-					},
-				);
+				// Send response to main task.
+				let _ = blocking_tx
+					.send(BlockingTaskMessage::Finished { block_hash: block, result })
+					.await;
 			}
-		}
-
-		// Respond with the batch ID.
-		let _ = sender.send(CacheBatchId(self.next_batch_id));
+			.instrument(tracing::trace_span!("Block tracing", block = %block)),
+		);
 
-		// Increase batch ID for next request.
-		self.next_batch_id = self.next_batch_id.overflowing_add(1).0;
+		CacheBlockState::Pooled { started: false, waiting_requests: vec![], unqueue_sender }
 	}
 
 	/// Handle a request to get the traces of the provided block.
@@ -628,6 +1009,39 @@ where
 		}
 	}
 
+	/// Handle a request to get the state diffs of the provided block.
+	/// The block is replayed in a blocking task once a permit is obtained, and the result is sent
+	/// directly to the requester without going through the cache.
+	#[instrument(skip(self, sender, overrides))]
+	fn request_get_state_diffs(
+		&mut self,
+		sender: oneshot::Sender<StateDiffsRes>,
+		block: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) {
+		let blocking_permits = Arc::clone(&self.blocking_permits);
+		let client = Arc::clone(&self.client);
+		let backend = Arc::clone(&self.backend);
+
+		tokio::spawn(
+			async move {
+				let result = async {
+					blocking_permits
+						.acquire("trace_stateDiff", None)
+						.await?
+						.run(move || Self::replay_state_diffs(client, backend, block, overrides))
+						.await
+				}
+				.await
+				.map_err(|e| format!("Tracing Substrate block {} failed : {}", block, e))
+				.and_then(|result| result);
+
+				let _ = sender.send(result);
+			}
+			.instrument(tracing::trace_span!("Block state diffs", block = %block)),
+		);
+	}
+
 	/// Handle a request to stop a batch.
 	/// For all blocks that needed to be traced, are only in this batch and not yet started, their
 	/// tracing is cancelled to save CPU-time and avoid attacks requesting large amount of blocks.
@@ -726,16 +1140,18 @@ where
 		}
 	}
 
-	/// (In blocking task) Use the Runtime API to trace the block.
-	#[instrument(skip(client, backend, overrides))]
-	fn cache_block(
-		client: Arc<C>,
-		backend: Arc<BE>,
+	/// (In blocking task) Fetch the header, Ethereum block, Ethereum transaction statuses and
+	/// extrinsics of a Substrate block.
+	fn block_data(
+		client: &C,
+		backend: &BE,
 		substrate_hash: H256,
-		overrides: Arc<OverrideHandle<B>>,
-	) -> TxsTraceRes {
+		overrides: &OverrideHandle<B>,
+	) -> Result<
+		(B::Header, ethereum::BlockV2, Vec<fp_rpc::TransactionStatus>, Vec<B::Extrinsic>),
+		String,
+	> {
 		// Get Subtrate block data.
-		let api = client.runtime_api();
 		let block_header = client
 			.header(substrate_hash)
 			.map_err(|e| {
@@ -744,10 +1160,8 @@ where
 			.ok_or_else(|| format!("Subtrate block {} don't exist", substrate_hash))?;
 
 		let height = *block_header.number();
-		let substrate_parent_hash = *block_header.parent_hash();
 
-		let schema =
-			fc_storage::onchain_storage_schema::<B, C, BE>(client.as_ref(), substrate_hash);
+		let schema = fc_storage::onchain_storage_schema::<B, C, BE>(client, substrate_hash);
 
 		// Get Ethereum block data.
 		let (eth_block, eth_transactions) = match overrides.schemas.get(&schema) {
@@ -765,9 +1179,6 @@ where
 			_ => return Err(format!("No storage override at {:?}", substrate_hash)),
 		};
 
-		let eth_block_hash = eth_block.header.hash();
-		let eth_tx_hashes = eth_transactions.iter().map(|t| t.transaction_hash).collect();
-
 		// Get extrinsics (containing Ethereum ones)
 		let extrinsics = backend
 			.blockchain()
@@ -777,6 +1188,71 @@ where
 			})?
 			.ok_or_else(|| format!("Could not find block {} when fetching extrinsics.", height))?;
 
+		Ok((block_header, eth_block, eth_transactions, extrinsics))
+	}
+
+	/// (In blocking task) Read the traces of the block from the trace store if any, or trace the
+	/// block and write its traces to the store.
+	#[instrument(skip(client, backend, store, overrides))]
+	fn load_or_cache_block(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		store: Option<Arc<TraceStore>>,
+		substrate_hash: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> TxsTraceRes {
+		let Some(store) = store else {
+			return Self::cache_block(client, backend, substrate_hash, overrides)
+		};
+
+		let height = *client
+			.header(substrate_hash)
+			.map_err(|e| {
+				format!("Error when fetching substrate block {} header : {:?}", substrate_hash, e)
+			})?
+			.ok_or_else(|| format!("Subtrate block {} don't exist", substrate_hash))?
+			.number();
+
+		match store.get(height, substrate_hash) {
+			Ok(Some(traces)) => return Ok(traces),
+			Ok(None) => (),
+			Err(e) => tracing::warn!(
+				"Failed to read traces of block {} from the trace store : {:?}",
+				substrate_hash,
+				e
+			),
+		}
+
+		let traces = Self::cache_block(Arc::clone(&client), backend, substrate_hash, overrides)?;
+		if let Err(e) = store.insert(height, substrate_hash, &traces, client.info().best_number) {
+			tracing::warn!(
+				"Failed to write traces of block {} to the trace store : {:?}",
+				substrate_hash,
+				e
+			);
+		}
+
+		Ok(traces)
+	}
+
+	/// (In blocking task) Use the Runtime API to trace the block.
+	#[instrument(skip(client, backend, overrides))]
+	fn cache_block(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		substrate_hash: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> TxsTraceRes {
+		let (block_header, eth_block, eth_transactions, extrinsics) =
+			Self::block_data(&client, &backend, substrate_hash, &overrides)?;
+		let height = *block_header.number();
+		let substrate_parent_hash = *block_header.parent_hash();
+
+		let eth_block_hash = eth_block.header.hash();
+		let eth_tx_hashes = eth_transactions.iter().map(|t| t.transaction_hash).collect();
+
+		let api = client.runtime_api();
+
 		// Get DebugRuntimeApi version
 		let trace_api_version = if let Ok(Some(api_version)) =
 			api.api_version::<dyn DebugRuntimeApi<B>>(substrate_parent_hash)
@@ -823,20 +1299,21 @@ where
 		for trace in traces.iter_mut() {
 			trace.block_hash = eth_block_hash;
 			trace.block_number = height;
-			trace.transaction_hash = eth_transactions
-				.get(trace.transaction_position as usize)
-				.ok_or_else(|| {
-					tracing::warn!(
-						"Bug: A transaction has been replayed while it shouldn't (in block {}).",
-						height
-					);
+			if let Some(transaction_position) = trace.transaction_position {
+				let transaction =
+					eth_transactions.get(transaction_position as usize).ok_or_else(|| {
+						tracing::warn!(
+							"Bug: A transaction has been replayed while it shouldn't (in block {}).",
+							height
+						);
 
-					format!(
-						"Bug: A transaction has been replayed while it shouldn't (in block {}).",
-						height
-					)
-				})?
-				.transaction_hash;
+						format!(
+							"Bug: A transaction has been replayed while it shouldn't (in block {}).",
+							height
+						)
+					})?;
+				trace.transaction_hash = Some(transaction.transaction_hash);
+			}
 
 			// Reformat error messages.
 			if let block::TransactionTraceOutput::Error(ref mut error) = trace.output {
@@ -847,6 +1324,83 @@ where
 		}
 		Ok(traces)
 	}
+
+	/// (In blocking task) Use the Runtime API to replay the block and compute the state diff of
+	/// each of its Ethereum transactions.
+	///
+	/// The block is traced a first time to collect the accounts and storage slots touched by each
+	/// transaction, then replayed a second time to read their state around each transaction.
+	#[instrument(skip(client, backend, overrides))]
+	fn replay_state_diffs(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		substrate_hash: H256,
+		overrides: Arc<OverrideHandle<B>>,
+	) -> StateDiffsRes {
+		let (block_header, _, eth_transactions, extrinsics) =
+			Self::block_data(&client, &backend, substrate_hash, &overrides)?;
+		let height = *block_header.number();
+		let substrate_parent_hash = *block_header.parent_hash();
+
+		let eth_tx_hashes: Vec<_> = eth_transactions.iter().map(|t| t.transaction_hash).collect();
+
+		let api_err = |e: sp_api::ApiError| format!("Runtime api access error: {:?}", e);
+
+		// Get DebugRuntimeApi version
+		let api = client.runtime_api();
+		match api.api_version::<dyn DebugRuntimeApi<B>>(substrate_parent_hash) {
+			Ok(Some(api_version)) if api_version >= 5 => (),
+			Ok(Some(_)) => return Err("Runtime does not support state diffs".to_string()),
+			_ => return Err("Runtime api version call failed (trace)".to_string()),
+		}
+
+		let mut proxy = peaq_client_evm_tracing::listeners::StateDiff::default();
+		proxy
+			.using(|| {
+				api.trace_block(
+					substrate_parent_hash,
+					extrinsics.clone(),
+					eth_tx_hashes.clone(),
+					&block_header,
+				)
+			})
+			.map_err(|e| format!("Blockchain error when replaying block {} : {:?}", height, e))?
+			.map_err(|e| {
+				format!("Internal runtime error when replaying block {} : {:?}", height, e)
+			})?;
+
+		// Replay the block without tracing, reading the state of the touched accounts before and
+		// after each Ethereum transaction.
+		let api = client.runtime_api();
+		api.initialize_block(substrate_parent_hash, &block_header).map_err(api_err)?;
+		for ext in extrinsics {
+			let index = api
+				.extrinsic_filter(substrate_parent_hash, vec![ext.clone()])
+				.map_err(api_err)?
+				.first()
+				.and_then(|transaction| {
+					eth_tx_hashes.iter().position(|hash| *hash == transaction.hash())
+				});
+
+			match index.and_then(|index| proxy.transactions.get_mut(index)) {
+				Some(transaction) => {
+					transaction.pre =
+						accounts_state::<B, _>(&*api, substrate_parent_hash, &transaction.touched)
+							.map_err(api_err)?;
+					let _ = api.apply_extrinsic(substrate_parent_hash, ext).map_err(api_err)?;
+					transaction.post =
+						accounts_state::<B, _>(&*api, substrate_parent_hash, &transaction.touched)
+							.map_err(api_err)?;
+				},
+				None => {
+					let _ = api.apply_extrinsic(substrate_parent_hash, ext).map_err(api_err)?;
+				},
+			}
+		}
+
+		peaq_client_evm_tracing::formatters::StateDiff::format(proxy)
+			.ok_or_else(|| "Fail to format proxy".to_string())
+	}
 }
 
 /// Prometheus metrics for tracing.
@@ -0,0 +1,411 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Persistent store of the block traces computed by the `CacheTask`.
+//!
+//! Traces are stored by block number and hash, so that the oldest blocks come first when
+//! iterating over the database and can be pruned once the store goes over its size limit or the
+//! pruning window.
+
+use super::CacheRequester;
+use ethereum_types::H256;
+use futures::StreamExt;
+use kvdb::{DBTransaction, KeyValueDB};
+use parity_scale_codec::{Decode, Encode};
+use peaq_client_evm_tracing::types::block::TransactionTrace;
+use sc_client_api::BlockchainEvents;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{
+	io,
+	path::Path,
+	sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Number of columns of the database.
+const NUM_COLUMNS: u32 = 2;
+
+mod columns {
+	/// Block number (big endian) and hash to the SCALE encoded traces of the block.
+	pub const TRACES: u32 = 0;
+	/// Metadata of the store.
+	pub const META: u32 = 1;
+}
+
+/// Total size in bytes of the stored traces.
+const SIZE_KEY: &[u8] = b"size";
+/// Version of the encoding of the stored traces.
+const VERSION_KEY: &[u8] = b"version";
+
+/// Current version of the encoding of the stored traces, to bump whenever `TransactionTrace`
+/// changes. Stores written with another version are wiped when opened.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Settings of the trace store.
+#[derive(Clone, Debug)]
+pub struct TraceStoreConfig {
+	/// Maximum size in bytes of the stored traces. The oldest blocks are pruned above it.
+	pub max_size: u64,
+	/// If set, only the traces of this number of most recent blocks are kept.
+	pub pruning: Option<u32>,
+}
+
+/// Persistent store of the traces of each block.
+pub struct TraceStore {
+	db: Arc<dyn KeyValueDB>,
+	config: TraceStoreConfig,
+	/// Total size of the stored traces, guarding the writes to the store.
+	size: Mutex<u64>,
+}
+
+impl TraceStore {
+	/// Open (or create) the RocksDB trace store at the given path.
+	pub fn open(path: &Path, config: TraceStoreConfig) -> Result<Self, String> {
+		let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+		db_config.create_if_missing = true;
+		let db = kvdb_rocksdb::Database::open(&db_config, path)
+			.map_err(|e| format!("Failed to open trace store at {}: {:?}", path.display(), e))?;
+
+		Self::new(Arc::new(db), config).map_err(|e| format!("Failed to read trace store: {:?}", e))
+	}
+
+	/// Create a trace store on top of the given database. The stored traces are removed if they
+	/// were written with another schema version.
+	pub fn new(db: Arc<dyn KeyValueDB>, config: TraceStoreConfig) -> io::Result<Self> {
+		let version = db
+			.get(columns::META, VERSION_KEY)?
+			.map(|version| u32::decode(&mut &version[..]).map_err(invalid_data))
+			.transpose()?;
+		if version != Some(SCHEMA_VERSION) {
+			Self::wipe(&*db, version)?;
+		}
+
+		let size = match db.get(columns::META, SIZE_KEY)? {
+			Some(size) => u64::decode(&mut &size[..]).map_err(invalid_data)?,
+			None => 0,
+		};
+
+		Ok(Self { db, config, size: Mutex::new(size) })
+	}
+
+	/// Remove all the stored traces and set the current schema version.
+	fn wipe(db: &dyn KeyValueDB, version: Option<u32>) -> io::Result<()> {
+		let mut transaction = DBTransaction::new();
+		let mut removed = 0u32;
+		for entry in db.iter(columns::TRACES) {
+			let (key, _) = entry?;
+			transaction.delete(columns::TRACES, &key);
+			removed += 1;
+		}
+		if removed > 0 {
+			log::warn!(
+				target: "tracing",
+				"Trace store schema version {:?} does not match {}, removing the traces of {} blocks",
+				version,
+				SCHEMA_VERSION,
+				removed,
+			);
+		}
+
+		transaction.put_vec(columns::META, SIZE_KEY, 0u64.encode());
+		transaction.put_vec(columns::META, VERSION_KEY, SCHEMA_VERSION.encode());
+		db.write(transaction)
+	}
+
+	fn key(number: u32, hash: H256) -> Vec<u8> {
+		let mut key = number.to_be_bytes().to_vec();
+		key.extend_from_slice(hash.as_bytes());
+		key
+	}
+
+	fn lock_size(&self) -> MutexGuard<'_, u64> {
+		// The size is only updated after a successful write, a poisoned lock is still valid.
+		self.size.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+	/// Total size in bytes of the stored traces.
+	pub fn size(&self) -> u64 {
+		*self.lock_size()
+	}
+
+	/// Traces of the given block, if they are stored.
+	pub fn get(&self, number: u32, hash: H256) -> io::Result<Option<Vec<TransactionTrace>>> {
+		self.db
+			.get(columns::TRACES, &Self::key(number, hash))?
+			.map(|traces| Vec::<TransactionTrace>::decode(&mut &traces[..]).map_err(invalid_data))
+			.transpose()
+	}
+
+	/// Store the traces of the given block, then prune the oldest blocks if the store goes over
+	/// its size limit or if they are out of the pruning window ending at `best_number`.
+	pub fn insert(
+		&self,
+		number: u32,
+		hash: H256,
+		traces: &[TransactionTrace],
+		best_number: u32,
+	) -> io::Result<()> {
+		let oldest = self
+			.config
+			.pruning
+			.map_or(0, |pruning| best_number.saturating_sub(pruning).saturating_add(1));
+		if number < oldest {
+			return Ok(())
+		}
+
+		let mut size = self.lock_size();
+		let key = Self::key(number, hash);
+		let value = traces.encode();
+
+		let mut transaction = DBTransaction::new();
+		let previous = self.db.get(columns::TRACES, &key)?.map_or(0, |value| value.len() as u64);
+		let mut new_size = size.saturating_sub(previous).saturating_add(value.len() as u64);
+		transaction.put_vec(columns::TRACES, &key, value);
+
+		for entry in self.db.iter(columns::TRACES) {
+			let (entry_key, entry_value) = entry?;
+			let entry_number = u32::from_be_bytes(
+				entry_key[..4]
+					.try_into()
+					.map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid key"))?,
+			);
+			if new_size <= self.config.max_size && entry_number >= oldest {
+				break
+			}
+			// Never prune the block being inserted because of the size limit.
+			if entry_key[..] == key[..] {
+				continue
+			}
+
+			transaction.delete(columns::TRACES, &entry_key);
+			new_size = new_size.saturating_sub(entry_value.len() as u64);
+		}
+
+		transaction.put_vec(columns::META, SIZE_KEY, new_size.encode());
+		self.db.write(transaction)?;
+		*size = new_size;
+
+		Ok(())
+	}
+
+	/// Remove the traces of all the blocks from the given number, so they are traced again.
+	pub fn clear_from(&self, from: u32) -> io::Result<()> {
+		let mut size = self.lock_size();
+
+		let mut transaction = DBTransaction::new();
+		let mut new_size = *size;
+		for entry in self.db.iter(columns::TRACES) {
+			let (key, value) = entry?;
+			if key[..4] >= from.to_be_bytes()[..] {
+				transaction.delete(columns::TRACES, &key);
+				new_size = new_size.saturating_sub(value.len() as u64);
+			}
+		}
+
+		transaction.put_vec(columns::META, SIZE_KEY, new_size.encode());
+		self.db.write(transaction)?;
+		*size = new_size;
+
+		Ok(())
+	}
+}
+
+fn invalid_data(e: parity_scale_codec::Error) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+/// Number of blocks traced in each batch when re-indexing.
+const REINDEX_BATCH_SIZE: u32 = 100;
+
+/// Trace the blocks through the `CacheTask`, which stores their traces.
+async fn index_blocks(requester: &CacheRequester, blocks: Vec<H256>) {
+	let batch_id = match requester.start_batch("trace_index", blocks.clone()).await {
+		Ok((batch_id, _)) => batch_id,
+		Err(e) => {
+			log::warn!(target: "tracing", "Failed to index blocks in the trace store: {}", e);
+			return
+		},
+	};
+	for block in blocks {
+		if let Err(e) = requester.get_traces(block).await {
+			log::warn!(target: "tracing", "Failed to index block {} in the trace store: {}", block, e);
+		}
+	}
+	requester.stop_batch(batch_id).await;
+}
+
+/// Task tracing the new best blocks as they are imported, so that their traces are stored before
+/// being requested.
+pub async fn index_on_import<B, C>(client: Arc<C>, requester: CacheRequester)
+where
+	B: BlockT<Hash = H256>,
+	C: BlockchainEvents<B>,
+{
+	let mut import_notifications = client.import_notification_stream();
+	while let Some(notification) = import_notifications.next().await {
+		if notification.is_new_best {
+			index_blocks(&requester, vec![notification.hash]).await;
+		}
+	}
+}
+
+/// Task removing the stored traces from the given block number, then tracing the blocks from it
+/// up to the current best block again.
+pub async fn reindex<B, C>(
+	client: Arc<C>,
+	requester: CacheRequester,
+	store: Arc<TraceStore>,
+	from: u32,
+) where
+	B: BlockT<Hash = H256>,
+	B::Header: HeaderT<Number = u32>,
+	C: HeaderBackend<B> + Send + Sync + 'static,
+{
+	let cleared = tokio::task::spawn_blocking(move || store.clear_from(from)).await;
+	if !matches!(cleared, Ok(Ok(()))) {
+		log::error!(target: "tracing", "Failed to clear the trace store: {:?}", cleared);
+		return
+	}
+
+	let best_number = client.info().best_number;
+	log::info!(target: "tracing", "Re-indexing traces of blocks #{} to #{}", from, best_number);
+
+	// No traces for genesis block.
+	for start in (from.max(1)..=best_number).step_by(REINDEX_BATCH_SIZE as usize) {
+		let end = start.saturating_add(REINDEX_BATCH_SIZE - 1).min(best_number);
+		let blocks =
+			(start..=end).filter_map(|number| client.hash(number).ok().flatten()).collect();
+		index_blocks(&requester, blocks).await;
+	}
+
+	log::info!(target: "tracing", "Re-indexed traces of blocks #{} to #{}", from, best_number);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::{H160, U256};
+	use peaq_client_evm_tracing::types::{
+		block::{TransactionTraceAction, TransactionTraceOutput, TransactionTraceResult},
+		CallType,
+	};
+
+	fn store(max_size: u64, pruning: Option<u32>) -> TraceStore {
+		TraceStore::new(
+			Arc::new(kvdb_memorydb::create(NUM_COLUMNS)),
+			TraceStoreConfig { max_size, pruning },
+		)
+		.unwrap()
+	}
+
+	fn reopen(store: &TraceStore) -> TraceStore {
+		TraceStore::new(store.db.clone(), store.config.clone()).unwrap()
+	}
+
+	fn traces(number: u32) -> Vec<TransactionTrace> {
+		vec![TransactionTrace {
+			action: TransactionTraceAction::Call {
+				call_type: CallType::Call,
+				from: H160::repeat_byte(1),
+				gas: U256::from(21_000),
+				input: vec![],
+				to: H160::repeat_byte(2),
+				value: U256::zero(),
+			},
+			block_hash: H256::repeat_byte(number as u8),
+			block_number: number,
+			output: TransactionTraceOutput::Result(TransactionTraceResult::Call {
+				gas_used: U256::from(21_000),
+				output: vec![],
+			}),
+			subtraces: 0,
+			trace_address: vec![],
+			transaction_hash: Some(H256::repeat_byte(3)),
+			transaction_position: Some(0),
+			extrinsic_index: Some(1),
+		}]
+	}
+
+	#[test]
+	fn stores_traces() {
+		let store = store(u64::MAX, None);
+		let hash = H256::repeat_byte(1);
+		assert_eq!(store.get(1, hash).unwrap(), None);
+
+		store.insert(1, hash, &traces(1), 1).unwrap();
+		assert_eq!(store.get(1, hash).unwrap(), Some(traces(1)));
+		assert_eq!(store.get(2, hash).unwrap(), None);
+		assert_eq!(store.size(), traces(1).encoded_size() as u64);
+
+		store.clear_from(1).unwrap();
+		assert_eq!(store.get(1, hash).unwrap(), None);
+		assert_eq!(store.size(), 0);
+	}
+
+	#[test]
+	fn prunes_oldest_blocks_above_size_limit() {
+		let block_size = traces(1).encoded_size() as u64;
+		let store = store(2 * block_size, None);
+		for number in 1..=3 {
+			store
+				.insert(number, H256::repeat_byte(number as u8), &traces(number), number)
+				.unwrap();
+		}
+
+		assert_eq!(store.get(1, H256::repeat_byte(1)).unwrap(), None);
+		assert!(store.get(2, H256::repeat_byte(2)).unwrap().is_some());
+		assert!(store.get(3, H256::repeat_byte(3)).unwrap().is_some());
+		assert_eq!(store.size(), 2 * block_size);
+	}
+
+	#[test]
+	fn prunes_blocks_out_of_window() {
+		let store = store(u64::MAX, Some(2));
+		for number in [1, 5, 9] {
+			store
+				.insert(number, H256::repeat_byte(number as u8), &traces(number), number)
+				.unwrap();
+		}
+		// Blocks out of the window are not stored.
+		store.insert(7, H256::repeat_byte(7), &traces(7), 10).unwrap();
+		store.insert(10, H256::repeat_byte(10), &traces(10), 10).unwrap();
+
+		assert_eq!(store.get(1, H256::repeat_byte(1)).unwrap(), None);
+		assert_eq!(store.get(5, H256::repeat_byte(5)).unwrap(), None);
+		assert_eq!(store.get(7, H256::repeat_byte(7)).unwrap(), None);
+		assert!(store.get(9, H256::repeat_byte(9)).unwrap().is_some());
+		assert!(store.get(10, H256::repeat_byte(10)).unwrap().is_some());
+	}
+
+	#[test]
+	fn keeps_traces_of_the_same_schema_version() {
+		let store = store(u64::MAX, None);
+		store.insert(1, H256::repeat_byte(1), &traces(1), 1).unwrap();
+
+		let store = reopen(&store);
+		assert_eq!(store.get(1, H256::repeat_byte(1)).unwrap(), Some(traces(1)));
+		assert_eq!(store.size(), traces(1).encoded_size() as u64);
+	}
+
+	#[test]
+	fn wipes_traces_of_another_schema_version() {
+		// Stores predating the schema version have no version at all.
+		for version in [Some(SCHEMA_VERSION + 1), None] {
+			let store = store(u64::MAX, None);
+			store.insert(1, H256::repeat_byte(1), &traces(1), 1).unwrap();
+			let mut transaction = DBTransaction::new();
+			match version {
+				Some(version) => transaction.put_vec(columns::META, VERSION_KEY, version.encode()),
+				None => transaction.delete(columns::META, VERSION_KEY),
+			}
+			store.db.write(transaction).unwrap();
+
+			let store = reopen(&store);
+			assert_eq!(store.get(1, H256::repeat_byte(1)).unwrap(), None);
+			assert_eq!(store.size(), 0);
+			assert_eq!(
+				store.db.get(columns::META, VERSION_KEY).unwrap(),
+				Some(SCHEMA_VERSION.encode())
+			);
+		}
+	}
+}
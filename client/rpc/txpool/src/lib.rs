@@ -16,9 +16,14 @@
 
 use ethereum_types::{H160, H256, U256};
 use fc_rpc::{internal_err, public_key};
-use jsonrpsee::core::RpcResult;
+use fp_rpc::EthereumRuntimeRPCApi;
+use futures::{select, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{RpcResult, SubscriptionResult},
+	PendingSubscriptionSink, SubscriptionMessage,
+};
 pub use peaq_rpc_core_txpool::{
-	GetT, Summary, Transaction, TransactionMap, TxPoolResult, TxPoolServer,
+	GetT, NonceGaps, NonceRange, Summary, Transaction, TransactionMap, TxPoolResult, TxPoolServer,
 };
 use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::InPoolTransaction;
@@ -27,7 +32,7 @@ use sha3::{Digest, Keccak256};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_runtime::traits::Block as BlockT;
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 
 use peaq_rpc_primitives_txpool::{Transaction as TransactionV2, TxPoolResponse, TxPoolRuntimeApi};
 
@@ -48,15 +53,21 @@ where
 {
 	/// Use the transaction graph interface to get the extrinsics currently in the ready and future
 	/// queues.
-	fn map_build<T>(&self) -> RpcResult<TxPoolResult<TransactionMap<T>>>
-	where
-		T: GetT + Serialize,
-	{
+	///
+	/// If a sender is given, the ready extrinsics of other senders are skipped: Ethereum
+	/// transactions provide the `(sender, nonce)` tag, which avoids matching the whole ready
+	/// queue in the runtime.
+	fn pool_extrinsics(&self, sender: Option<H160>) -> (Vec<B::Extrinsic>, Vec<B::Extrinsic>) {
 		// Collect transactions in the ready validated pool.
 		let txs_ready = self
 			.graph
 			.validated_pool()
 			.ready()
+			.filter(|in_pool_tx| {
+				sender.map_or(true, |sender| {
+					in_pool_tx.provides().iter().any(|tag| tag.starts_with(sender.as_bytes()))
+				})
+			})
 			.map(|in_pool_tx| in_pool_tx.data().clone())
 			.collect();
 
@@ -69,7 +80,15 @@ where
 			.map(|(_hash, extrinsic)| extrinsic.clone())
 			.collect();
 
-		// Use the runtime to match the (here) opaque extrinsics against ethereum transactions.
+		(txs_ready, txs_future)
+	}
+
+	/// Use the runtime to match the (here) opaque extrinsics against ethereum transactions.
+	fn ethereum_transactions(
+		&self,
+		txs_ready: Vec<B::Extrinsic>,
+		txs_future: Vec<B::Extrinsic>,
+	) -> RpcResult<TxPoolResponse> {
 		let best_block = self.client.info().best_hash;
 		let api = self.client.runtime_api();
 		let api_version =
@@ -78,57 +97,57 @@ where
 			} else {
 				return Err(internal_err("failed to retrieve Runtime Api version".to_string()));
 			};
-		let ethereum_txns: TxPoolResponse = if api_version == 1 {
+		if api_version == 1 {
 			#[allow(deprecated)]
 			let res = api.extrinsic_filter_before_version_2(best_block, txs_ready, txs_future)
 				.map_err(|err| {
 					internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
 				})?;
-			TxPoolResponse {
+			Ok(TxPoolResponse {
 				ready: res.ready.iter().map(|t| TransactionV2::Legacy(t.clone())).collect(),
 				future: res.future.iter().map(|t| TransactionV2::Legacy(t.clone())).collect(),
-			}
+			})
 		} else {
 			api.extrinsic_filter(best_block, txs_ready, txs_future).map_err(|err| {
 				internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
-			})?
-		};
-		// Build the T response.
-		let mut pending = TransactionMap::<T>::new();
-		for txn in ethereum_txns.ready.iter() {
-			let hash = txn.hash();
-			let nonce = match txn {
-				TransactionV2::Legacy(t) => t.nonce,
-				TransactionV2::EIP2930(t) => t.nonce,
-				TransactionV2::EIP1559(t) => t.nonce,
-			};
-			let from_address = match public_key(txn) {
-				Ok(pk) => H160::from(H256::from_slice(Keccak256::digest(pk).as_slice())),
-				Err(_e) => H160::default(),
-			};
-			pending
-				.entry(from_address)
-				.or_default()
-				.insert(nonce, T::get(hash, from_address, txn));
+			})
 		}
-		let mut queued = TransactionMap::<T>::new();
-		for txn in ethereum_txns.future.iter() {
-			let hash = txn.hash();
-			let nonce = match txn {
-				TransactionV2::Legacy(t) => t.nonce,
-				TransactionV2::EIP2930(t) => t.nonce,
-				TransactionV2::EIP1559(t) => t.nonce,
-			};
-			let from_address = match public_key(txn) {
-				Ok(pk) => H160::from(H256::from_slice(Keccak256::digest(pk).as_slice())),
-				Err(_e) => H160::default(),
-			};
-			queued
-				.entry(from_address)
-				.or_default()
-				.insert(nonce, T::get(hash, from_address, txn));
-		}
-		Ok(TxPoolResult { pending, queued })
+	}
+
+	fn map_build<T>(&self) -> RpcResult<TxPoolResult<TransactionMap<T>>>
+	where
+		T: GetT + Serialize,
+	{
+		let (txs_ready, txs_future) = self.pool_extrinsics(None);
+		let ethereum_txns = self.ethereum_transactions(txs_ready, txs_future)?;
+
+		// Build the T response.
+		let map = |txns: &[TransactionV2]| {
+			let mut map = TransactionMap::<T>::new();
+			for txn in txns {
+				let from_address = sender(txn);
+				map.entry(from_address)
+					.or_default()
+					.insert(nonce(txn), T::get(txn.hash(), from_address, txn));
+			}
+			map
+		};
+		Ok(TxPoolResult { pending: map(&ethereum_txns.ready), queued: map(&ethereum_txns.future) })
+	}
+}
+
+fn sender(txn: &TransactionV2) -> H160 {
+	match public_key(txn) {
+		Ok(pk) => H160::from(H256::from_slice(Keccak256::digest(pk).as_slice())),
+		Err(_e) => H160::default(),
+	}
+}
+
+fn nonce(txn: &TransactionV2) -> U256 {
+	match txn {
+		TransactionV2::Legacy(t) => t.nonce,
+		TransactionV2::EIP2930(t) => t.nonce,
+		TransactionV2::EIP1559(t) => t.nonce,
 	}
 }
 
@@ -138,6 +157,7 @@ impl<B: BlockT, C, A: ChainApi> TxPool<B, C, A> {
 	}
 }
 
+#[jsonrpsee::core::async_trait]
 impl<B, C, A> TxPoolServer for TxPool<B, C, A>
 where
 	C: ProvideRuntimeApi<B>,
@@ -145,7 +165,7 @@ where
 	C: Send + Sync + 'static,
 	B: BlockT<Hash = H256> + Send + Sync + 'static,
 	A: ChainApi<Block = B> + 'static,
-	C::Api: TxPoolRuntimeApi<B>,
+	C::Api: TxPoolRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
 {
 	fn content(&self) -> RpcResult<TxPoolResult<TransactionMap<Transaction>>> {
 		self.map_build::<Transaction>()
@@ -159,4 +179,82 @@ where
 		let status = self.graph.validated_pool().status();
 		Ok(TxPoolResult { pending: U256::from(status.ready), queued: U256::from(status.future) })
 	}
+
+	fn content_from(&self, address: H160) -> RpcResult<TxPoolResult<HashMap<U256, Transaction>>> {
+		let (txs_ready, txs_future) = self.pool_extrinsics(Some(address));
+		let ethereum_txns = self.ethereum_transactions(txs_ready, txs_future)?;
+
+		let by_nonce = |txns: &[TransactionV2]| {
+			txns.iter()
+				.filter(|txn| sender(txn) == address)
+				.map(|txn| (nonce(txn), Transaction::get(txn.hash(), address, txn)))
+				.collect()
+		};
+		Ok(TxPoolResult {
+			pending: by_nonce(&ethereum_txns.ready),
+			queued: by_nonce(&ethereum_txns.future),
+		})
+	}
+
+	fn nonce_gaps(&self, address: H160) -> RpcResult<NonceGaps> {
+		let best_block = self.client.info().best_hash;
+		let account =
+			self.client.runtime_api().account_basic(best_block, address).map_err(|err| {
+				internal_err(format!("fetch runtime account basic failed: {:?}", err))
+			})?;
+
+		let content = self.content_from(address)?;
+		Ok(NonceGaps::new(
+			account.nonce,
+			content.pending.into_keys().chain(content.queued.into_keys()),
+		))
+	}
+
+	async fn subscribe_pending(
+		&self,
+		pending: PendingSubscriptionSink,
+		from_address: Option<Vec<H160>>,
+	) -> SubscriptionResult {
+		let from_address = from_address.unwrap_or_default();
+		// Notifies the hashes of the transactions imported in (or promoted to) the ready queue.
+		let mut imported = self.graph.validated_pool().import_notification_stream().fuse();
+
+		let sink = pending.accept().await?;
+		loop {
+			let hash = select! {
+				hash = imported.next() => match hash {
+					Some(hash) => hash,
+					None => break,
+				},
+				_ = sink.closed().fuse() => break,
+			};
+
+			let Some(in_pool_tx) = self.graph.validated_pool().ready_by_hash(&hash) else {
+				continue
+			};
+			let ethereum_txns =
+				match self.ethereum_transactions(vec![in_pool_tx.data().clone()], vec![]) {
+					Ok(ethereum_txns) => ethereum_txns,
+					Err(e) => {
+						log::warn!(
+							target: "txpool",
+							"Failed to match pending transaction {:?}: {:?}",
+							hash,
+							e
+						);
+						continue
+					},
+				};
+
+			for txn in ethereum_txns.ready {
+				let from = sender(&txn);
+				if from_address.is_empty() || from_address.contains(&from) {
+					let transaction = Transaction::get(txn.hash(), from, &txn);
+					sink.send(SubscriptionMessage::from_json(&transaction)?).await?;
+				}
+			}
+		}
+
+		Ok(())
+	}
 }
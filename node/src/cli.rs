@@ -16,10 +16,22 @@ pub struct RunCmd {
 	#[clap(long, conflicts_with = "validator", conflicts_with = "collator", value_delimiter = ',')]
 	pub ethapi: Vec<EthApi>,
 
-	/// Number of concurrent tracing tasks. Meant to be shared by both "debug" and "trace" modules.
+	/// Number of concurrent tracing tasks. Meant to be shared by both "debug" and "trace" modules,
+	/// and by the block replays of the fee details RPC.
 	#[clap(long, default_value = "10")]
 	pub ethapi_max_permits: u32,
 
+	/// Maximum number of tracing requests waiting for a permit. Requests above it are rejected.
+	/// A "trace" request holds a single slot until all its blocks are traced.
+	#[clap(long, default_value = "500")]
+	pub ethapi_max_queued_requests: usize,
+
+	/// Duration (in seconds) after which a tracing request is aborted, including the time spent
+	/// waiting for a permit. It covers all the blocks of a "trace" request. The `timeout` of
+	/// "debug" requests can only lower it.
+	#[clap(long, default_value = "60")]
+	pub ethapi_trace_timeout: u64,
+
 	/// Size in bytes of data a raw tracing request is allowed to use.
 	/// Bound the size of memory, stack and storage data.
 	#[clap(long, default_value = "20000000")]
@@ -31,11 +43,43 @@ pub struct RunCmd {
 	#[clap(long, default_value = "500")]
 	pub ethapi_trace_max_count: u32,
 
+	/// Maximum number of blocks a single request of `trace_filter` is allowed to trace.
+	#[clap(long, default_value = "1000")]
+	pub ethapi_trace_max_block_range: u32,
+
 	/// Duration (in seconds) after which the cache of `trace_filter` for a given block will be
 	/// discarded.
 	#[clap(long, default_value = "300")]
 	pub ethapi_trace_cache_duration: u64,
 
+	/// Maximum number of concurrent `trace_subscribe` subscriptions. Subscriptions above it are
+	/// rejected.
+	#[clap(long, default_value = "100")]
+	pub ethapi_trace_max_subscriptions: u32,
+
+	/// Persist the traces computed for `trace_filter` in an on-disk store next to the Frontier
+	/// database, so they survive the cache expiration and node restarts.
+	#[clap(long)]
+	pub ethapi_trace_store: bool,
+
+	/// Maximum size (in MiB) of the trace store. The traces of the oldest blocks are pruned above
+	/// it.
+	#[clap(long, default_value = "10240")]
+	pub ethapi_trace_store_max_size: u64,
+
+	/// Only keep the traces of this number of most recent blocks in the trace store.
+	#[clap(long)]
+	pub ethapi_trace_store_pruning: Option<u32>,
+
+	/// Trace new best blocks as they are imported to write them to the trace store.
+	#[clap(long, requires = "ethapi_trace_store")]
+	pub ethapi_trace_store_index_on_import: bool,
+
+	/// Drop the stored traces from this block number, and trace the blocks from it up to the best
+	/// block again in the background.
+	#[clap(long, requires = "ethapi_trace_store")]
+	pub ethapi_trace_store_reindex: Option<u32>,
+
 	/// Size of the LRU cache for block data and their transaction statuses.
 	#[clap(long, default_value = "3000")]
 	pub eth_log_block_cache: usize,
@@ -23,8 +23,17 @@ impl FromStr for EthApi {
 pub struct RpcConfig {
 	pub ethapi: Vec<EthApi>,
 	pub ethapi_max_permits: u32,
+	pub ethapi_max_queued_requests: usize,
+	pub ethapi_trace_timeout: u64,
 	pub ethapi_trace_max_count: u32,
+	pub ethapi_trace_max_block_range: u32,
 	pub ethapi_trace_cache_duration: u64,
+	pub ethapi_trace_max_subscriptions: u32,
+	pub ethapi_trace_store: bool,
+	pub ethapi_trace_store_max_size: u64,
+	pub ethapi_trace_store_pruning: Option<u32>,
+	pub ethapi_trace_store_index_on_import: bool,
+	pub ethapi_trace_store_reindex: Option<u32>,
 	pub eth_log_block_cache: usize,
 	pub eth_statuses_cache: usize,
 	pub fee_history_limit: u64,
@@ -426,8 +426,17 @@ pub fn run() -> sc_cli::Result<()> {
 				let rpc_config = RpcConfig {
 					ethapi: cli.run.ethapi.clone(),
 					ethapi_max_permits: cli.run.ethapi_max_permits,
+					ethapi_max_queued_requests: cli.run.ethapi_max_queued_requests,
+					ethapi_trace_timeout: cli.run.ethapi_trace_timeout,
 					ethapi_trace_max_count: cli.run.ethapi_trace_max_count,
+					ethapi_trace_max_block_range: cli.run.ethapi_trace_max_block_range,
 					ethapi_trace_cache_duration: cli.run.ethapi_trace_cache_duration,
+					ethapi_trace_max_subscriptions: cli.run.ethapi_trace_max_subscriptions,
+					ethapi_trace_store: cli.run.ethapi_trace_store,
+					ethapi_trace_store_max_size: cli.run.ethapi_trace_store_max_size,
+					ethapi_trace_store_pruning: cli.run.ethapi_trace_store_pruning,
+					ethapi_trace_store_index_on_import: cli.run.ethapi_trace_store_index_on_import,
+					ethapi_trace_store_reindex: cli.run.ethapi_trace_store_reindex,
 					eth_log_block_cache: cli.run.eth_log_block_cache,
 					eth_statuses_cache: cli.run.eth_statuses_cache,
 					fee_history_limit: cli.run.fee_history_limit,
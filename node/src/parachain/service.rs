@@ -304,6 +304,7 @@ where
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ peaq_pallet_storage_rpc::PeaqStorageRuntimeApi<Block, AccountId>
 		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>
+		+ peaq_primitives_fee::FeeDetailsApi<Block, AccountId, StorageAssetId, Balance>
 		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>,
 	sc_client_api::StateBackendFor<FullBackend, Block>:
 		sc_client_api::backend::StateBackend<BlakeTwo256>,
@@ -443,11 +444,30 @@ where
 	);
 
 	let ethapi_cmd = rpc_config.ethapi.clone();
+	let trace_store = if ethapi_cmd.contains(&EthApiCmd::Trace) && rpc_config.ethapi_trace_store {
+		Some(Arc::new(peaq_rpc_trace::TraceStore::open(
+			&frontier_database_dir(&parachain_config, "traces"),
+			peaq_rpc_trace::TraceStoreConfig {
+				max_size: rpc_config.ethapi_trace_store_max_size.saturating_mul(1024 * 1024),
+				pruning: rpc_config.ethapi_trace_store_pruning,
+			},
+		)?))
+	} else {
+		None
+	};
+	let tracing_permits = Arc::new(peaq_client_evm_tracing::permits::TracingPermits::new(
+		rpc_config.ethapi_max_permits as usize,
+		rpc_config.ethapi_max_queued_requests,
+		Duration::from_secs(rpc_config.ethapi_trace_timeout),
+		prometheus_registry.as_ref(),
+	));
 	let tracing_requesters =
 		if ethapi_cmd.contains(&EthApiCmd::Debug) || ethapi_cmd.contains(&EthApiCmd::Trace) {
 			crate::rpc::tracing::spawn_tracing_tasks(
 				&rpc_config,
 				prometheus_registry.clone(),
+				trace_store,
+				Arc::clone(&tracing_permits),
 				crate::rpc::SpawnTasksParams {
 					task_manager: &task_manager,
 					client: client.clone(),
@@ -485,6 +505,7 @@ where
 		let overrides = overrides.clone();
 		let fee_history_cache = fee_history_cache.clone();
 		let block_data_cache = block_data_cache.clone();
+		let tracing_permits = tracing_permits.clone();
 
 		move |deny_unsafe, subscription_task_executor| {
 			let deps = crate::rpc::FullDeps {
@@ -509,6 +530,7 @@ where
 				overrides: overrides.clone(),
 				block_data_cache: block_data_cache.clone(),
 				forced_parent_hashes: None,
+				tracing_permits: tracing_permits.clone(),
 			};
 
 			if ethapi_cmd.contains(&EthApiCmd::Debug) || ethapi_cmd.contains(&EthApiCmd::Trace) {
@@ -518,6 +540,8 @@ where
 					Some(crate::rpc::TracingConfig {
 						tracing_requesters: tracing_requesters.clone(),
 						trace_filter_max_count: rpc_config.ethapi_trace_max_count,
+						trace_filter_max_block_range: rpc_config.ethapi_trace_max_block_range,
+						trace_max_subscriptions: rpc_config.ethapi_trace_max_subscriptions,
 					}),
 				)
 				.map_err(Into::into)
@@ -691,6 +715,7 @@ where
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
 		+ peaq_pallet_storage_rpc::PeaqStorageRuntimeApi<Block, AccountId>
 		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>
+		+ peaq_primitives_fee::FeeDetailsApi<Block, AccountId, StorageAssetId, Balance>
 		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>,
 {
 	start_contracts_node_impl::<RuntimeApi, _, _>(
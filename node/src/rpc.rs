@@ -6,12 +6,13 @@ use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 use fc_rpc::{EthBlockDataCacheTask, OverrideHandle};
 use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
 use jsonrpsee::RpcModule;
+use peaq_client_evm_tracing::permits::TracingPermits;
 use peaq_primitives_xcm::*;
 use polkadot_primitives::PersistedValidationData;
 use sc_client_api::{
 	backend::{AuxStore, Backend, StateBackend, StorageProvider},
 	client::BlockchainEvents,
-	UsageProvider,
+	BlockBackend, UsageProvider,
 };
 use sc_consensus_manual_seal::rpc::EngineCommand;
 use sc_network::NetworkService;
@@ -31,6 +32,7 @@ use sp_runtime::traits::{BlakeTwo256, Block as BlockT};
 use std::{collections::BTreeMap, sync::Arc};
 use zenlink_protocol::AssetId as ZenlinkAssetId;
 
+pub mod did;
 pub mod tracing;
 use crate::cli_opt::EthApi as EthApiCmd;
 
@@ -103,11 +105,15 @@ pub struct FullDeps<C, P, A: ChainApi, BE> {
 	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
 	/// Mandated parent hashes for a given block hash.
 	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
+	/// Permits bounding the concurrent block replays, shared by the tracing and fee RPCs.
+	pub tracing_permits: Arc<TracingPermits>,
 }
 
 pub struct TracingConfig {
 	pub tracing_requesters: crate::rpc::tracing::RpcRequesters,
 	pub trace_filter_max_count: u32,
+	pub trace_filter_max_block_range: u32,
+	pub trace_max_subscriptions: u32,
 }
 
 /// Instantiate all full RPC extensions.
@@ -121,7 +127,7 @@ where
 	BE::State: StateBackend<BlakeTwo256>,
 	// BE::Blockchain: BlockchainBackend<Block>,
 	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore + UsageProvider<Block>,
-	C: BlockchainEvents<Block>,
+	C: BlockchainEvents<Block> + BlockBackend<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
 	C: CallApiAt<Block>,
 	C: Send + Sync + 'static,
@@ -137,11 +143,13 @@ where
 	C::Api: peaq_rpc_primitives_txpool::TxPoolRuntimeApi<Block>,
 	C::Api: peaq_pallet_storage_rpc::PeaqStorageRuntimeApi<Block, AccountId>,
 	C::Api: zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId>,
+	C::Api: peaq_primitives_fee::FeeDetailsApi<Block, AccountId, StorageAssetId, Balance>,
 	P: TransactionPool<Block = Block> + 'static,
 	A: ChainApi<Block = Block> + 'static,
 
 	BE::Blockchain: BlockchainBackend<Block>,
 {
+	use did::{PeaqDIDDocument, PeaqDIDDocumentApiServer};
 	use fc_rpc::{
 		Eth, EthApiServer, EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, Net,
 		NetApiServer, Web3, Web3ApiServer,
@@ -151,6 +159,7 @@ where
 	use peaq_pallet_rbac_rpc::{PeaqRBAC, PeaqRBACApiServer};
 	use peaq_pallet_storage_rpc::{PeaqStorage, PeaqStorageApiServer};
 	use peaq_rpc_debug::{Debug, DebugServer};
+	use peaq_rpc_fee_details::{FeeDetails, FeeDetailsServer};
 	use peaq_rpc_trace::{Trace, TraceServer};
 	use peaq_rpc_txpool::{TxPool, TxPoolServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -177,6 +186,7 @@ where
 		overrides,
 		block_data_cache,
 		forced_parent_hashes,
+		tracing_permits,
 	} = deps;
 
 	io.merge(System::new(Arc::clone(&client), Arc::clone(&pool), deny_unsafe).into_rpc())?;
@@ -255,7 +265,7 @@ where
 		io.merge(
 			EthFilter::new(
 				client.clone(),
-				frontier_backend,
+				Arc::clone(&frontier_backend),
 				graph.clone(),
 				filter_pool,
 				500_usize, // max stored filters
@@ -283,8 +293,13 @@ where
 
 	io.merge(PeaqStorage::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(PeaqDID::new(Arc::clone(&client)).into_rpc())?;
+	io.merge(PeaqDIDDocument::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(PeaqRBAC::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(ZenlinkProtocol::new(Arc::clone(&client)).into_rpc())?;
+	io.merge(
+		FeeDetails::new(Arc::clone(&client), Arc::clone(&frontier_backend), tracing_permits)
+			.into_rpc(),
+	)?;
 	io.merge(Web3::new(Arc::clone(&client)).into_rpc())?;
 	io.merge(
 		EthPubSub::new(
@@ -304,8 +319,15 @@ where
 	if let Some(tracing_config) = maybe_tracing_config {
 		if let Some(trace_filter_requester) = tracing_config.tracing_requesters.trace {
 			io.merge(
-				Trace::new(client, trace_filter_requester, tracing_config.trace_filter_max_count)
-					.into_rpc(),
+				Trace::new(
+					client,
+					frontier_backend,
+					trace_filter_requester,
+					tracing_config.trace_filter_max_count,
+					tracing_config.trace_filter_max_block_range,
+					tracing_config.trace_max_subscriptions as usize,
+				)
+				.into_rpc(),
 			)?;
 		}
 
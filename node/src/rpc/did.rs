@@ -0,0 +1,82 @@
+//! Resolution of peaq DIDs into W3C-style documents.
+
+use fc_rpc::internal_err;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use parity_scale_codec::Encode;
+use peaq_pallet_did_rpc::PeaqDIDRuntimeApi;
+use peaq_primitives_did::{self as did_document, DocumentAttribute};
+use peaq_primitives_xcm::{AccountId, Block, BlockNumber, Hash, Moment};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use std::sync::Arc;
+
+#[rpc(server)]
+pub trait PeaqDIDDocumentApi {
+	/// Returns the JSON document of a DID, built from its valid attributes among `names`.
+	///
+	/// The attributes of a DID cannot be enumerated from storage, so the caller passes the
+	/// names it expects.
+	#[method(name = "peaqdid_readDocument")]
+	fn read_document(
+		&self,
+		did_account: AccountId,
+		names: Vec<Bytes>,
+		at: Option<Hash>,
+	) -> RpcResult<String>;
+}
+
+pub struct PeaqDIDDocument<C> {
+	client: Arc<C>,
+}
+
+impl<C> PeaqDIDDocument<C> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> PeaqDIDDocumentApiServer for PeaqDIDDocument<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PeaqDIDRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+{
+	fn read_document(
+		&self,
+		did_account: AccountId,
+		names: Vec<Bytes>,
+		at: Option<Hash>,
+	) -> RpcResult<String> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let now = self
+			.client
+			.number(at)
+			.map_err(|err| internal_err(format!("fetch block number failed: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("unknown block {:?}", at)))?;
+
+		let api = self.client.runtime_api();
+		let mut attributes = Vec::new();
+		for name in names {
+			let attribute = api
+				.read(at, did_account.clone(), name.to_vec())
+				.map_err(|err| internal_err(format!("read attribute failed: {:?}", err)))?;
+			if let Some(attribute) = attribute {
+				if did_document::is_valid(attribute.validity, now) {
+					attributes.push(attribute);
+				}
+			}
+		}
+
+		let document = did_document::document(
+			&did_account.encode(),
+			attributes.iter().map(|attribute| DocumentAttribute {
+				name: &attribute.name,
+				value: &attribute.value,
+				validity: attribute.validity,
+				created: attribute.created.into(),
+			}),
+		);
+		String::from_utf8(document)
+			.map_err(|err| internal_err(format!("invalid document: {:?}", err)))
+	}
+}
@@ -16,10 +16,10 @@
 
 use super::*;
 
+use peaq_client_evm_tracing::permits::TracingPermits;
 use peaq_rpc_debug::{DebugHandler, DebugRequester};
-use peaq_rpc_trace::{CacheRequester as TraceFilterCacheRequester, CacheTask};
+use peaq_rpc_trace::{CacheRequester as TraceFilterCacheRequester, CacheTask, TraceStore};
 use substrate_prometheus_endpoint::Registry as PrometheusRegistry;
-use tokio::sync::Semaphore;
 
 use crate::cli_opt::EthApi as EthApiCmd;
 use fp_rpc::EthereumRuntimeRPCApi;
@@ -39,6 +39,8 @@ pub struct RpcRequesters {
 pub fn spawn_tracing_tasks<B, C, BE>(
 	rpc_config: &crate::cli_opt::RpcConfig,
 	prometheus: Option<PrometheusRegistry>,
+	trace_store: Option<Arc<TraceStore>>,
+	permit_pool: Arc<TracingPermits>,
 	params: SpawnTasksParams<B, C, BE>,
 ) -> RpcRequesters
 where
@@ -54,8 +56,6 @@ where
 	BE: Backend<B> + 'static,
 	BE::State: StateBackend<BlakeTwo256>,
 {
-	let permit_pool = Arc::new(Semaphore::new(rpc_config.ethapi_max_permits as usize));
-
 	let (trace_filter_task, trace_filter_requester) =
 		if rpc_config.ethapi.contains(&EthApiCmd::Trace) {
 			let (trace_filter_task, trace_filter_requester) = CacheTask::create(
@@ -64,6 +64,7 @@ where
 				Duration::from_secs(rpc_config.ethapi_trace_cache_duration),
 				Arc::clone(&permit_pool),
 				Arc::clone(&params.overrides),
+				trace_store.clone(),
 				prometheus,
 			);
 			(Some(trace_filter_task), Some(trace_filter_requester))
@@ -97,6 +98,33 @@ where
 		);
 	}
 
+	// Trace store indexing tasks if enabled.
+	if let (Some(trace_store), Some(requester)) = (trace_store, &trace_filter_requester) {
+		if let Some(from) = rpc_config.ethapi_trace_store_reindex {
+			params.task_manager.spawn_handle().spawn(
+				"trace-store-reindex",
+				Some("eth-tracing"),
+				peaq_rpc_trace::reindex::<B, C>(
+					Arc::clone(&params.client),
+					requester.clone(),
+					trace_store,
+					from,
+				),
+			);
+		}
+
+		if rpc_config.ethapi_trace_store_index_on_import {
+			params.task_manager.spawn_handle().spawn(
+				"trace-store-indexer",
+				Some("eth-tracing"),
+				peaq_rpc_trace::index_on_import::<B, C>(
+					Arc::clone(&params.client),
+					requester.clone(),
+				),
+			);
+		}
+	}
+
 	// `debug` task if enabled. Essential.
 	// Proxies rpc requests to it's handler.
 	if let Some(debug_task) = debug_task {
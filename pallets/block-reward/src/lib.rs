@@ -212,27 +212,17 @@ pub mod pallet {
 		/// # Arguments
 		/// * `imbalance` - imbalance that will be split and distributed
 		fn distribute_imbalances(imbalance: NegativeImbalanceOf<T>, dpt_event: Event<T>) {
-			let distro_params = Self::reward_config();
-
-			// Pre-calculate balance which will be deposited for each beneficiary
-			let collator_delegator_balance =
-				distro_params.collators_delegators_percent * imbalance.peek();
-			let coretime_balance = distro_params.coretime_percent * imbalance.peek();
-			let subsidization_pool_balance =
-				distro_params.subsidization_pool_percent * imbalance.peek();
-			let depin_staking_balance = distro_params.depin_staking_percent * imbalance.peek();
-			let depin_incentivization_balance =
-				distro_params.depin_incentivization_percent * imbalance.peek();
+			let distribution = Self::reward_config().split(imbalance.peek());
 
 			// Prepare imbalances
 			let (collator_delegator_imbalance, remainder) =
-				imbalance.split(collator_delegator_balance);
-			let (coretime_imbalance, remainder) = remainder.split(coretime_balance);
+				imbalance.split(distribution.collators_delegators);
+			let (coretime_imbalance, remainder) = remainder.split(distribution.coretime);
 			let (subsidization_pool_imbalance, remainder) =
-				remainder.split(subsidization_pool_balance);
-			let (depin_staking_imbalance, remainder) = remainder.split(depin_staking_balance);
+				remainder.split(distribution.subsidization_pool);
+			let (depin_staking_imbalance, remainder) = remainder.split(distribution.depin_staking);
 			let (depin_incentivization_imbalance, treasury_imbalance) =
-				remainder.split(depin_incentivization_balance);
+				remainder.split(distribution.depin_incentivization);
 
 			// Payout beneficiaries
 			T::BeneficiaryPayout::treasury(treasury_imbalance);
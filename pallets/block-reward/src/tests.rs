@@ -98,6 +98,33 @@ fn reward_distribution_config_not_consistent() {
 	assert!(!reward_config.is_consistent());
 }
 
+#[test]
+fn reward_distribution_config_split() {
+	let reward_config = RewardDistributionConfig::default();
+
+	let distribution = reward_config.split(1000u128);
+	assert_eq!(distribution.treasury, 250);
+	assert_eq!(distribution.collators_delegators, 400);
+	assert_eq!(distribution.coretime, 100);
+	assert_eq!(distribution.subsidization_pool, 50);
+	assert_eq!(distribution.depin_staking, 50);
+	assert_eq!(distribution.depin_incentivization, 150);
+
+	// The treasury gets whatever is left, so nothing is lost to rounding
+	for amount in [0u128, 1, 7, 999, 123_456_789] {
+		let distribution = reward_config.split(amount);
+		assert_eq!(
+			distribution.treasury +
+				distribution.collators_delegators +
+				distribution.coretime +
+				distribution.subsidization_pool +
+				distribution.depin_staking +
+				distribution.depin_incentivization,
+			amount
+		);
+	}
+}
+
 #[test]
 pub fn set_configuration_fails() {
 	ExternalityBuilder::build().execute_with(|| {
@@ -1,7 +1,11 @@
 //! Type and trait definitions of the crate
 
 use frame_support::{pallet_prelude::*, traits::Currency};
-use sp_runtime::{traits::CheckedAdd, Perbill};
+use peaq_primitives_fee::RewardDistribution;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, CheckedAdd},
+	Perbill,
+};
 use sp_std::vec;
 
 use crate::pallet::Config as PalletConfig;
@@ -166,4 +170,33 @@ impl RewardDistributionConfig {
 
 		Perbill::one() == accumulator
 	}
+
+	/// Splits `amount` between the beneficiaries, as done when distributing an imbalance: each
+	/// beneficiary gets its share in turn and the treasury gets the remainder.
+	pub fn split<Balance: AtLeast32BitUnsigned + Copy>(
+		&self,
+		amount: Balance,
+	) -> RewardDistribution<Balance> {
+		let mut remainder = amount;
+		let mut take = |percent: Perbill| {
+			let share = (percent * amount).min(remainder);
+			remainder -= share;
+			share
+		};
+
+		let collators_delegators = take(self.collators_delegators_percent);
+		let coretime = take(self.coretime_percent);
+		let subsidization_pool = take(self.subsidization_pool_percent);
+		let depin_staking = take(self.depin_staking_percent);
+		let depin_incentivization = take(self.depin_incentivization_percent);
+
+		RewardDistribution {
+			treasury: remainder,
+			collators_delegators,
+			coretime,
+			subsidization_pool,
+			depin_staking,
+			depin_incentivization,
+		}
+	}
 }
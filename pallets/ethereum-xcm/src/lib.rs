@@ -0,0 +1,246 @@
+//! # Ethereum XCM Pallet
+//!
+//! ## Overview
+//!
+//! This pallet lets an XCM origin execute an EVM call on this chain through the `Transact`
+//! instruction.
+//!
+//! The remote location is converted into a local account with `LocationToAccountId`, and that
+//! account is unified with an EVM address through the address-unification pallet. The first
+//! call made by a location links the derived account to its default EVM address, so the balance
+//! used by the EVM and the one held by the derived account are the same.
+//!
+//! If the default EVM address already holds funds, e.g. it was funded from the EVM before the
+//! first call, the address-unification pallet refuses to link it. The call is then executed
+//! from the default EVM address without linking it, its balance staying the one of the account
+//! mapped to that address. Linking is attempted again on the next call.
+//!
+//! The dispatch weight is derived from the provided gas limit with the runtime's
+//! `GasWeightMapping`, which includes the proof size through `GasLimitPovSizeRatio`. The EVM
+//! execution is metered against that weight, so the PoV consumed by the call cannot exceed what
+//! was paid for by the XCM `BuyExecution`.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Function
+//!
+//! - `transact` - executes an EVM call on behalf of the XCM origin
+//!
+//! ### Other
+//!
+//! - `xcm_evm_address` - returns the EVM address used for a remote location
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::ExitReason;
+use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, Pays, PostDispatchInfo},
+	pallet_prelude::*,
+};
+use frame_system::pallet_prelude::*;
+use pallet_evm::{GasWeightMapping, Runner};
+use parity_scale_codec::Encode;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::UniqueSaturatedInto, DispatchErrorWithPostInfo};
+use sp_std::vec::Vec;
+use xcm::latest::Location;
+use xcm_executor::traits::ConvertLocation;
+
+use address_unification::{EVMAddressMapping, EvmAddresses, WeightInfo as _};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Encoded size of the fixed fields of a `transact` call: pallet and call index (2),
+/// target (20), value (32) and gas limit (8).
+const TRANSACT_FIXED_ENCODED_LEN: u64 = 62;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + pallet_evm::Config + address_unification::Config
+	{
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to execute EVM calls, resolving to the remote location.
+		type XcmOrigin: EnsureOrigin<
+			<Self as frame_system::Config>::RuntimeOrigin,
+			Success = Location,
+		>;
+
+		/// Converts a remote location into the local account acting on its behalf.
+		type LocationToAccountId: ConvertLocation<Self::AccountId>;
+
+		/// Maximum gas limit accepted for a single call.
+		#[pallet::constant]
+		type MaxGasLimit: Get<u64>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An EVM call was executed on behalf of a remote location.
+		Executed { from: H160, to: H160, exit_reason: ExitReason, used_gas: U256 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The remote location cannot be converted into a local account.
+		LocationNotConvertible,
+		/// The gas limit exceeds `MaxGasLimit`.
+		GasLimitTooHigh,
+		/// The EVM runner rejected the call before execution.
+		EvmCallFailed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Execute an EVM call on behalf of the XCM origin.
+		///
+		/// The call is executed from the EVM address derived from the origin location. EVM fees
+		/// are not charged since the execution is paid for by the XCM message.
+		///
+		/// - `target`: The contract or account to call
+		/// - `value`: The amount of native token to transfer along the call
+		/// - `gas_limit`: The gas limit of the call, used to derive the dispatch weight
+		/// - `input`: The call data
+		/// - `access_list`: The EIP-2930 access list of the call
+		#[pallet::call_index(0)]
+		#[pallet::weight(Pallet::<T>::transact_weight(*gas_limit))]
+		pub fn transact(
+			origin: OriginFor<T>,
+			target: H160,
+			value: U256,
+			gas_limit: u64,
+			input: Vec<u8>,
+			access_list: Vec<(H160, Vec<H256>)>,
+		) -> DispatchResultWithPostInfo {
+			let location = T::XcmOrigin::ensure_origin(origin)?;
+			ensure!(gas_limit <= T::MaxGasLimit::get(), Error::<T>::GasLimitTooHigh);
+
+			let source = Self::link_xcm_evm_address(&location)?;
+
+			let without_base_extrinsic_weight = true;
+			let weight_limit =
+				T::GasWeightMapping::gas_to_weight(gas_limit, without_base_extrinsic_weight);
+			let proof_size_base_cost = Self::proof_size_base_cost(&input, &access_list);
+
+			let info = <T as pallet_evm::Config>::Runner::call(
+				source,
+				target,
+				input,
+				value,
+				gas_limit,
+				None,
+				None,
+				None,
+				access_list,
+				false,
+				true,
+				Some(weight_limit),
+				Some(proof_size_base_cost),
+				<T as pallet_evm::Config>::config(),
+			)
+			.map_err(|err| {
+				log::debug!(
+					target: "ethereum-xcm",
+					"EVM call from {:?} to {:?} rejected: {:?}",
+					source, target, err.error,
+				);
+				DispatchErrorWithPostInfo {
+					post_info: PostDispatchInfo {
+						actual_weight: Some(err.weight),
+						pays_fee: Pays::Yes,
+					},
+					error: Error::<T>::EvmCallFailed.into(),
+				}
+			})?;
+
+			Self::deposit_event(Event::Executed {
+				from: source,
+				to: target,
+				exit_reason: info.exit_reason,
+				used_gas: info.used_gas.standard,
+			});
+
+			let mut actual_weight = T::GasWeightMapping::gas_to_weight(
+				info.used_gas.standard.unique_saturated_into(),
+				without_base_extrinsic_weight,
+			);
+			if let Some(proof_size_usage) = info.weight_info.and_then(|w| w.proof_size_usage) {
+				*actual_weight.proof_size_mut() = proof_size_usage;
+			}
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(
+					actual_weight
+						.saturating_add(Self::link_weight())
+						.min(Self::transact_weight(gas_limit)),
+				),
+				pays_fee: Pays::Yes,
+			})
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The weight charged for a `transact` call with the given gas limit.
+	///
+	/// Covers the EVM execution, including its proof size, and the linking of the derived
+	/// account on the first call of a location.
+	pub fn transact_weight(gas_limit: u64) -> Weight {
+		let without_base_extrinsic_weight = true;
+		T::GasWeightMapping::gas_to_weight(gas_limit, without_base_extrinsic_weight)
+			.saturating_add(Self::link_weight())
+	}
+
+	/// Returns the EVM address used for a remote location, if it can be converted.
+	///
+	/// The address is the one linked to the derived account, or the default EVM address of the
+	/// derived account if no call was executed yet.
+	pub fn xcm_evm_address(location: &Location) -> Option<H160> {
+		let account = T::LocationToAccountId::convert_location(location)?;
+		Some(address_unification::Pallet::<T>::get_evm_address_or_default(&account))
+	}
+
+	fn link_xcm_evm_address(location: &Location) -> Result<H160, DispatchError> {
+		let account = T::LocationToAccountId::convert_location(location)
+			.ok_or(Error::<T>::LocationNotConvertible)?;
+
+		if let Some(evm_address) = EvmAddresses::<T>::get(&account) {
+			return Ok(evm_address);
+		}
+
+		match address_unification::Pallet::<T>::claim_default_account(
+			frame_system::RawOrigin::Signed(account.clone()).into(),
+		) {
+			Ok(()) => {},
+			// The default EVM address is used unlinked as long as it holds funds.
+			Err(err)
+				if err ==
+					DispatchError::from(address_unification::Error::<T>::NonEmptyAccounts) => {},
+			Err(err) => return Err(err),
+		}
+		Ok(address_unification::Pallet::<T>::get_detault_evm_address(&account))
+	}
+
+	fn link_weight() -> Weight {
+		<T as address_unification::Config>::WeightInfo::claim_default_account()
+	}
+
+	fn proof_size_base_cost(input: &[u8], access_list: &[(H160, Vec<H256>)]) -> u64 {
+		TRANSACT_FIXED_ENCODED_LEN
+			.saturating_add(input.encoded_size() as u64)
+			.saturating_add(access_list.encoded_size() as u64)
+	}
+}
@@ -0,0 +1,204 @@
+//! Mocks for the ethereum-xcm module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Everything},
+};
+use pallet_evm::{EnsureAddressNever, HashedAddressMapping};
+use sp_core::crypto::AccountId32;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+use xcm::latest::prelude::*;
+use xcm_builder::{DescribeAllTerminal, DescribeFamily, HashedDescription};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId = AccountId32::new([2u8; 32]);
+
+/// Sibling parachain the mocked XCM origins are coming from.
+pub const SIBLING_PARA_ID: u32 = 2000;
+
+/// Contract storing `1` in its first storage slot: `PUSH1 1 PUSH1 0 SSTORE STOP`.
+pub const STORE_ONE_CODE: [u8; 6] = [0x60, 0x01, 0x60, 0x00, 0x55, 0x00];
+
+pub type LocationToAccountId = HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>;
+
+mod ethereum_xcm {
+	pub use super::super::*;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		EVM: pallet_evm,
+		AddressUnification: address_unification,
+		EthereumXcm: ethereum_xcm,
+	}
+);
+
+impl frame_system::Config for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+	type RuntimeTask = ();
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(15_000_000);
+	pub WeightPerGas: Weight = Weight::from_parts(1, 0);
+	pub const GasLimitPovSizeRatio: u64 = 4;
+	pub const GasLimitStorageGrowthRatio: u64 = 1;
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type CallOrigin = EnsureAddressNever<AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<AccountId>;
+	type AddressMapping = AddressUnification;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type ChainId = ();
+	type OnChargeTransaction = ();
+	type BlockGasLimit = BlockGasLimit;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type FindAuthor = ();
+	type OnCreate = ();
+	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+	type Timestamp = Timestamp;
+	type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
+	type SuicideQuickClearLimit = ();
+}
+
+impl address_unification::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type ChainId = ();
+	type OriginAddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type WeightInfo = address_unification::weights::WeightInfo<Runtime>;
+}
+
+/// Treats a signed origin as an account on the sibling parachain `SIBLING_PARA_ID`, the same
+/// way `pallet_xcm::EnsureXcm` would resolve a `Transact` descended into that account.
+pub struct EnsureSiblingAccount;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingAccount {
+	type Success = Location;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Location, RuntimeOrigin> {
+		let raw: Result<frame_system::RawOrigin<AccountId>, RuntimeOrigin> = o.clone().into();
+		match raw {
+			Ok(frame_system::RawOrigin::Signed(who)) => Ok(sibling_account_location(who)),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(ALICE))
+	}
+}
+
+impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type XcmOrigin = EnsureSiblingAccount;
+	type LocationToAccountId = LocationToAccountId;
+	type MaxGasLimit = ConstU64<1_000_000>;
+}
+
+pub fn sibling_account_location(who: AccountId) -> Location {
+	Location::new(
+		1,
+		[Parachain(SIBLING_PARA_ID), Junction::AccountId32 { network: None, id: who.into() }],
+	)
+}
+
+/// The local account derived for `who` on the sibling parachain.
+pub fn derived_account(who: AccountId) -> AccountId {
+	LocationToAccountId::convert_location(&sibling_account_location(who))
+		.expect("sibling account locations are convertible")
+}
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self { balances: vec![(derived_account(ALICE), 1_000_000)] }
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> { balances: self.balances }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,278 @@
+//! Unit tests for the ethereum-xcm module.
+
+#![cfg(test)]
+
+use super::*;
+use evm::ExitSucceed;
+use frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo, traits::Currency};
+use mock::{
+	derived_account, sibling_account_location, AddressUnification, EthereumXcm, ExtBuilder,
+	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, System, ALICE, BOB, STORE_ONE_CODE,
+};
+use pallet_evm::AddressMapping;
+use sp_runtime::{traits::Dispatchable, DispatchError};
+
+const GAS_LIMIT: u64 = 100_000;
+
+fn contract() -> H160 {
+	H160::repeat_byte(0xaa)
+}
+
+fn deploy_store_one() {
+	pallet_evm::AccountCodes::<Runtime>::insert(contract(), STORE_ONE_CODE.to_vec());
+}
+
+fn transact_call(target: H160, value: U256, gas_limit: u64) -> RuntimeCall {
+	RuntimeCall::EthereumXcm(Call::transact {
+		target,
+		value,
+		gas_limit,
+		input: vec![],
+		access_list: vec![],
+	})
+}
+
+fn evm_nonce(address: H160) -> U256 {
+	pallet_evm::Pallet::<Runtime>::account_basic(&address).0.nonce
+}
+
+#[test]
+fn transact_links_derived_account_and_executes() {
+	ExtBuilder::default().build().execute_with(|| {
+		deploy_store_one();
+		let account = derived_account(ALICE);
+		let evm_address = AddressUnification::get_detault_evm_address(&account);
+
+		assert_eq!(
+			EthereumXcm::xcm_evm_address(&sibling_account_location(ALICE)),
+			Some(evm_address)
+		);
+		assert!(EvmAddresses::<Runtime>::get(&account).is_none());
+
+		assert_ok!(EthereumXcm::transact(
+			RuntimeOrigin::signed(ALICE),
+			contract(),
+			U256::zero(),
+			GAS_LIMIT,
+			vec![],
+			vec![],
+		));
+
+		assert_eq!(EvmAddresses::<Runtime>::get(&account), Some(evm_address));
+		assert_eq!(AddressUnification::into_account_id(evm_address), account);
+		assert_eq!(
+			pallet_evm::AccountStorages::<Runtime>::get(contract(), H256::zero()),
+			H256::from_low_u64_be(1)
+		);
+		assert!(matches!(
+			System::events().last().map(|r| r.event.clone()),
+			Some(RuntimeEvent::EthereumXcm(Event::Executed {
+				from,
+				to,
+				exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+				..
+			})) if from == evm_address && to == contract()
+		));
+	});
+}
+
+#[test]
+fn transact_increments_evm_nonce_of_derived_address() {
+	ExtBuilder::default().build().execute_with(|| {
+		deploy_store_one();
+		let evm_address = EthereumXcm::xcm_evm_address(&sibling_account_location(ALICE)).unwrap();
+		assert_eq!(evm_nonce(evm_address), U256::zero());
+
+		for expected_nonce in 1..=3u64 {
+			assert_ok!(EthereumXcm::transact(
+				RuntimeOrigin::signed(ALICE),
+				contract(),
+				U256::zero(),
+				GAS_LIMIT,
+				vec![],
+				vec![],
+			));
+			assert_eq!(evm_nonce(evm_address), U256::from(expected_nonce));
+		}
+
+		// The derived address is stable across calls.
+		assert_eq!(
+			EthereumXcm::xcm_evm_address(&sibling_account_location(ALICE)),
+			Some(evm_address)
+		);
+	});
+}
+
+#[test]
+fn transact_transfers_value_from_derived_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = derived_account(ALICE);
+		let receiver = H160::repeat_byte(0xbb);
+
+		assert_ok!(EthereumXcm::transact(
+			RuntimeOrigin::signed(ALICE),
+			receiver,
+			U256::from(1_000),
+			GAS_LIMIT,
+			vec![],
+			vec![],
+		));
+
+		assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(&account), 999_000);
+		assert_eq!(
+			pallet_balances::Pallet::<Runtime>::free_balance(&AddressUnification::into_account_id(
+				receiver
+			)),
+			1_000
+		);
+	});
+}
+
+#[test]
+fn transact_from_funded_default_address_executes_unlinked() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = derived_account(ALICE);
+		let evm_address = AddressUnification::get_detault_evm_address(&account);
+		let mapped_account = AddressUnification::into_account_id(evm_address);
+		assert_ne!(mapped_account, account);
+
+		// The default EVM address is funded before the location made any call.
+		let _ = pallet_balances::Pallet::<Runtime>::deposit_creating(&mapped_account, 5_000);
+		let receiver = H160::repeat_byte(0xbb);
+
+		for _ in 0..2 {
+			assert_ok!(EthereumXcm::transact(
+				RuntimeOrigin::signed(ALICE),
+				receiver,
+				U256::from(1_000),
+				GAS_LIMIT,
+				vec![],
+				vec![],
+			));
+		}
+
+		assert!(EvmAddresses::<Runtime>::get(&account).is_none());
+		assert_eq!(
+			EthereumXcm::xcm_evm_address(&sibling_account_location(ALICE)),
+			Some(evm_address)
+		);
+		assert_eq!(evm_nonce(evm_address), U256::from(2));
+		assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(&mapped_account), 3_000);
+		assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(&account), 1_000_000);
+	});
+}
+
+#[test]
+fn transact_keeps_using_linked_address() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = derived_account(ALICE);
+		assert_ok!(EthereumXcm::transact(
+			RuntimeOrigin::signed(ALICE),
+			H160::repeat_byte(0xbb),
+			U256::zero(),
+			GAS_LIMIT,
+			vec![],
+			vec![],
+		));
+		let evm_address = EvmAddresses::<Runtime>::get(&account).unwrap();
+
+		// Funding the linked address credits the derived account and does not break later calls.
+		let _ = pallet_balances::Pallet::<Runtime>::deposit_creating(
+			&AddressUnification::into_account_id(evm_address),
+			5_000,
+		);
+		assert_ok!(EthereumXcm::transact(
+			RuntimeOrigin::signed(ALICE),
+			H160::repeat_byte(0xbb),
+			U256::zero(),
+			GAS_LIMIT,
+			vec![],
+			vec![],
+		));
+
+		assert_eq!(EvmAddresses::<Runtime>::get(&account), Some(evm_address));
+		assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(&account), 1_005_000);
+	});
+}
+
+#[test]
+fn transact_weight_accounts_for_proof_size() {
+	ExtBuilder::default().build().execute_with(|| {
+		let gas_weight =
+			<Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(GAS_LIMIT, true);
+		let link_weight =
+			<Runtime as address_unification::Config>::WeightInfo::claim_default_account();
+
+		let weight = EthereumXcm::transact_weight(GAS_LIMIT);
+		assert_eq!(weight, gas_weight.saturating_add(link_weight));
+		// GasLimitPovSizeRatio is 4 in the mock.
+		assert!(weight.proof_size() >= GAS_LIMIT / 4);
+
+		let call = transact_call(contract(), U256::zero(), GAS_LIMIT);
+		assert_eq!(call.get_dispatch_info().weight, weight);
+	});
+}
+
+#[test]
+fn transact_refunds_unused_weight() {
+	ExtBuilder::default().build().execute_with(|| {
+		deploy_store_one();
+		let call = transact_call(contract(), U256::zero(), GAS_LIMIT);
+		let declared = call.get_dispatch_info().weight;
+
+		let post_info = call.dispatch(RuntimeOrigin::signed(ALICE)).unwrap();
+		let actual = post_info.actual_weight.unwrap();
+
+		assert!(actual.all_lte(declared));
+		assert!(actual.ref_time() < declared.ref_time());
+	});
+}
+
+#[test]
+fn transact_rejects_gas_limit_above_max() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EthereumXcm::transact(
+				RuntimeOrigin::signed(ALICE),
+				contract(),
+				U256::zero(),
+				1_000_001,
+				vec![],
+				vec![],
+			),
+			Error::<Runtime>::GasLimitTooHigh
+		);
+	});
+}
+
+#[test]
+fn transact_requires_xcm_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			EthereumXcm::transact(
+				RuntimeOrigin::root(),
+				contract(),
+				U256::zero(),
+				GAS_LIMIT,
+				vec![],
+				vec![],
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn transact_without_funds_for_value_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let result = EthereumXcm::transact(
+			RuntimeOrigin::signed(BOB),
+			H160::repeat_byte(0xbb),
+			U256::from(1_000),
+			GAS_LIMIT,
+			vec![],
+			vec![],
+		);
+		assert_eq!(result.map_err(|e| e.error), Err(Error::<Runtime>::EvmCallFailed.into()));
+	});
+}
@@ -137,5 +137,25 @@
      * Selector: 9dc29fac
      */
     function burn(address who, uint256 amount) external returns (bool);
+
+    /**
+     * @dev Atomically increases the allowance granted to spender by the caller.
+     * The allowance saturates at the maximum balance of the asset.
+     * Selector: 39509351
+     * @param spender The address which will spend the funds.
+     * @param addedValue The amount of tokens added to the allowance.
+     */
+    function increaseAllowance(address spender, uint256 addedValue)
+        external returns (bool);
+
+    /**
+     * @dev Atomically decreases the allowance granted to spender by the caller.
+     * Reverts if the allowance would go below zero.
+     * Selector: a457c2d7
+     * @param spender The address which will spend the funds.
+     * @param subtractedValue The amount of tokens removed from the allowance.
+     */
+    function decreaseAllowance(address spender, uint256 subtractedValue)
+        external returns (bool);
 }
 
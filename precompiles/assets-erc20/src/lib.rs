@@ -52,8 +52,8 @@ use peaq_primitives_xcm::EVMAddressToAssetId;
 use precompile_utils::{
 	evm::logs::LogsBuilder,
 	prelude::{
-		Address, DiscriminantResult, InjectBacktrace, LogExt, MayRevert, PrecompileHandleExt,
-		RevertReason, RuntimeHelper, UnboundedBytes,
+		revert, Address, DiscriminantResult, InjectBacktrace, LogExt, MayRevert,
+		PrecompileHandleExt, RevertReason, RuntimeHelper, UnboundedBytes,
 	},
 	solidity,
 };
@@ -74,14 +74,17 @@ use precompile_utils::{
 	// PrecompileHandleExt,
 	// RuntimeHelper,
 };
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::{Bounded, CheckedSub, Saturating};
 
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	marker::PhantomData,
 };
 
+mod eip2612;
+use eip2612::Eip2612;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -233,59 +236,136 @@ where
 	) -> EvmResult<bool> {
 		handle.record_log_costs_manual(3, 32)?;
 
+		let caller = handle.context().caller;
+		let spender: H160 = spender.into();
+		// Amount saturate if too high.
+		let amount: BalanceOf<Runtime, Instance> =
+			value.try_into().unwrap_or_else(|_| Bounded::max_value());
+
+		Self::approve_inner(asset_id, handle, caller, spender, amount)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log3(SELECTOR_LOG_APPROVAL, caller, spender, solidity::encode_event_data(value))
+			.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("increaseAllowance(address,uint256)")]
+	#[precompile::public("increase_allowance(address,uint256)")]
+	fn increase_allowance(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		spender: Address,
+		added_value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+
+		let caller = handle.context().caller;
 		let spender: H160 = spender.into();
+		let added_value = Self::u256_to_amount(added_value).in_field("addedValue")?;
+
+		let allowance = Self::allowance_of(asset_id.clone(), handle, caller, spender)?;
+		// Allowance saturate if too high, as in `approve`.
+		let amount = allowance.saturating_add(added_value);
+
+		Self::approve_inner(asset_id, handle, caller, spender, amount)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log3(SELECTOR_LOG_APPROVAL, caller, spender, solidity::encode_event_data(amount))
+			.record(handle)?;
+
+		Ok(true)
+	}
 
+	#[precompile::public("decreaseAllowance(address,uint256)")]
+	#[precompile::public("decrease_allowance(address,uint256)")]
+	fn decrease_allowance(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		spender: Address,
+		subtracted_value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+
+		let caller = handle.context().caller;
+		let spender: H160 = spender.into();
+		let subtracted_value =
+			Self::u256_to_amount(subtracted_value).in_field("subtractedValue")?;
+
+		let allowance = Self::allowance_of(asset_id.clone(), handle, caller, spender)?;
+		let amount = allowance
+			.checked_sub(&subtracted_value)
+			.ok_or_else(|| revert("Decreased allowance below zero"))?;
+
+		Self::approve_inner(asset_id, handle, caller, spender, amount)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log3(SELECTOR_LOG_APPROVAL, caller, spender, solidity::encode_event_data(amount))
+			.record(handle)?;
+
+		Ok(true)
+	}
+
+	/// Replaces the approval of `spender` on the funds of `owner` by `amount`.
+	fn approve_inner(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		owner: H160,
+		spender: H160,
+		amount: BalanceOf<Runtime, Instance>,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(owner);
+		let spender: Runtime::AccountId = Runtime::AddressMapping::into_account_id(spender);
+
+		// Storage item: Approvals:
+		// Blake2_128(16) + AssetId(16) + (2 * Blake2_128(16) + AccountId(20)) + Approval(32)
+		handle.record_db_read::<Runtime>(136)?;
+
+		// If previous approval exists, we need to clean it
+		if pallet_assets::Pallet::<Runtime, Instance>::allowance(
+			asset_id.clone(),
+			&origin,
+			&spender,
+		) != 0u32.into()
 		{
-			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-			let spender: Runtime::AccountId = Runtime::AddressMapping::into_account_id(spender);
-			// Amount saturate if too high.
-			let value: BalanceOf<Runtime, Instance> =
-				value.try_into().unwrap_or_else(|_| Bounded::max_value());
-
-			// Storage item: Approvals:
-			// Blake2_128(16) + AssetId(16) + (2 * Blake2_128(16) + AccountId(20)) + Approval(32)
-			handle.record_db_read::<Runtime>(136)?;
-
-			// If previous approval exists, we need to clean it
-			if pallet_assets::Pallet::<Runtime, Instance>::allowance(
-				asset_id.clone(),
-				&origin,
-				&spender,
-			) != 0u32.into()
-			{
-				RuntimeHelper::<Runtime>::try_dispatch(
-					handle,
-					Some(origin.clone()).into(),
-					pallet_assets::Call::<Runtime, Instance>::cancel_approval {
-						id: asset_id.clone().into(),
-						delegate: Runtime::Lookup::unlookup(spender.clone()),
-					},
-					0,
-				)?;
-			}
-			// Dispatch call (if enough gas).
 			RuntimeHelper::<Runtime>::try_dispatch(
 				handle,
-				Some(origin).into(),
-				pallet_assets::Call::<Runtime, Instance>::approve_transfer {
-					id: asset_id.into(),
-					delegate: Runtime::Lookup::unlookup(spender),
-					amount: value,
+				Some(origin.clone()).into(),
+				pallet_assets::Call::<Runtime, Instance>::cancel_approval {
+					id: asset_id.clone().into(),
+					delegate: Runtime::Lookup::unlookup(spender.clone()),
 				},
 				0,
 			)?;
 		}
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::approve_transfer {
+				id: asset_id.into(),
+				delegate: Runtime::Lookup::unlookup(spender),
+				amount,
+			},
+			0,
+		)?;
 
-		LogsBuilder::new(handle.context().address)
-			.log3(
-				SELECTOR_LOG_APPROVAL,
-				handle.context().caller,
-				spender,
-				solidity::encode_event_data(value),
-			)
-			.record(handle)?;
+		Ok(())
+	}
 
-		Ok(true)
+	fn allowance_of(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		owner: H160,
+		spender: H160,
+	) -> EvmResult<BalanceOf<Runtime, Instance>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner);
+		let spender: Runtime::AccountId = Runtime::AddressMapping::into_account_id(spender);
+
+		Ok(pallet_assets::Pallet::<Runtime, Instance>::allowance(asset_id, &owner, &spender))
 	}
 
 	#[precompile::public("transfer(address,uint256)")]
@@ -505,6 +585,43 @@ where
 		Ok(true)
 	}
 
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public("permit(address,address,uint256,uint256,uint8,bytes32,bytes32)")]
+	fn eip2612_permit(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+		value: U256,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult {
+		<Eip2612<Runtime, Instance>>::permit(
+			asset_id, handle, owner, spender, value, deadline, v, r, s,
+		)
+	}
+
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn eip2612_nonces(
+		_asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+	) -> EvmResult<U256> {
+		<Eip2612<Runtime, Instance>>::nonces(handle, owner)
+	}
+
+	#[precompile::public("DOMAIN_SEPARATOR()")]
+	#[precompile::view]
+	fn eip2612_domain_separator(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<H256> {
+		<Eip2612<Runtime, Instance>>::domain_separator(asset_id, handle)
+	}
+
 	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime, Instance>> {
 		value
 			.try_into()
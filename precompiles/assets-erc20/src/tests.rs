@@ -37,8 +37,9 @@ use frame_support::assert_ok;
 use sp_runtime::traits::Zero;
 use std::str::from_utf8;
 
-use crate::{mock::*, *};
+use crate::{eip2612::Eip2612, mock::*, *};
 
+use libsecp256k1::{sign, Message, SecretKey};
 use precompile_utils::testing::*;
 // use precompile_utils::{prelude::LogsBuilder, testing::*, EvmDataWriter};
 use sha3::{Digest, Keccak256};
@@ -107,6 +108,11 @@ fn selectors() {
 	assert!(PCall::minimum_balance_selectors().contains(&0xb9d1d49b));
 	assert!(PCall::mint_selectors().contains(&0x40c10f19));
 	assert!(PCall::burn_selectors().contains(&0x9dc29fac));
+	assert!(PCall::increase_allowance_selectors().contains(&0x39509351));
+	assert!(PCall::decrease_allowance_selectors().contains(&0xa457c2d7));
+	assert!(PCall::eip2612_nonces_selectors().contains(&0x7ecebe00));
+	assert!(PCall::eip2612_permit_selectors().contains(&0xd505accf));
+	assert!(PCall::eip2612_domain_separator_selectors().contains(&0x3644e515));
 
 	assert_eq!(
 		crate::SELECTOR_LOG_TRANSFER,
@@ -153,6 +159,11 @@ fn modifiers() {
 
 			tester.test_default_modifier(PCall::mint_selectors());
 			tester.test_default_modifier(PCall::burn_selectors());
+			tester.test_default_modifier(PCall::increase_allowance_selectors());
+			tester.test_default_modifier(PCall::decrease_allowance_selectors());
+			tester.test_view_modifier(PCall::eip2612_nonces_selectors());
+			tester.test_default_modifier(PCall::eip2612_permit_selectors());
+			tester.test_view_modifier(PCall::eip2612_domain_separator_selectors());
 		});
 }
 
@@ -1026,3 +1037,506 @@ fn burn_non_admin_is_not_ok() {
 			.execute_reverts(|output| from_utf8(output).unwrap().contains("NoPermission"));
 	});
 }
+
+fn create_asset_with_metadata(asset_id: MockAssetId, name: &[u8]) {
+	assert_ok!(Assets::force_create(
+		RuntimeOrigin::root(),
+		asset_id,
+		MockPeaqAccount::Alice,
+		true,
+		1
+	));
+	assert_ok!(Assets::force_set_metadata(
+		RuntimeOrigin::root(),
+		asset_id,
+		name.to_vec(),
+		b"Test".to_vec(),
+		12,
+		false
+	));
+}
+
+#[test]
+fn increase_allowance() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::approve {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						value: U256::from(500),
+					},
+				)
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::increase_allowance {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						added_value: U256::from(200),
+					},
+				)
+				.expect_log(LogsBuilder::new(MockPeaqAccount::AssetId(asset_id).into()).log3(
+					SELECTOR_LOG_APPROVAL,
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::Bob,
+					solidity::encode_event_data(U256::from(700)),
+				))
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::allowance {
+						owner: Address(MockPeaqAccount::Alice.into()),
+						spender: Address(MockPeaqAccount::Bob.into()),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(700));
+		});
+}
+
+#[test]
+fn increase_allowance_saturating() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::approve {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						value: U256::MAX,
+					},
+				)
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::increase_allowance {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						added_value: U256::from(200),
+					},
+				)
+				.expect_log(LogsBuilder::new(MockPeaqAccount::AssetId(asset_id).into()).log3(
+					SELECTOR_LOG_APPROVAL,
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::Bob,
+					solidity::encode_event_data(U256::from(u128::MAX)),
+				))
+				.execute_returns(true);
+		});
+}
+
+#[test]
+fn decrease_allowance() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::approve {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						value: U256::from(500),
+					},
+				)
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::decrease_allowance {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						subtracted_value: U256::from(200),
+					},
+				)
+				.expect_log(LogsBuilder::new(MockPeaqAccount::AssetId(asset_id).into()).log3(
+					SELECTOR_LOG_APPROVAL,
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::Bob,
+					solidity::encode_event_data(U256::from(300)),
+				))
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::allowance {
+						owner: Address(MockPeaqAccount::Alice.into()),
+						spender: Address(MockPeaqAccount::Bob.into()),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(300));
+		});
+}
+
+#[test]
+fn decrease_allowance_below_zero() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::approve {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						value: U256::from(500),
+					},
+				)
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::decrease_allowance {
+						spender: Address(MockPeaqAccount::Bob.into()),
+						subtracted_value: U256::from(501),
+					},
+				)
+				.expect_no_logs()
+				.execute_reverts(|output| output == b"Decreased allowance below zero");
+		});
+}
+
+#[test]
+fn permit_valid() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::CryptoAlith, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			let owner: H160 = MockPeaqAccount::CryptoAlith.into();
+			let spender: H160 = MockPeaqAccount::Bob.into();
+			let value: U256 = 500u16.into();
+			let deadline: U256 = 0u8.into();
+
+			let permit = Eip2612::<Runtime>::generate_permit(
+				MockPeaqAccount::AssetId(asset_id).into(),
+				asset_id,
+				owner,
+				spender,
+				value,
+				0u8.into(), // nonce
+				deadline,
+			);
+
+			let secret_key = SecretKey::parse(&alith_secret_key()).unwrap();
+			let message = Message::parse(&permit);
+			let (rs, v) = sign(&message, &secret_key);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_nonces { owner: Address(owner) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(0u8));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Charlie, // can be anyone
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_permit {
+						owner: Address(owner),
+						spender: Address(spender),
+						value,
+						deadline,
+						v: v.serialize(),
+						r: rs.r.b32().into(),
+						s: rs.s.b32().into(),
+					},
+				)
+				.expect_log(LogsBuilder::new(MockPeaqAccount::AssetId(asset_id).into()).log3(
+					SELECTOR_LOG_APPROVAL,
+					owner,
+					spender,
+					solidity::encode_event_data(value),
+				))
+				.execute_returns(());
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::allowance { owner: Address(owner), spender: Address(spender) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(500u16));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_nonces { owner: Address(owner) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(1u8));
+		});
+}
+
+#[test]
+fn permit_invalid_nonce() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::CryptoAlith, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			let owner: H160 = MockPeaqAccount::CryptoAlith.into();
+			let spender: H160 = MockPeaqAccount::Bob.into();
+			let value: U256 = 500u16.into();
+			let deadline: U256 = 0u8.into();
+
+			let permit = Eip2612::<Runtime>::generate_permit(
+				MockPeaqAccount::AssetId(asset_id).into(),
+				asset_id,
+				owner,
+				spender,
+				value,
+				1u8.into(), // nonce
+				deadline,
+			);
+
+			let secret_key = SecretKey::parse(&alith_secret_key()).unwrap();
+			let message = Message::parse(&permit);
+			let (rs, v) = sign(&message, &secret_key);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Charlie, // can be anyone
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_permit {
+						owner: Address(owner),
+						spender: Address(spender),
+						value,
+						deadline,
+						v: v.serialize(),
+						r: rs.r.b32().into(),
+						s: rs.s.b32().into(),
+					},
+				)
+				.execute_reverts(|output| output == b"Invalid permit");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::allowance { owner: Address(owner), spender: Address(spender) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(0u16));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_nonces { owner: Address(owner) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(0u8));
+		});
+}
+
+#[test]
+fn permit_invalid_signature() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::CryptoAlith, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			let owner: H160 = MockPeaqAccount::CryptoAlith.into();
+			let spender: H160 = MockPeaqAccount::Bob.into();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Charlie, // can be anyone
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_permit {
+						owner: Address(owner),
+						spender: Address(spender),
+						value: 500u16.into(),
+						deadline: 0u8.into(),
+						v: 0,
+						r: H256::repeat_byte(0x11),
+						s: H256::repeat_byte(0x11),
+					},
+				)
+				.execute_reverts(|output| output == b"Invalid permit");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_nonces { owner: Address(owner) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(0u8));
+		});
+}
+
+#[test]
+fn permit_invalid_deadline() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::CryptoAlith, 1000)])
+		.build()
+		.execute_with(|| {
+			pallet_timestamp::Pallet::<Runtime>::set_timestamp(10_000);
+
+			let asset_id = MockAssetId(0u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+
+			let owner: H160 = MockPeaqAccount::CryptoAlith.into();
+			let spender: H160 = MockPeaqAccount::Bob.into();
+			let value: U256 = 500u16.into();
+			let deadline: U256 = 5u8.into(); // deadline < timestamp => expired
+
+			let permit = Eip2612::<Runtime>::generate_permit(
+				MockPeaqAccount::AssetId(asset_id).into(),
+				asset_id,
+				owner,
+				spender,
+				value,
+				0u8.into(), // nonce
+				deadline,
+			);
+
+			let secret_key = SecretKey::parse(&alith_secret_key()).unwrap();
+			let message = Message::parse(&permit);
+			let (rs, v) = sign(&message, &secret_key);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Charlie, // can be anyone
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_permit {
+						owner: Address(owner),
+						spender: Address(spender),
+						value,
+						deadline,
+						v: v.serialize(),
+						r: rs.r.b32().into(),
+						s: rs.s.b32().into(),
+					},
+				)
+				.execute_reverts(|output| output == b"Permit expired");
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_nonces { owner: Address(owner) },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(0u8));
+		});
+}
+
+#[test]
+fn permit_is_bound_to_its_asset() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::CryptoAlith, 1000)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockAssetId(0u128);
+			let other_asset_id = MockAssetId(1u128);
+			create_asset_with_metadata(asset_id, b"TestToken");
+			create_asset_with_metadata(other_asset_id, b"OtherToken");
+
+			let owner: H160 = MockPeaqAccount::CryptoAlith.into();
+			let spender: H160 = MockPeaqAccount::Bob.into();
+			let value: U256 = 500u16.into();
+			let deadline: U256 = 0u8.into();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::CryptoAlith,
+					MockPeaqAccount::AssetId(asset_id),
+					PCall::eip2612_domain_separator {},
+				)
+				.expect_no_logs()
+				.execute_returns(H256::from(Eip2612::<Runtime>::compute_domain_separator(
+					MockPeaqAccount::AssetId(asset_id).into(),
+					asset_id,
+				)));
+			assert_ne!(
+				Eip2612::<Runtime>::compute_domain_separator(
+					MockPeaqAccount::AssetId(asset_id).into(),
+					asset_id,
+				),
+				Eip2612::<Runtime>::compute_domain_separator(
+					MockPeaqAccount::AssetId(other_asset_id).into(),
+					other_asset_id,
+				)
+			);
+
+			// A permit signed for one asset cannot be used on another one.
+			let permit = Eip2612::<Runtime>::generate_permit(
+				MockPeaqAccount::AssetId(asset_id).into(),
+				asset_id,
+				owner,
+				spender,
+				value,
+				0u8.into(), // nonce
+				deadline,
+			);
+
+			let secret_key = SecretKey::parse(&alith_secret_key()).unwrap();
+			let message = Message::parse(&permit);
+			let (rs, v) = sign(&message, &secret_key);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Charlie, // can be anyone
+					MockPeaqAccount::AssetId(other_asset_id),
+					PCall::eip2612_permit {
+						owner: Address(owner),
+						spender: Address(spender),
+						value,
+						deadline,
+						v: v.serialize(),
+						r: rs.r.b32().into(),
+						s: rs.s.b32().into(),
+					},
+				)
+				.execute_reverts(|output| output == b"Invalid permit");
+		});
+}
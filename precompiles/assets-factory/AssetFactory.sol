@@ -52,4 +52,66 @@ interface AssetFactory {
      /// Complete destroying asset and unreserve currency
     /// selector: 99c720ff
     function finishDestroy(uint64 id) external;
+
+    /// Freeze an account, preventing it from transferring the asset
+    /// selector: 4d1a1635
+    function freeze(uint64 id, address who) external;
+
+    /// Allow an account frozen before to transfer the asset again
+    /// selector: ef33e57a
+    function thaw(uint64 id, address who) external;
+
+    /// Freeze all accounts of a given asset
+    /// selector: fdade1d5
+    function freezeAsset(uint64 id) external;
+
+    /// Allow all accounts of an asset frozen before to transfer it again
+    /// selector: c8ae38b6
+    function thawAsset(uint64 id) external;
+
+    /// Block an account, preventing it from sending and receiving the asset
+    /// selector: 63422462
+    function block(uint64 id, address who) external;
+
+    /// Move some assets from one account to another, as the admin of the asset
+    /// selector: 831cd7a4
+    function forceTransfer(uint64 id, address source, address dest, uint128 amount) external;
+
+    /// Get the owner of a given asset
+    /// selector: 4d1f8c31
+    function owner(uint64 id) external view returns (address);
+
+    /// Get the issuer, admin and freezer of a given asset
+    /// selector: 23160313
+    function team(uint64 id) external view returns (address issuer, address admin, address freezer);
+
+    /// Get the status of a given asset: 0 live, 1 frozen, 2 destroying
+    /// selector: 20bff893
+    function status(uint64 id) external view returns (uint8);
+
+    /// Get the total supply of a given asset
+    /// selector: dc6d08b7
+    function totalSupply(uint64 id) external view returns (uint256);
+
+    /// Whether a given asset is sufficient to keep an account alive
+    /// selector: ffa6e5a7
+    function isSufficient(uint64 id) external view returns (bool);
+
+    /// Event emitted when an account is frozen
+    event AccountFrozen(uint64 indexed id, address indexed who);
+
+    /// Event emitted when an account is thawed
+    event AccountThawed(uint64 indexed id, address indexed who);
+
+    /// Event emitted when an account is blocked
+    event AccountBlocked(uint64 indexed id, address indexed who);
+
+    /// Event emitted when an asset is frozen
+    event AssetFrozen(uint64 indexed id);
+
+    /// Event emitted when an asset is thawed
+    event AssetThawed(uint64 indexed id);
+
+    /// Event emitted when assets are moved by the admin
+    event ForceTransferred(uint64 indexed id, address indexed source, address indexed dest, uint128 amount);
 }
@@ -5,26 +5,35 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use address_unification::EVMAddressMapping;
 use fp_evm::PrecompileHandle;
 use frame_support::{
 	dispatch::{GetDispatchInfo, PostDispatchInfo},
 	sp_runtime::traits::StaticLookup,
-	traits::{ConstU32, OriginTrait},
+	storage::{storage_prefix, unhashed},
+	traits::{
+		fungibles::{roles::Inspect as RolesInspect, Inspect},
+		ConstU32, Currency, OriginTrait, PalletInfoAccess,
+	},
+	Blake2_128Concat, StorageHasher,
 };
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use sp_runtime::traits::Dispatchable;
 
 use pallet_evm::AddressMapping;
 use peaq_primitives_xcm::{AssetId as PeaqAssetId, EVMAddressToAssetId};
 use precompile_utils::{
+	keccak256,
 	prelude::{
-		Address, BoundedBytes, InjectBacktrace, PrecompileHandleExt, RevertReason, RuntimeHelper,
+		log2, log3, log4, Address, BoundedBytes, InjectBacktrace, LogExt, PrecompileHandleExt,
+		RevertReason, RuntimeHelper,
 	},
 	solidity, EvmResult,
 };
 use sp_runtime::traits::Bounded;
 
 use peaq_primitives_xcm::AssetIdExt;
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	marker::PhantomData,
@@ -38,6 +47,25 @@ mod tests;
 
 type GetBytesLimit = ConstU32<{ 2u32.pow(16) }>;
 
+/// Solidity selector of the AccountFrozen log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_ACCOUNT_FROZEN: [u8; 32] = keccak256!("AccountFrozen(uint64,address)");
+
+/// Solidity selector of the AccountThawed log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_ACCOUNT_THAWED: [u8; 32] = keccak256!("AccountThawed(uint64,address)");
+
+/// Solidity selector of the AccountBlocked log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_ACCOUNT_BLOCKED: [u8; 32] = keccak256!("AccountBlocked(uint64,address)");
+
+/// Solidity selector of the AssetFrozen log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_ASSET_FROZEN: [u8; 32] = keccak256!("AssetFrozen(uint64)");
+
+/// Solidity selector of the AssetThawed log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_ASSET_THAWED: [u8; 32] = keccak256!("AssetThawed(uint64)");
+
+/// Solidity selector of the ForceTransferred log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_FORCE_TRANSFERRED: [u8; 32] =
+	keccak256!("ForceTransferred(uint64,address,address,uint128)");
+
 /// Alias for the Balance type for the provided Runtime and Instance.
 pub type BalanceOf<Runtime, Instance = ()> = <Runtime as pallet_assets::Config<Instance>>::Balance;
 
@@ -49,14 +77,60 @@ pub type StorageAssetIdOf<Runtime, Instance = ()> =
 pub type AssetIdParameterOf<Runtime, Instance = ()> =
 	<Runtime as pallet_assets::Config<Instance>>::AssetIdParameter;
 
-pub struct AssetsFactoryPrecompile<Runtime, Instance: 'static = ()>(
-	PhantomData<(Runtime, Instance)>,
+/// Alias for the deposit Balance type for the provided Runtime and Instance.
+pub type DepositBalanceOf<Runtime, Instance = ()> =
+	<<Runtime as pallet_assets::Config<Instance>>::Currency as Currency<
+		<Runtime as frame_system::Config>::AccountId,
+	>>::Balance;
+
+/// Status of an asset, as returned by `status`. Encoded as `pallet_assets::AssetStatus`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Decode)]
+pub enum AssetStatus {
+	Live,
+	Frozen,
+	Destroying,
+}
+
+type AssetDetailsOf<Runtime, Instance> = pallet_assets::AssetDetails<
+	BalanceOf<Runtime, Instance>,
+	<Runtime as frame_system::Config>::AccountId,
+	DepositBalanceOf<Runtime, Instance>,
+>;
+
+/// Mirror of `pallet_assets::AssetDetails`, whose fields are private, decoding its storage.
+#[derive(Decode)]
+struct AssetDetailsMirror<Balance, AccountId, DepositBalance> {
+	_owner: AccountId,
+	_issuer: AccountId,
+	_admin: AccountId,
+	_freezer: AccountId,
+	_supply: Balance,
+	_deposit: DepositBalance,
+	_min_balance: Balance,
+	is_sufficient: bool,
+	_accounts: u32,
+	_sufficients: u32,
+	_approvals: u32,
+	status: AssetStatus,
+}
+
+type AssetDetailsMirrorOf<Runtime, Instance> = AssetDetailsMirror<
+	BalanceOf<Runtime, Instance>,
+	<Runtime as frame_system::Config>::AccountId,
+	DepositBalanceOf<Runtime, Instance>,
+>;
+
+type Assets<Runtime, Instance> = pallet_assets::Pallet<Runtime, Instance>;
+
+pub struct AssetsFactoryPrecompile<Runtime, AddressMapper, Instance: 'static = ()>(
+	PhantomData<(Runtime, AddressMapper, Instance)>,
 );
 
 #[precompile_utils::precompile]
-impl<Runtime, Instance> AssetsFactoryPrecompile<Runtime, Instance>
+impl<Runtime, AddressMapper, Instance> AssetsFactoryPrecompile<Runtime, AddressMapper, Instance>
 where
 	Instance: 'static,
+	AddressMapper: EVMAddressMapping<Runtime::AccountId>,
 	Runtime: pallet_assets::Config<Instance> + pallet_evm::Config + frame_system::Config,
 	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 	Runtime::RuntimeCall: From<pallet_assets::Call<Runtime, Instance>>,
@@ -379,4 +453,311 @@ where
 
 		Ok(())
 	}
+
+	#[precompile::public("freeze(uint64,address)")]
+	fn freeze(handle: &mut impl PrecompileHandle, id: u64, who: Address) -> EvmResult {
+		handle.record_log_costs_manual(3, 0)?;
+
+		let asset_id = Self::asset_id_parameter(id)?;
+		let who: H160 = who.into();
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let who = Runtime::AddressMapping::into_account_id(who);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_assets::Call::<Runtime, Instance>::freeze {
+					id: asset_id,
+					who: Runtime::Lookup::unlookup(who),
+				},
+				0,
+			)?;
+		}
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_ACCOUNT_FROZEN,
+			Self::id_topic(id),
+			who,
+			Vec::new(),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("thaw(uint64,address)")]
+	fn thaw(handle: &mut impl PrecompileHandle, id: u64, who: Address) -> EvmResult {
+		handle.record_log_costs_manual(3, 0)?;
+
+		let asset_id = Self::asset_id_parameter(id)?;
+		let who: H160 = who.into();
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let who = Runtime::AddressMapping::into_account_id(who);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_assets::Call::<Runtime, Instance>::thaw {
+					id: asset_id,
+					who: Runtime::Lookup::unlookup(who),
+				},
+				0,
+			)?;
+		}
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_ACCOUNT_THAWED,
+			Self::id_topic(id),
+			who,
+			Vec::new(),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("freezeAsset(uint64)")]
+	#[precompile::public("freeze_asset(uint64)")]
+	fn freeze_asset(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult {
+		handle.record_log_costs_manual(2, 0)?;
+
+		let asset_id = Self::asset_id_parameter(id)?;
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_assets::Call::<Runtime, Instance>::freeze_asset { id: asset_id },
+				0,
+			)?;
+		}
+
+		log2(handle.context().address, SELECTOR_LOG_ASSET_FROZEN, Self::id_topic(id), Vec::new())
+			.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("thawAsset(uint64)")]
+	#[precompile::public("thaw_asset(uint64)")]
+	fn thaw_asset(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult {
+		handle.record_log_costs_manual(2, 0)?;
+
+		let asset_id = Self::asset_id_parameter(id)?;
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_assets::Call::<Runtime, Instance>::thaw_asset { id: asset_id },
+				0,
+			)?;
+		}
+
+		log2(handle.context().address, SELECTOR_LOG_ASSET_THAWED, Self::id_topic(id), Vec::new())
+			.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("block(uint64,address)")]
+	fn block(handle: &mut impl PrecompileHandle, id: u64, who: Address) -> EvmResult {
+		handle.record_log_costs_manual(3, 0)?;
+
+		let asset_id = Self::asset_id_parameter(id)?;
+		let who: H160 = who.into();
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let who = Runtime::AddressMapping::into_account_id(who);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_assets::Call::<Runtime, Instance>::block {
+					id: asset_id,
+					who: Runtime::Lookup::unlookup(who),
+				},
+				0,
+			)?;
+		}
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_ACCOUNT_BLOCKED,
+			Self::id_topic(id),
+			who,
+			Vec::new(),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("forceTransfer(uint64,address,address,uint128)")]
+	#[precompile::public("force_transfer(uint64,address,address,uint128)")]
+	fn force_transfer(
+		handle: &mut impl PrecompileHandle,
+		id: u64,
+		source: Address,
+		dest: Address,
+		amount: u128,
+	) -> EvmResult {
+		handle.record_log_costs_manual(4, 32)?;
+
+		let asset_id = Self::asset_id_parameter(id)?;
+		let source: H160 = source.into();
+		let dest: H160 = dest.into();
+		let balance: BalanceOf<Runtime, Instance> = amount
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("amount"))?;
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let source = Runtime::AddressMapping::into_account_id(source);
+			let dest = Runtime::AddressMapping::into_account_id(dest);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_assets::Call::<Runtime, Instance>::force_transfer {
+					id: asset_id,
+					source: Runtime::Lookup::unlookup(source),
+					dest: Runtime::Lookup::unlookup(dest),
+					amount: balance,
+				},
+				0,
+			)?;
+		}
+
+		log4(
+			handle.context().address,
+			SELECTOR_LOG_FORCE_TRANSFERRED,
+			Self::id_topic(id),
+			source,
+			dest,
+			solidity::encode_event_data(amount),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("owner(uint64)")]
+	#[precompile::view]
+	fn owner(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult<Address> {
+		let asset_id = Self::existing_asset_id(handle, id)?;
+
+		Ok(Self::role_address(Assets::<Runtime, Instance>::owner(asset_id)))
+	}
+
+	#[precompile::public("team(uint64)")]
+	#[precompile::view]
+	fn team(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult<(Address, Address, Address)> {
+		let asset_id = Self::existing_asset_id(handle, id)?;
+
+		Ok((
+			Self::role_address(Assets::<Runtime, Instance>::issuer(asset_id.clone())),
+			Self::role_address(Assets::<Runtime, Instance>::admin(asset_id.clone())),
+			Self::role_address(Assets::<Runtime, Instance>::freezer(asset_id)),
+		))
+	}
+
+	#[precompile::public("status(uint64)")]
+	#[precompile::view]
+	fn status(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult<u8> {
+		let asset_id = Self::existing_asset_id(handle, id)?;
+
+		Ok(Self::asset_details(asset_id)?.status as u8)
+	}
+
+	#[precompile::public("totalSupply(uint64)")]
+	#[precompile::public("total_supply(uint64)")]
+	#[precompile::view]
+	fn total_supply(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult<U256> {
+		let asset_id = Self::existing_asset_id(handle, id)?;
+
+		Ok(Assets::<Runtime, Instance>::total_issuance(asset_id).into())
+	}
+
+	#[precompile::public("isSufficient(uint64)")]
+	#[precompile::public("is_sufficient(uint64)")]
+	#[precompile::view]
+	fn is_sufficient(handle: &mut impl PrecompileHandle, id: u64) -> EvmResult<bool> {
+		let asset_id = Self::existing_asset_id(handle, id)?;
+
+		Ok(Self::asset_details(asset_id)?.is_sufficient)
+	}
+
+	fn asset_id_parameter(id: u64) -> EvmResult<AssetIdParameterOf<Runtime, Instance>> {
+		id.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("asset id type").in_field("id").into())
+	}
+
+	fn id_topic(id: u64) -> H256 {
+		H256::from_low_u64_be(id)
+	}
+
+	/// Converts the `id` of an existing asset, recording the read of its details.
+	fn existing_asset_id(
+		handle: &mut impl PrecompileHandle,
+		id: u64,
+	) -> EvmResult<StorageAssetIdOf<Runtime, Instance>> {
+		handle.record_db_read::<Runtime>(AssetDetailsOf::<Runtime, Instance>::max_encoded_len())?;
+
+		let asset_id: StorageAssetIdOf<Runtime, Instance> = id
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("asset id type").in_field("id"))?;
+
+		if !Assets::<Runtime, Instance>::asset_exists(asset_id.clone()) {
+			return Err(RevertReason::custom("Asset not found").in_field("id").into());
+		}
+
+		Ok(asset_id)
+	}
+
+	/// Reads the details of an existing asset from the `Asset` storage of `pallet_assets`. Their
+	/// read is recorded by `existing_asset_id`.
+	fn asset_details(
+		asset_id: StorageAssetIdOf<Runtime, Instance>,
+	) -> EvmResult<AssetDetailsMirrorOf<Runtime, Instance>> {
+		let key = [
+			storage_prefix(Assets::<Runtime, Instance>::name().as_bytes(), b"Asset").as_slice(),
+			&asset_id.using_encoded(Blake2_128Concat::hash),
+		]
+		.concat();
+
+		unhashed::get(&key)
+			.ok_or_else(|| RevertReason::custom("Asset details cannot be decoded").into())
+	}
+
+	fn role_address(account: Option<Runtime::AccountId>) -> Address {
+		Address(
+			account.map_or_else(H160::zero, |account| {
+				AddressMapper::get_evm_address_or_default(&account)
+			}),
+		)
+	}
 }
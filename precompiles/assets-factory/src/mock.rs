@@ -140,10 +140,36 @@ impl pallet_balances::Config for Runtime {
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 /// Block Storage Limit in bytes. Set to 40KB.
 const BLOCK_STORAGE_LIMIT: u64 = 40 * 1024;
-pub type PCall = AssetsFactoryPrecompileCall<Runtime, ()>;
+pub type PCall = AssetsFactoryPrecompileCall<Runtime, MockAddressMapper, ()>;
+
+pub type Precompiles<R> = PrecompileSetBuilder<
+	R,
+	(PrecompileAt<AddressU64<1>, AssetsFactoryPrecompile<R, MockAddressMapper>>,),
+>;
+
+/// Uses the EVM address embedded in the mocked accounts.
+pub struct MockAddressMapper;
+impl EVMAddressMapping<AccountId> for MockAddressMapper {
+	fn get_account_id_or_default(evm: &H160) -> AccountId {
+		AccountId::into_account_id(*evm)
+	}
+
+	fn get_detault_account_id(evm: &H160) -> AccountId {
+		AccountId::into_account_id(*evm)
+	}
+
+	fn get_evm_address_or_default(account_id: &AccountId) -> H160 {
+		(*account_id).into()
+	}
 
-pub type Precompiles<R> =
-	PrecompileSetBuilder<R, (PrecompileAt<AddressU64<1>, AssetsFactoryPrecompile<R>>,)>;
+	fn get_detault_evm_address(account_id: &AccountId) -> H160 {
+		(*account_id).into()
+	}
+
+	fn is_linked(account_id: &AccountId, evm: &H160) -> bool {
+		H160::from(*account_id) == *evm
+	}
+}
 
 parameter_types! {
 	pub BlockGasLimit: U256 = U256::from(u64::MAX);
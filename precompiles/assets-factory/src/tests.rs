@@ -36,7 +36,9 @@
 
 use crate::{mock::*, *};
 
+use frame_support::assert_ok;
 use precompile_utils::testing::*;
+use std::str::from_utf8;
 
 fn precompiles() -> Precompiles<Runtime> {
 	PrecompilesValue::get()
@@ -81,6 +83,17 @@ fn selectors() {
 	assert!(PCall::transfer_ownership_selectors().contains(&0x0a94864e));
 	assert!(PCall::start_destroy_selectors().contains(&0x13f946af));
 	assert!(PCall::finish_destroy_selectors().contains(&0x99c720ff));
+	assert!(PCall::freeze_selectors().contains(&0x4d1a1635));
+	assert!(PCall::thaw_selectors().contains(&0xef33e57a));
+	assert!(PCall::freeze_asset_selectors().contains(&0xfdade1d5));
+	assert!(PCall::thaw_asset_selectors().contains(&0xc8ae38b6));
+	assert!(PCall::block_selectors().contains(&0x63422462));
+	assert!(PCall::force_transfer_selectors().contains(&0x831cd7a4));
+	assert!(PCall::owner_selectors().contains(&0x4d1f8c31));
+	assert!(PCall::team_selectors().contains(&0x23160313));
+	assert!(PCall::status_selectors().contains(&0x20bff893));
+	assert!(PCall::total_supply_selectors().contains(&0xdc6d08b7));
+	assert!(PCall::is_sufficient_selectors().contains(&0xffa6e5a7));
 }
 
 #[test]
@@ -105,6 +118,17 @@ fn modifiers() {
 			tester.test_default_modifier(PCall::transfer_ownership_selectors());
 			tester.test_default_modifier(PCall::start_destroy_selectors());
 			tester.test_default_modifier(PCall::finish_destroy_selectors());
+			tester.test_default_modifier(PCall::freeze_selectors());
+			tester.test_default_modifier(PCall::thaw_selectors());
+			tester.test_default_modifier(PCall::freeze_asset_selectors());
+			tester.test_default_modifier(PCall::thaw_asset_selectors());
+			tester.test_default_modifier(PCall::block_selectors());
+			tester.test_default_modifier(PCall::force_transfer_selectors());
+			tester.test_view_modifier(PCall::owner_selectors());
+			tester.test_view_modifier(PCall::team_selectors());
+			tester.test_view_modifier(PCall::status_selectors());
+			tester.test_view_modifier(PCall::total_supply_selectors());
+			tester.test_view_modifier(PCall::is_sufficient_selectors());
 		});
 }
 
@@ -361,3 +385,373 @@ fn end_destroy() {
 				.execute_returns(());
 		});
 }
+
+/// Creates asset 7 owned by Alice with Bob as team, and mints 1000 of it to Charlie.
+fn create_and_mint() {
+	precompiles()
+		.prepare_test(
+			MockPeaqAccount::Alice,
+			MockPeaqAccount::EVMu1Account,
+			PCall::create {
+				id: 7u64,
+				admin: Address(MockPeaqAccount::Bob.into()),
+				min_balance: 500,
+			},
+		)
+		.expect_no_logs()
+		.execute_returns(());
+
+	assert_ok!(Assets::mint(
+		RuntimeOrigin::signed(MockPeaqAccount::Bob),
+		MockAssetId(7),
+		MockPeaqAccount::Charlie,
+		1000
+	));
+}
+
+#[test]
+fn freeze_and_thaw() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::freeze { id: 7u64, who: Address(MockPeaqAccount::Charlie.into()) },
+				)
+				.expect_log(log3(
+					MockPeaqAccount::EVMu1Account,
+					SELECTOR_LOG_ACCOUNT_FROZEN,
+					H256::from_low_u64_be(7),
+					H160::from(MockPeaqAccount::Charlie),
+					Vec::new(),
+				))
+				.execute_returns(());
+
+			assert!(Assets::transfer(
+				RuntimeOrigin::signed(MockPeaqAccount::Charlie),
+				MockAssetId(7),
+				MockPeaqAccount::Alice,
+				500
+			)
+			.is_err());
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::thaw { id: 7u64, who: Address(MockPeaqAccount::Charlie.into()) },
+				)
+				.expect_log(log3(
+					MockPeaqAccount::EVMu1Account,
+					SELECTOR_LOG_ACCOUNT_THAWED,
+					H256::from_low_u64_be(7),
+					H160::from(MockPeaqAccount::Charlie),
+					Vec::new(),
+				))
+				.execute_returns(());
+
+			assert_ok!(Assets::transfer(
+				RuntimeOrigin::signed(MockPeaqAccount::Charlie),
+				MockAssetId(7),
+				MockPeaqAccount::Alice,
+				500
+			));
+		});
+}
+
+#[test]
+fn freeze_requires_freezer() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Charlie,
+					MockPeaqAccount::EVMu1Account,
+					PCall::freeze { id: 7u64, who: Address(MockPeaqAccount::Charlie.into()) },
+				)
+				.execute_reverts(|output| from_utf8(output).unwrap().contains("NoPermission"));
+		});
+}
+
+#[test]
+fn block() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::block { id: 7u64, who: Address(MockPeaqAccount::Charlie.into()) },
+				)
+				.expect_log(log3(
+					MockPeaqAccount::EVMu1Account,
+					SELECTOR_LOG_ACCOUNT_BLOCKED,
+					H256::from_low_u64_be(7),
+					H160::from(MockPeaqAccount::Charlie),
+					Vec::new(),
+				))
+				.execute_returns(());
+
+			assert!(Assets::mint(
+				RuntimeOrigin::signed(MockPeaqAccount::Bob),
+				MockAssetId(7),
+				MockPeaqAccount::Charlie,
+				100
+			)
+			.is_err());
+		});
+}
+
+#[test]
+fn freeze_and_thaw_asset() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::freeze_asset { id: 7u64 },
+				)
+				.expect_log(log2(
+					MockPeaqAccount::EVMu1Account,
+					SELECTOR_LOG_ASSET_FROZEN,
+					H256::from_low_u64_be(7),
+					Vec::new(),
+				))
+				.execute_returns(());
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::status { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(1u8);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::thaw_asset { id: 7u64 },
+				)
+				.expect_log(log2(
+					MockPeaqAccount::EVMu1Account,
+					SELECTOR_LOG_ASSET_THAWED,
+					H256::from_low_u64_be(7),
+					Vec::new(),
+				))
+				.execute_returns(());
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::status { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(0u8);
+		});
+}
+
+#[test]
+fn force_transfer() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Bob,
+					MockPeaqAccount::EVMu1Account,
+					PCall::force_transfer {
+						id: 7u64,
+						source: Address(MockPeaqAccount::Charlie.into()),
+						dest: Address(MockPeaqAccount::Alice.into()),
+						amount: 600,
+					},
+				)
+				.expect_log(log4(
+					MockPeaqAccount::EVMu1Account,
+					SELECTOR_LOG_FORCE_TRANSFERRED,
+					H256::from_low_u64_be(7),
+					H160::from(MockPeaqAccount::Charlie),
+					H160::from(MockPeaqAccount::Alice),
+					solidity::encode_event_data(600u128),
+				))
+				.execute_returns(());
+
+			assert_eq!(Assets::balance(MockAssetId(7), MockPeaqAccount::Charlie), 400);
+			assert_eq!(Assets::balance(MockAssetId(7), MockPeaqAccount::Alice), 600);
+		});
+}
+
+#[test]
+fn asset_views() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::owner { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(Address(MockPeaqAccount::Alice.into()));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::team { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns((
+					Address(MockPeaqAccount::Bob.into()),
+					Address(MockPeaqAccount::Bob.into()),
+					Address(MockPeaqAccount::Bob.into()),
+				));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::status { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(0u8);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::total_supply { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(U256::from(1000));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::is_sufficient { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(false);
+		});
+}
+
+#[test]
+fn asset_views_of_sufficient_and_destroyed_assets() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				MockAssetId(7),
+				MockPeaqAccount::Alice,
+				true,
+				1
+			));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::is_sufficient { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(true);
+
+			assert_ok!(Assets::start_destroy(
+				RuntimeOrigin::signed(MockPeaqAccount::Alice),
+				MockAssetId(7)
+			));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::status { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(2u8);
+		});
+}
+
+#[test]
+fn asset_views_ignore_the_funds_of_the_asset_address() {
+	ExtBuilder::default()
+		.with_balances(vec![(MockPeaqAccount::Alice, 5000), (MockPeaqAccount::Charlie, 5000)])
+		.build()
+		.execute_with(|| {
+			create_and_mint();
+
+			// Funding the account of the asset's own address must not hide its details.
+			let asset_account = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(
+				Runtime::asset_id_to_address(MockAssetId(7)).unwrap(),
+			);
+			assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), asset_account, 100));
+			assert_ok!(Assets::transfer(
+				RuntimeOrigin::signed(MockPeaqAccount::Charlie),
+				MockAssetId(7),
+				asset_account,
+				500
+			));
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::is_sufficient { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(false);
+
+			precompiles()
+				.prepare_test(
+					MockPeaqAccount::Alice,
+					MockPeaqAccount::EVMu1Account,
+					PCall::status { id: 7u64 },
+				)
+				.expect_no_logs()
+				.execute_returns(0u8);
+		});
+}
+
+#[test]
+fn asset_views_unknown_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				MockPeaqAccount::Alice,
+				MockPeaqAccount::EVMu1Account,
+				PCall::owner { id: 7u64 },
+			)
+			.execute_reverts(|output| output == b"id: Asset not found");
+	});
+}
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Council contract's address.
+address constant COUNCIL_ADDRESS = 0x000000000000000000000000000000000000080D;
+
+/// @dev The Council contract's instance.
+Collective constant COUNCIL_CONTRACT = Collective(COUNCIL_ADDRESS);
+
+/// @author The Peaq Team
+/// @title Collective Interface
+/// The interface through which solidity contracts will propose, vote on and close the motions
+/// of a collective, such as the council. Proposals are SCALE encoded runtime calls.
+/// @custom:address 0x000000000000000000000000000000000000080D
+interface Collective {
+    /// Executes a proposal as a single member of the collective
+    /// @custom:selector 09c5eabe
+    /// @param proposal The SCALE encoded call to execute
+    function execute(bytes memory proposal) external;
+
+    /// Opens a motion, or executes the proposal directly if the threshold is below 2
+    /// @custom:selector c57f3260
+    /// @param threshold The number of ayes needed to approve the motion
+    /// @param proposal The SCALE encoded call to execute once approved
+    /// @return proposed Whether a motion was opened, false if the proposal was executed directly
+    /// @return index The index of the motion, 0 if none was opened
+    function propose(uint32 threshold, bytes memory proposal)
+        external
+        returns (bool proposed, uint32 index);
+
+    /// Votes on an open motion
+    /// @custom:selector 73e37688
+    /// @param proposalHash The hash of the proposal
+    /// @param proposalIndex The index of the motion
+    /// @param approve Whether to vote aye or nay
+    function vote(
+        bytes32 proposalHash,
+        uint32 proposalIndex,
+        bool approve
+    ) external;
+
+    /// The weight of a call, as the maximum execution time and proof size.
+    struct Weight {
+        uint64 refTime;
+        uint64 proofSize;
+    }
+
+    /// Closes a motion once it has enough votes or once its voting period ended
+    /// @custom:selector e1a64bb2
+    /// @param proposalHash The hash of the proposal
+    /// @param proposalIndex The index of the motion
+    /// @param proposalWeightBound The maximum weight of the proposal
+    /// @param lengthBound The maximum length of the encoded proposal
+    /// @return executed Whether the proposal was approved and executed
+    function close(
+        bytes32 proposalHash,
+        uint32 proposalIndex,
+        Weight memory proposalWeightBound,
+        uint32 lengthBound
+    ) external returns (bool executed);
+
+    /// Computes the hash of a proposal
+    /// @custom:selector fc379417
+    /// @param proposal The SCALE encoded call
+    function proposalHash(bytes memory proposal) external view returns (bytes32);
+
+    /// Returns the hashes of the open motions
+    /// @custom:selector 55ef20e6
+    function proposals() external view returns (bytes32[] memory);
+
+    /// Returns the number of motions opened so far
+    /// @custom:selector da35c664
+    function proposalCount() external view returns (uint32);
+
+    /// Returns the members of the collective
+    /// @custom:selector bdd4d18d
+    function members() external view returns (address[] memory);
+
+    /// Returns whether an account is a member of the collective
+    /// @custom:selector a230c524
+    /// @param account The account to check
+    function isMember(address account) external view returns (bool);
+
+    /// Returns the prime member, or the zero address if there is none
+    /// @custom:selector c7ee005e
+    function prime() external view returns (address);
+
+    /// @dev Emitted when a proposal is executed
+    /// @param proposalHash The hash of the proposal
+    event Executed(bytes32 indexed proposalHash);
+
+    /// @dev Emitted when a motion is opened
+    /// @param who The member opening the motion
+    /// @param proposalIndex The index of the motion
+    /// @param proposalHash The hash of the proposal
+    /// @param threshold The number of ayes needed to approve the motion
+    event Proposed(
+        address indexed who,
+        uint32 indexed proposalIndex,
+        bytes32 indexed proposalHash,
+        uint32 threshold
+    );
+
+    /// @dev Emitted when a member votes on a motion
+    /// @param who The member voting
+    /// @param proposalHash The hash of the proposal
+    /// @param voted Whether the member voted aye
+    event Voted(address indexed who, bytes32 indexed proposalHash, bool voted);
+
+    /// @dev Emitted when a motion is closed without being executed
+    /// @param proposalHash The hash of the proposal
+    event Closed(bytes32 indexed proposalHash);
+}
@@ -0,0 +1,307 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Precompile to propose, vote on and close the motions of a `pallet_collective` instance via
+//! the EVM
+//!
+//! Proposals are SCALE encoded runtime calls. Members are given as EVM addresses and mapped to
+//! substrate accounts with the runtime `AddressMapping`, which is backed by address-unification.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use address_unification::EVMAddressMapping;
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	dispatch::{GetDispatchInfo, Pays, PostDispatchInfo},
+	traits::{ConstU32, Get},
+	weights::Weight,
+};
+use pallet_evm::AddressMapping;
+use parity_scale_codec::{DecodeLimit, MaxEncodedLen};
+use precompile_utils::{keccak256, prelude::*};
+use sp_core::{H160, H256};
+use sp_runtime::traits::{Dispatchable, Hash};
+use sp_std::{boxed::Box, marker::PhantomData, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the Executed log.
+pub const SELECTOR_LOG_EXECUTED: [u8; 32] = keccak256!("Executed(bytes32)");
+
+/// Solidity selector of the Proposed log.
+pub const SELECTOR_LOG_PROPOSED: [u8; 32] = keccak256!("Proposed(address,uint32,bytes32,uint32)");
+
+/// Solidity selector of the Voted log.
+pub const SELECTOR_LOG_VOTED: [u8; 32] = keccak256!("Voted(address,bytes32,bool)");
+
+/// Solidity selector of the Closed log.
+pub const SELECTOR_LOG_CLOSED: [u8; 32] = keccak256!("Closed(bytes32)");
+
+pub const PROPOSAL_SIZE_LIMIT: u32 = 2u32.pow(16);
+type GetProposalSizeLimit = ConstU32<PROPOSAL_SIZE_LIMIT>;
+
+/// Maximum depth when decoding a proposal.
+pub const MAX_CALL_DECODE_DEPTH: u32 = 8;
+
+pub type ProposalOf<Runtime, Instance> = <Runtime as pallet_collective::Config<Instance>>::Proposal;
+
+/// A precompile to propose, vote on and close the motions of a collective
+pub struct CollectivePrecompile<Runtime, AddressMapper, Instance: 'static>(
+	PhantomData<(Runtime, AddressMapper, Instance)>,
+);
+
+#[precompile_utils::precompile]
+impl<Runtime, AddressMapper, Instance> CollectivePrecompile<Runtime, AddressMapper, Instance>
+where
+	Instance: 'static,
+	Runtime: pallet_collective::Config<Instance> + pallet_evm::Config + frame_system::Config,
+	AddressMapper: EVMAddressMapping<Runtime::AccountId>,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		From<pallet_collective::Call<Runtime, Instance>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	Runtime::Hash: Into<H256> + From<H256>,
+{
+	#[precompile::public("execute(bytes)")]
+	fn execute(
+		handle: &mut impl PrecompileHandle,
+		proposal: BoundedBytes<GetProposalSizeLimit>,
+	) -> EvmResult {
+		let proposal: Vec<u8> = proposal.into();
+		let length_bound = proposal.len() as u32;
+		let proposal = Self::decode_proposal(proposal)?;
+		let proposal_hash = Self::hash_of(&proposal);
+
+		let log = log2(handle.context().address, SELECTOR_LOG_EXECUTED, proposal_hash, Vec::new());
+		handle.record_log_costs(&[&log])?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_collective::Call::<Runtime, Instance>::execute { proposal, length_bound },
+			0,
+		)?;
+
+		log.record(handle)?;
+
+		Ok(())
+	}
+
+	/// Returns whether a motion was opened, along with its index. A proposal whose threshold is
+	/// below 2 is executed right away instead, and no index is used.
+	#[precompile::public("propose(uint32,bytes)")]
+	fn propose(
+		handle: &mut impl PrecompileHandle,
+		threshold: u32,
+		proposal: BoundedBytes<GetProposalSizeLimit>,
+	) -> EvmResult<(bool, u32)> {
+		let proposal: Vec<u8> = proposal.into();
+		let length_bound = proposal.len() as u32;
+		let proposal = Self::decode_proposal(proposal)?;
+		let proposal_hash = Self::hash_of(&proposal);
+
+		// ProposalCount: u32(4), read before and after the dispatch.
+		handle.record_db_read::<Runtime>(4)?;
+		handle.record_db_read::<Runtime>(4)?;
+		let proposal_index = pallet_collective::Pallet::<Runtime, Instance>::proposal_count();
+
+		// With a threshold below 2 the proposal is executed right away instead of being stored.
+		let log = if threshold < 2 {
+			log2(handle.context().address, SELECTOR_LOG_EXECUTED, proposal_hash, Vec::new())
+		} else {
+			log4(
+				handle.context().address,
+				SELECTOR_LOG_PROPOSED,
+				handle.context().caller,
+				H256::from_low_u64_be(proposal_index as u64),
+				proposal_hash,
+				solidity::encode_event_data(threshold),
+			)
+		};
+		handle.record_log_costs(&[&log])?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_collective::Call::<Runtime, Instance>::propose {
+				threshold,
+				proposal,
+				length_bound,
+			},
+			0,
+		)?;
+
+		log.record(handle)?;
+
+		// The count is only increased when the motion is stored.
+		let proposed =
+			pallet_collective::Pallet::<Runtime, Instance>::proposal_count() != proposal_index;
+
+		Ok((proposed, if proposed { proposal_index } else { 0 }))
+	}
+
+	#[precompile::public("vote(bytes32,uint32,bool)")]
+	fn vote(
+		handle: &mut impl PrecompileHandle,
+		proposal_hash: H256,
+		proposal_index: u32,
+		approve: bool,
+	) -> EvmResult {
+		let log = log3(
+			handle.context().address,
+			SELECTOR_LOG_VOTED,
+			handle.context().caller,
+			proposal_hash,
+			solidity::encode_event_data(approve),
+		);
+		handle.record_log_costs(&[&log])?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_collective::Call::<Runtime, Instance>::vote {
+				proposal: proposal_hash.into(),
+				index: proposal_index,
+				approve,
+			},
+			0,
+		)?;
+
+		log.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("close(bytes32,uint32,(uint64,uint64),uint32)")]
+	fn close(
+		handle: &mut impl PrecompileHandle,
+		proposal_hash: H256,
+		proposal_index: u32,
+		proposal_weight_bound: Weight,
+		length_bound: u32,
+	) -> EvmResult<bool> {
+		// Both logs have the same cost.
+		handle.record_log_costs_manual(2, 0)?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let post_dispatch_info = RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_collective::Call::<Runtime, Instance>::close {
+				proposal_hash: proposal_hash.into(),
+				index: proposal_index,
+				proposal_weight_bound,
+				length_bound,
+			},
+			0,
+		)?;
+
+		let (executed, selector) = match post_dispatch_info.pays_fee {
+			Pays::Yes => (true, SELECTOR_LOG_EXECUTED),
+			Pays::No => (false, SELECTOR_LOG_CLOSED),
+		};
+		log2(handle.context().address, selector, proposal_hash, Vec::new()).record(handle)?;
+
+		Ok(executed)
+	}
+
+	#[precompile::public("proposalHash(bytes)")]
+	#[precompile::public("proposal_hash(bytes)")]
+	#[precompile::view]
+	fn proposal_hash(
+		_handle: &mut impl PrecompileHandle,
+		proposal: BoundedBytes<GetProposalSizeLimit>,
+	) -> EvmResult<H256> {
+		let proposal = Self::decode_proposal(proposal.into())?;
+
+		Ok(Self::hash_of(&proposal))
+	}
+
+	#[precompile::public("proposals()")]
+	#[precompile::view]
+	fn proposals(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<H256>> {
+		// Proposals: MaxProposals * Hash(32)
+		handle.record_db_read::<Runtime>(
+			(<Runtime as pallet_collective::Config<Instance>>::MaxProposals::get() as usize)
+				.saturating_mul(32),
+		)?;
+
+		Ok(pallet_collective::Pallet::<Runtime, Instance>::proposals()
+			.into_iter()
+			.map(Into::into)
+			.collect())
+	}
+
+	#[precompile::public("proposalCount()")]
+	#[precompile::public("proposal_count()")]
+	#[precompile::view]
+	fn proposal_count(handle: &mut impl PrecompileHandle) -> EvmResult<u32> {
+		// ProposalCount: u32(4)
+		handle.record_db_read::<Runtime>(4)?;
+
+		Ok(pallet_collective::Pallet::<Runtime, Instance>::proposal_count())
+	}
+
+	#[precompile::public("members()")]
+	#[precompile::view]
+	fn members(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<Address>> {
+		Self::record_members_read(handle)?;
+
+		Ok(pallet_collective::Pallet::<Runtime, Instance>::members()
+			.iter()
+			.map(|member| Address(AddressMapper::get_evm_address_or_default(member)))
+			.collect())
+	}
+
+	#[precompile::public("isMember(address)")]
+	#[precompile::public("is_member(address)")]
+	#[precompile::view]
+	fn is_member(handle: &mut impl PrecompileHandle, account: Address) -> EvmResult<bool> {
+		Self::record_members_read(handle)?;
+
+		let account = Runtime::AddressMapping::into_account_id(account.into());
+
+		Ok(pallet_collective::Pallet::<Runtime, Instance>::is_member(&account))
+	}
+
+	#[precompile::public("prime()")]
+	#[precompile::view]
+	fn prime(handle: &mut impl PrecompileHandle) -> EvmResult<Address> {
+		// Prime: AccountId
+		handle.record_db_read::<Runtime>(Runtime::AccountId::max_encoded_len())?;
+
+		Ok(pallet_collective::Pallet::<Runtime, Instance>::prime()
+			.map(|prime| Address(AddressMapper::get_evm_address_or_default(&prime)))
+			.unwrap_or(Address(H160::zero())))
+	}
+
+	fn record_members_read(handle: &mut impl PrecompileHandle) -> EvmResult {
+		// Members: MaxMembers * AccountId
+		handle.record_db_read::<Runtime>(
+			(<Runtime as pallet_collective::Config<Instance>>::MaxMembers::get() as usize)
+				.saturating_mul(Runtime::AccountId::max_encoded_len()),
+		)?;
+
+		Ok(())
+	}
+
+	fn hash_of(proposal: &ProposalOf<Runtime, Instance>) -> H256 {
+		<Runtime as frame_system::Config>::Hashing::hash_of(proposal).into()
+	}
+
+	fn decode_proposal(proposal: Vec<u8>) -> EvmResult<Box<ProposalOf<Runtime, Instance>>> {
+		ProposalOf::<Runtime, Instance>::decode_with_depth_limit(
+			MAX_CALL_DECODE_DEPTH,
+			&mut &proposal[..],
+		)
+		.map(Box::new)
+		.map_err(|_| RevertReason::custom("Failed decoding proposal").in_field("proposal").into())
+	}
+}
@@ -0,0 +1,268 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use parity_scale_codec::Encode;
+use precompile_utils::testing::*;
+use sp_runtime::traits::BlakeTwo256;
+use std::str::from_utf8;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn council() -> ExtBuilder {
+	ExtBuilder::default().with_members(vec![Alice.into(), Bob.into(), Charlie.into()])
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![1, 2, 3] })
+}
+
+fn remark_hash() -> H256 {
+	BlakeTwo256::hash_of(&remark())
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::execute_selectors().contains(&0x09c5eabe));
+	assert!(PCall::propose_selectors().contains(&0xc57f3260));
+	assert!(PCall::vote_selectors().contains(&0x73e37688));
+	assert!(PCall::close_selectors().contains(&0xe1a64bb2));
+	assert!(PCall::proposal_hash_selectors().contains(&0xfc379417));
+	assert!(PCall::proposals_selectors().contains(&0x55ef20e6));
+	assert!(PCall::proposal_count_selectors().contains(&0xda35c664));
+	assert!(PCall::members_selectors().contains(&0xbdd4d18d));
+	assert!(PCall::is_member_selectors().contains(&0xa230c524));
+	assert!(PCall::prime_selectors().contains(&0xc7ee005e));
+}
+
+#[test]
+fn modifiers() {
+	council().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+		tester.test_default_modifier(PCall::execute_selectors());
+		tester.test_default_modifier(PCall::propose_selectors());
+		tester.test_default_modifier(PCall::vote_selectors());
+		tester.test_default_modifier(PCall::close_selectors());
+		tester.test_view_modifier(PCall::proposal_hash_selectors());
+		tester.test_view_modifier(PCall::proposals_selectors());
+		tester.test_view_modifier(PCall::proposal_count_selectors());
+		tester.test_view_modifier(PCall::members_selectors());
+		tester.test_view_modifier(PCall::is_member_selectors());
+		tester.test_view_modifier(PCall::prime_selectors());
+	});
+}
+
+#[test]
+fn members_views() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::members {})
+			.expect_no_logs()
+			.execute_returns(vec![
+				Address(Alice.into()),
+				Address(Bob.into()),
+				Address(Charlie.into()),
+			]);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::is_member { account: Address(Bob.into()) })
+			.expect_no_logs()
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::is_member { account: Address(David.into()) })
+			.expect_no_logs()
+			.execute_returns(false);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::prime {})
+			.expect_no_logs()
+			.execute_returns(Address(H160::zero()));
+	});
+}
+
+#[test]
+fn proposal_hash() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::proposal_hash { proposal: remark().encode().into() },
+			)
+			.expect_no_logs()
+			.execute_returns(remark_hash());
+	});
+}
+
+#[test]
+fn execute_as_member() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::execute { proposal: remark().encode().into() })
+			.expect_log(log2(Precompile1, SELECTOR_LOG_EXECUTED, remark_hash(), Vec::new()))
+			.execute_returns(());
+	});
+}
+
+#[test]
+fn propose_requires_member() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				David,
+				Precompile1,
+				PCall::propose { threshold: 2, proposal: remark().encode().into() },
+			)
+			.execute_reverts(|output| from_utf8(output).unwrap().contains("NotMember"));
+	});
+}
+
+#[test]
+fn propose_below_threshold_2_executes_without_motion() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::propose { threshold: 1, proposal: remark().encode().into() },
+			)
+			.expect_log(log2(Precompile1, SELECTOR_LOG_EXECUTED, remark_hash(), Vec::new()))
+			.execute_returns((false, 0u32));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposal_count {})
+			.expect_no_logs()
+			.execute_returns(0u32);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposals {})
+			.expect_no_logs()
+			.execute_returns(Vec::<H256>::new());
+	});
+}
+
+#[test]
+fn propose_vote_and_close() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::propose { threshold: 2, proposal: remark().encode().into() },
+			)
+			.expect_log(log4(
+				Precompile1,
+				SELECTOR_LOG_PROPOSED,
+				H160::from(Alice),
+				H256::from_low_u64_be(0),
+				remark_hash(),
+				solidity::encode_event_data(2u32),
+			))
+			.execute_returns((true, 0u32));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposals {})
+			.expect_no_logs()
+			.execute_returns(vec![remark_hash()]);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposal_count {})
+			.expect_no_logs()
+			.execute_returns(1u32);
+
+		for voter in [H160::from(Alice), H160::from(Bob)] {
+			precompiles()
+				.prepare_test(
+					voter,
+					Precompile1,
+					PCall::vote { proposal_hash: remark_hash(), proposal_index: 0, approve: true },
+				)
+				.expect_log(log3(
+					Precompile1,
+					SELECTOR_LOG_VOTED,
+					voter,
+					remark_hash(),
+					solidity::encode_event_data(true),
+				))
+				.execute_returns(());
+		}
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::close {
+					proposal_hash: remark_hash(),
+					proposal_index: 0,
+					proposal_weight_bound: Weight::from_parts(1_000_000_000, 1_000_000),
+					length_bound: remark().encode().len() as u32,
+				},
+			)
+			.expect_log(log2(Precompile1, SELECTOR_LOG_EXECUTED, remark_hash(), Vec::new()))
+			.execute_returns(true);
+
+		assert!(events().contains(&RuntimeEvent::Council(pallet_collective::Event::Executed {
+			proposal_hash: remark_hash(),
+			result: Ok(()),
+		})));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposals {})
+			.expect_no_logs()
+			.execute_returns(Vec::<H256>::new());
+	});
+}
+
+#[test]
+fn close_disapproved_proposal() {
+	council().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::propose { threshold: 2, proposal: remark().encode().into() },
+			)
+			.execute_returns((true, 0u32));
+
+		for voter in [H160::from(Bob), H160::from(Charlie)] {
+			precompiles()
+				.prepare_test(
+					voter,
+					Precompile1,
+					PCall::vote { proposal_hash: remark_hash(), proposal_index: 0, approve: false },
+				)
+				.execute_returns(());
+		}
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::close {
+					proposal_hash: remark_hash(),
+					proposal_index: 0,
+					proposal_weight_bound: Weight::from_parts(1_000_000_000, 1_000_000),
+					length_bound: remark().encode().len() as u32,
+				},
+			)
+			.expect_log(log2(Precompile1, SELECTOR_LOG_CLOSED, remark_hash(), Vec::new()))
+			.execute_returns(false);
+
+		assert!(events().contains(&RuntimeEvent::Council(pallet_collective::Event::Disapproved {
+			proposal_hash: remark_hash(),
+		})));
+		assert!(!events().iter().any(|event| matches!(
+			event,
+			RuntimeEvent::Council(pallet_collective::Event::Executed { .. })
+		)));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposals {})
+			.expect_no_logs()
+			.execute_returns(Vec::<H256>::new());
+	});
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Inflation contract's address.
+address constant INFLATION_ADDRESS = 0x000000000000000000000000000000000000080F;
+
+/// @dev The Inflation contract's instance.
+Inflation constant INFLATION_CONTRACT = Inflation(INFLATION_ADDRESS);
+
+/// @author The Peaq Team
+/// @title Inflation Interface
+/// The interface through which solidity contracts will read the inflation and block reward
+/// parameters. Rates and shares are given in parts per billion.
+/// @custom:address 0x000000000000000000000000000000000000080F
+interface Inflation {
+    /// The inflation parameters of the current year.
+    struct InflationParameters {
+        uint32 inflationRate;
+        uint32 disinflationRate;
+    }
+
+    /// The shares of the block reward going to each beneficiary.
+    struct RewardDistribution {
+        uint32 treasury;
+        uint32 collatorsDelegators;
+        uint32 coretime;
+        uint32 subsidizationPool;
+        uint32 depinStaking;
+        uint32 depinIncentivization;
+    }
+
+    /// The free balances of the pots receiving the block rewards.
+    struct PotBalances {
+        uint256 treasury;
+        uint256 collatorsDelegators;
+        uint256 coretime;
+        uint256 subsidizationPool;
+        uint256 depinStaking;
+        uint256 depinIncentivization;
+    }
+
+    /// Returns the amount issued each block
+    /// @custom:selector da2448cb
+    function blockRewards() external view returns (uint256);
+
+    /// Returns the inflation parameters of the current year
+    /// @custom:selector 7db5d242
+    function inflationParameters()
+        external
+        view
+        returns (InflationParameters memory);
+
+    /// Returns the current year of inflation, starting at 1
+    /// @custom:selector 0b5a006b
+    function currentYear() external view returns (uint256);
+
+    /// Returns the block at which the inflation parameters are next recalculated
+    /// @custom:selector 73c88836
+    function doRecalculationAt() external view returns (uint32);
+
+    /// Returns the shares of the block reward going to each beneficiary
+    /// @custom:selector cade439b
+    function rewardDistributionConfig()
+        external
+        view
+        returns (RewardDistribution memory);
+
+    /// Returns the free balances of the pots receiving the block rewards
+    /// @custom:selector 8d1fd7a5
+    function potBalances() external view returns (PotBalances memory);
+}
@@ -0,0 +1,163 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Precompile to read the inflation and block reward parameters via the EVM
+//!
+//! Rates and shares are returned as parts per billion, the inner value of a `Perbill`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	traits::{Currency, Get},
+	PalletId,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_block_reward::RewardDistributionConfig;
+use parity_scale_codec::MaxEncodedLen;
+use precompile_utils::prelude::*;
+use sp_core::U256;
+use sp_runtime::{
+	traits::{AccountIdConversion, UniqueSaturatedInto},
+	PerThing,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// The inflation parameters of the current year.
+#[derive(Default, Debug, PartialEq, Eq, solidity::Codec)]
+pub struct InflationParameters {
+	pub inflation_rate: u32,
+	pub disinflation_rate: u32,
+}
+
+/// The shares of the block reward going to each beneficiary.
+#[derive(Default, Debug, PartialEq, Eq, solidity::Codec)]
+pub struct RewardDistribution {
+	pub treasury: u32,
+	pub collators_delegators: u32,
+	pub coretime: u32,
+	pub subsidization_pool: u32,
+	pub depin_staking: u32,
+	pub depin_incentivization: u32,
+}
+
+/// The free balances of the pots receiving the block rewards.
+#[derive(Default, Debug, PartialEq, Eq, solidity::Codec)]
+pub struct PotBalances {
+	pub treasury: U256,
+	pub collators_delegators: U256,
+	pub coretime: U256,
+	pub subsidization_pool: U256,
+	pub depin_staking: U256,
+	pub depin_incentivization: U256,
+}
+
+/// A precompile to read the inflation and block reward parameters.
+///
+/// `RewardPots` gives the pallet ids of the pots, in the order of the `RewardDistributionConfig`
+/// shares: treasury, collators and delegators, coretime, subsidization pool, DePIN staking and
+/// DePIN incentivization.
+pub struct InflationPrecompile<Runtime, RewardPots>(PhantomData<(Runtime, RewardPots)>);
+
+#[precompile_utils::precompile]
+impl<Runtime, RewardPots> InflationPrecompile<Runtime, RewardPots>
+where
+	Runtime: inflation_manager::Config + pallet_block_reward::Config + pallet_evm::Config,
+	RewardPots: Get<[PalletId; 6]>,
+	BlockNumberFor<Runtime>: UniqueSaturatedInto<u32>,
+{
+	#[precompile::public("blockRewards()")]
+	#[precompile::public("block_rewards()")]
+	#[precompile::view]
+	fn block_rewards(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		// BlockRewards: Balance(16)
+		handle.record_db_read::<Runtime>(16)?;
+
+		Ok(inflation_manager::Pallet::<Runtime>::block_rewards().into())
+	}
+
+	#[precompile::public("inflationParameters()")]
+	#[precompile::public("inflation_parameters()")]
+	#[precompile::view]
+	fn inflation_parameters(handle: &mut impl PrecompileHandle) -> EvmResult<InflationParameters> {
+		// InflationParameters: Perbill(4) + Perbill(4)
+		handle.record_db_read::<Runtime>(8)?;
+
+		let parameters = inflation_manager::Pallet::<Runtime>::inflation_parameters();
+
+		Ok(InflationParameters {
+			inflation_rate: parameters.inflation_rate.deconstruct(),
+			disinflation_rate: parameters.disinflation_rate.deconstruct(),
+		})
+	}
+
+	#[precompile::public("currentYear()")]
+	#[precompile::public("current_year()")]
+	#[precompile::view]
+	fn current_year(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		// CurrentYear: u128(16)
+		handle.record_db_read::<Runtime>(16)?;
+
+		Ok(inflation_manager::Pallet::<Runtime>::current_year().into())
+	}
+
+	#[precompile::public("doRecalculationAt()")]
+	#[precompile::public("do_recalculation_at()")]
+	#[precompile::view]
+	fn do_recalculation_at(handle: &mut impl PrecompileHandle) -> EvmResult<u32> {
+		// DoRecalculationAt: BlockNumber
+		handle.record_db_read::<Runtime>(BlockNumberFor::<Runtime>::max_encoded_len())?;
+
+		Ok(inflation_manager::Pallet::<Runtime>::do_recalculation_at().unique_saturated_into())
+	}
+
+	#[precompile::public("rewardDistributionConfig()")]
+	#[precompile::public("reward_distribution_config()")]
+	#[precompile::view]
+	fn reward_distribution_config(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<RewardDistribution> {
+		// RewardDistributionConfigStorage: RewardDistributionConfig
+		handle.record_db_read::<Runtime>(RewardDistributionConfig::max_encoded_len())?;
+
+		let config = pallet_block_reward::Pallet::<Runtime>::reward_config();
+
+		Ok(RewardDistribution {
+			treasury: config.treasury_percent.deconstruct(),
+			collators_delegators: config.collators_delegators_percent.deconstruct(),
+			coretime: config.coretime_percent.deconstruct(),
+			subsidization_pool: config.subsidization_pool_percent.deconstruct(),
+			depin_staking: config.depin_staking_percent.deconstruct(),
+			depin_incentivization: config.depin_incentivization_percent.deconstruct(),
+		})
+	}
+
+	#[precompile::public("potBalances()")]
+	#[precompile::public("pot_balances()")]
+	#[precompile::view]
+	fn pot_balances(handle: &mut impl PrecompileHandle) -> EvmResult<PotBalances> {
+		// One account read per pot
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(6))?;
+
+		let [treasury, collators_delegators, coretime, subsidization_pool, depin_staking, depin_incentivization] =
+			RewardPots::get().map(|pot| -> U256 {
+				<Runtime as inflation_manager::Config>::Currency::free_balance(
+					&pot.into_account_truncating(),
+				)
+				.into()
+			});
+
+		Ok(PotBalances {
+			treasury,
+			collators_delegators,
+			coretime,
+			subsidization_pool,
+			depin_staking,
+			depin_incentivization,
+		})
+	}
+}
@@ -0,0 +1,266 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Test utilities
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstU32, Everything},
+	weights::Weight,
+	PalletId,
+};
+use pallet_balances::NegativeImbalance;
+use pallet_evm::{EnsureAddressNever, EnsureAddressRoot};
+use precompile_utils::{precompile_set::*, testing::*};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, Perbill,
+};
+
+pub type AccountId = MockAccount;
+pub type Balance = u128;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Evm: pallet_evm,
+		Timestamp: pallet_timestamp,
+		InflationManager: inflation_manager,
+		BlockReward: pallet_block_reward,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u32 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+	type RuntimeTask = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type MaxLocks = ();
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+}
+
+parameter_types! {
+	pub const InflationPot: PalletId = PalletId(*b"inflapot");
+	pub const DefaultTotalIssuanceNum: Balance = 5_256_000_000_000;
+	pub const DefaultInflationConfiguration: inflation_manager::InflationConfigurationT =
+		inflation_manager::InflationConfigurationT {
+			inflation_parameters: inflation_manager::InflationParametersT {
+				inflation_rate: Perbill::from_perthousand(35u32),
+				disinflation_rate: Perbill::from_percent(10),
+			},
+			inflation_stagnation_rate: Perbill::from_percent(1),
+			inflation_stagnation_year: 13,
+		};
+	pub const InitializeInflationAt: u64 = 0;
+	pub const BlockRewardBeforeInitialize: Balance = 0;
+}
+
+impl inflation_manager::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PotId = InflationPot;
+	type DefaultTotalIssuanceNum = DefaultTotalIssuanceNum;
+	type DefaultInflationConfiguration = DefaultInflationConfiguration;
+	type BoundedDataLen = ConstU32<1024>;
+	type WeightInfo = inflation_manager::weights::WeightInfo<Runtime>;
+	type DoInitializeAt = InitializeInflationAt;
+	type BlockRewardBeforeInitialize = BlockRewardBeforeInitialize;
+}
+
+pub(crate) const TREASURY_POT: PalletId = PalletId(*b"py/trsry");
+pub(crate) const COLLATOR_DELEGATOR_POT: PalletId = PalletId(*b"PotStake");
+pub(crate) const CORETIME_POT: PalletId = PalletId(*b"PotCoret");
+pub(crate) const SUBSIDIZATION_POT: PalletId = PalletId(*b"PotSubsi");
+pub(crate) const DEPIN_STAKING_POT: PalletId = PalletId(*b"PotDPStk");
+pub(crate) const DEPIN_INCENTIVIZATION_POT: PalletId = PalletId(*b"PotDPInc");
+
+parameter_types! {
+	pub const RewardPots: [PalletId; 6] = [
+		TREASURY_POT,
+		COLLATOR_DELEGATOR_POT,
+		CORETIME_POT,
+		SUBSIDIZATION_POT,
+		DEPIN_STAKING_POT,
+		DEPIN_INCENTIVIZATION_POT,
+	];
+}
+
+pub struct BeneficiaryPayout;
+impl BeneficiaryPayout {
+	fn resolve(pot: PalletId, reward: NegativeImbalance<Runtime>) {
+		Balances::resolve_creating(&pot.into_account_truncating(), reward);
+	}
+}
+
+impl pallet_block_reward::BeneficiaryPayout<NegativeImbalance<Runtime>> for BeneficiaryPayout {
+	fn treasury(reward: NegativeImbalance<Runtime>) {
+		Self::resolve(TREASURY_POT, reward);
+	}
+
+	fn collators_delegators(reward: NegativeImbalance<Runtime>) {
+		Self::resolve(COLLATOR_DELEGATOR_POT, reward);
+	}
+
+	fn coretime(reward: NegativeImbalance<Runtime>) {
+		Self::resolve(CORETIME_POT, reward);
+	}
+
+	fn subsidization_pool(reward: NegativeImbalance<Runtime>) {
+		Self::resolve(SUBSIDIZATION_POT, reward);
+	}
+
+	fn depin_staking(reward: NegativeImbalance<Runtime>) {
+		Self::resolve(DEPIN_STAKING_POT, reward);
+	}
+
+	fn depin_incentivization(reward: NegativeImbalance<Runtime>) {
+		Self::resolve(DEPIN_INCENTIVIZATION_POT, reward);
+	}
+}
+
+impl pallet_block_reward::Config for Runtime {
+	type Currency = Balances;
+	type BeneficiaryPayout = BeneficiaryPayout;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_block_reward::weights::WeightInfo<Runtime>;
+}
+
+pub type Precompiles<R> =
+	PrecompileSetBuilder<R, (PrecompileAt<AddressU64<1>, InflationPrecompile<R, RewardPots>>,)>;
+
+pub type PCall = InflationPrecompileCall<Runtime, RewardPots>;
+
+const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+/// Block storage limit in bytes. Set to 40 KB.
+const BLOCK_STORAGE_LIMIT: u64 = 40 * 1024;
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(u64::MAX);
+	pub PrecompilesValue: Precompiles<Runtime> = Precompiles::new();
+	pub const WeightPerGas: Weight = Weight::from_parts(1, 0);
+	pub GasLimitPovSizeRatio: u64 = {
+		let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+		block_gas_limit.saturating_div(MAX_POV_SIZE)
+	};
+	pub GasLimitStorageGrowthRatio: u64 = {
+		let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+		block_gas_limit.saturating_div(BLOCK_STORAGE_LIMIT)
+	};
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type CallOrigin = EnsureAddressRoot<AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<AccountId>;
+	type AddressMapping = AccountId;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type PrecompilesValue = PrecompilesValue;
+	type PrecompilesType = Precompiles<Self>;
+	type ChainId = ();
+	type OnChargeTransaction = ();
+	type BlockGasLimit = BlockGasLimit;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type FindAuthor = ();
+	type OnCreate = ();
+	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+	type Timestamp = Timestamp;
+	type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
+	type SuicideQuickClearLimit = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+#[derive(Default)]
+pub(crate) struct ExtBuilder {
+	// endowed accounts with balances
+	balances: Vec<(AccountId, Balance)>,
+}
+
+impl ExtBuilder {
+	pub(crate) fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.expect("Frame system builds valid default genesis config");
+
+		pallet_balances::GenesisConfig::<Runtime> { balances: self.balances }
+			.assimilate_storage(&mut t)
+			.expect("Pallet balances storage can be assimilated");
+
+		pallet_block_reward::GenesisConfig::<Runtime>::default()
+			.assimilate_storage(&mut t)
+			.expect("Pallet block reward storage can be assimilated");
+
+		inflation_manager::GenesisConfig::<Runtime>::default()
+			.assimilate_storage(&mut t)
+			.expect("Inflation manager storage can be assimilated");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
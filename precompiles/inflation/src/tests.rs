@@ -0,0 +1,106 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use precompile_utils::testing::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::block_rewards_selectors().contains(&0xda2448cb));
+	assert!(PCall::inflation_parameters_selectors().contains(&0x7db5d242));
+	assert!(PCall::current_year_selectors().contains(&0x0b5a006b));
+	assert!(PCall::do_recalculation_at_selectors().contains(&0x73c88836));
+	assert!(PCall::reward_distribution_config_selectors().contains(&0xcade439b));
+	assert!(PCall::pot_balances_selectors().contains(&0x8d1fd7a5));
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+		tester.test_view_modifier(PCall::block_rewards_selectors());
+		tester.test_view_modifier(PCall::inflation_parameters_selectors());
+		tester.test_view_modifier(PCall::current_year_selectors());
+		tester.test_view_modifier(PCall::do_recalculation_at_selectors());
+		tester.test_view_modifier(PCall::reward_distribution_config_selectors());
+		tester.test_view_modifier(PCall::pot_balances_selectors());
+	});
+}
+
+#[test]
+fn inflation_of_first_year() {
+	ExtBuilder::default().build().execute_with(|| {
+		// 3.5% of the total issuance, spread over a year of blocks
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::block_rewards {})
+			.expect_no_logs()
+			.execute_returns(U256::from(35_000));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::inflation_parameters {})
+			.expect_no_logs()
+			.execute_returns(InflationParameters {
+				inflation_rate: 35_000_000,
+				disinflation_rate: 1_000_000_000,
+			});
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::current_year {})
+			.expect_no_logs()
+			.execute_returns(U256::one());
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::do_recalculation_at {})
+			.expect_no_logs()
+			.execute_returns(inflation_manager::BLOCKS_PER_YEAR);
+	});
+}
+
+#[test]
+fn reward_distribution_config() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::reward_distribution_config {})
+			.expect_no_logs()
+			.execute_returns(RewardDistribution {
+				treasury: 250_000_000,
+				collators_delegators: 400_000_000,
+				coretime: 100_000_000,
+				subsidization_pool: 50_000_000,
+				depin_staking: 50_000_000,
+				depin_incentivization: 150_000_000,
+			});
+	});
+}
+
+#[test]
+fn pot_balances() {
+	ExtBuilder::default()
+		.with_balances(vec![
+			(TREASURY_POT.into_account_truncating(), 100),
+			(COLLATOR_DELEGATOR_POT.into_account_truncating(), 200),
+			(CORETIME_POT.into_account_truncating(), 300),
+			(SUBSIDIZATION_POT.into_account_truncating(), 400),
+			(DEPIN_STAKING_POT.into_account_truncating(), 500),
+			(DEPIN_INCENTIVIZATION_POT.into_account_truncating(), 600),
+		])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(Alice, Precompile1, PCall::pot_balances {})
+				.expect_no_logs()
+				.execute_returns(PotBalances {
+					treasury: 100.into(),
+					collators_delegators: 200.into(),
+					coretime: 300.into(),
+					subsidization_pool: 400.into(),
+					depin_staking: 500.into(),
+					depin_incentivization: 600.into(),
+				});
+		});
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Multisig contract's address.
+address constant MULTISIG_ADDRESS = 0x000000000000000000000000000000000000080C;
+
+/// @dev The Multisig contract's instance.
+Multisig constant MULTISIG_CONTRACT = Multisig(MULTISIG_ADDRESS);
+
+/// @author The Peaq Team
+/// @title Multisig Interface
+/// The interface through which solidity contracts will create, approve and cancel multisig
+/// operations. Signatories are EVM addresses and can be given in any order. Only the calls allowed
+/// by the dispatch precompile can be dispatched from the multisig account.
+/// @custom:address 0x000000000000000000000000000000000000080C
+interface Multisig {
+    /// A point in time of the chain, identifying when a multisig operation was opened.
+    /// A height of 0 means there is no timepoint.
+    struct Timepoint {
+        uint32 height;
+        uint32 index;
+    }
+
+    /// The weight of a call, as the maximum execution time and proof size.
+    struct Weight {
+        uint64 refTime;
+        uint64 proofSize;
+    }
+
+    /// Approves a multisig operation and dispatches the call if the threshold is reached
+    /// @custom:selector 11749cd4
+    /// @param threshold The number of approvals needed to dispatch the call
+    /// @param otherSignatories The signatories of the multisig, without the caller
+    /// @param maybeTimepoint The timepoint of the first approval, if any
+    /// @param call The SCALE encoded call to dispatch from the multisig account
+    /// @param maxWeight The maximum weight of the call
+    function asMulti(
+        uint16 threshold,
+        address[] memory otherSignatories,
+        Timepoint memory maybeTimepoint,
+        bytes memory call,
+        Weight memory maxWeight
+    ) external;
+
+    /// Approves a multisig operation by the hash of its call, without dispatching it
+    /// @custom:selector 008b964e
+    /// @param threshold The number of approvals needed to dispatch the call
+    /// @param otherSignatories The signatories of the multisig, without the caller
+    /// @param maybeTimepoint The timepoint of the first approval, if any
+    /// @param callHash The blake2-256 hash of the SCALE encoded call
+    /// @param maxWeight The maximum weight of the call
+    function approveAsMulti(
+        uint16 threshold,
+        address[] memory otherSignatories,
+        Timepoint memory maybeTimepoint,
+        bytes32 callHash,
+        Weight memory maxWeight
+    ) external;
+
+    /// Cancels a multisig operation opened by the caller and releases its deposit
+    /// @custom:selector 2a42b358
+    /// @param threshold The number of approvals needed to dispatch the call
+    /// @param otherSignatories The signatories of the multisig, without the caller
+    /// @param timepoint The timepoint of the first approval
+    /// @param callHash The blake2-256 hash of the SCALE encoded call
+    function cancelAsMulti(
+        uint16 threshold,
+        address[] memory otherSignatories,
+        Timepoint memory timepoint,
+        bytes32 callHash
+    ) external;
+
+    /// Dispatches a call from a multisig account with a threshold of 1
+    /// @custom:selector f9bcdd45
+    /// @param otherSignatories The signatories of the multisig, without the caller
+    /// @param call The SCALE encoded call to dispatch from the multisig account
+    function asMultiThreshold1(address[] memory otherSignatories, bytes memory call)
+        external;
+
+    /// Returns the account of a multisig
+    /// @custom:selector a72a0b23
+    /// @param threshold The number of approvals needed to dispatch a call
+    /// @param signatories All the signatories of the multisig
+    /// @return evmAddress The EVM address of the multisig account
+    /// @return accountId The SCALE encoded multisig account id
+    function multisigAccount(uint16 threshold, address[] memory signatories)
+        external
+        view
+        returns (address evmAddress, bytes memory accountId);
+
+    /// Returns the state of a pending multisig operation
+    /// @custom:selector b5421a7f
+    /// @param threshold The number of approvals needed to dispatch the call
+    /// @param signatories All the signatories of the multisig
+    /// @param callHash The blake2-256 hash of the SCALE encoded call
+    /// @return exists Whether the operation is pending
+    /// @return when The timepoint of the first approval
+    /// @return deposit The amount reserved from the depositor
+    /// @return depositor The account which opened the operation
+    /// @return approvals The signatories which approved the operation
+    function getMultisig(
+        uint16 threshold,
+        address[] memory signatories,
+        bytes32 callHash
+    )
+        external
+        view
+        returns (
+            bool exists,
+            Timepoint memory when,
+            uint256 deposit,
+            address depositor,
+            address[] memory approvals
+        );
+}
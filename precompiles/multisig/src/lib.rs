@@ -0,0 +1,334 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Precompile to create, approve and cancel `pallet_multisig` operations via the EVM
+//!
+//! Signatories are given as EVM addresses and mapped to substrate accounts with the runtime
+//! `AddressMapping`, which is backed by address-unification. They are sorted here, so callers do
+//! not need to know the order of the mapped accounts, and duplicated signatories are rejected. A
+//! missing timepoint is encoded as a timepoint at height `0`.
+//!
+//! The calls dispatched from the multisig account must be accepted by the `CallFilter` of the
+//! precompile, as they would otherwise bypass the allowlist of the dispatch precompile (e.g. to
+//! run a nested EVM call from the multisig account).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use address_unification::EVMAddressMapping;
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::{ConstU32, Contains, Currency},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_evm::AddressMapping;
+use parity_scale_codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
+use precompile_utils::prelude::*;
+use sp_core::{H256, U256};
+use sp_runtime::traits::{Dispatchable, UniqueSaturatedInto};
+use sp_std::{boxed::Box, marker::PhantomData, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub const CALL_SIZE_LIMIT: u32 = 2u32.pow(16);
+type GetCallSizeLimit = ConstU32<CALL_SIZE_LIMIT>;
+
+/// Maximum depth when decoding the call executed by the multisig.
+pub const MAX_CALL_DECODE_DEPTH: u32 = 8;
+
+pub type GetMaxSignatories<Runtime> = <Runtime as pallet_multisig::Config>::MaxSignatories;
+
+pub type MultisigCallOf<Runtime> = <Runtime as pallet_multisig::Config>::RuntimeCall;
+
+pub type BalanceOf<Runtime> = <<Runtime as pallet_multisig::Config>::Currency as Currency<
+	<Runtime as frame_system::Config>::AccountId,
+>>::Balance;
+
+type MultisigOf<Runtime> = pallet_multisig::Multisig<
+	BlockNumberFor<Runtime>,
+	BalanceOf<Runtime>,
+	<Runtime as frame_system::Config>::AccountId,
+	GetMaxSignatories<Runtime>,
+>;
+
+/// Same encoding as `pallet_multisig::Multisig`, whose fields are private to the pallet.
+///
+/// Only decoded from a value read through `pallet_multisig::Multisigs`.
+#[derive(Decode)]
+struct MultisigDetails<BlockNumber, Balance, AccountId> {
+	when: pallet_multisig::Timepoint<BlockNumber>,
+	deposit: Balance,
+	depositor: AccountId,
+	approvals: Vec<AccountId>,
+}
+
+/// Size of an address-unification mapping entry: Twox64(8) + H160(20) + AccountId.
+fn address_mapping_entry_size<Runtime: frame_system::Config>() -> usize {
+	8 + 20 + Runtime::AccountId::max_encoded_len()
+}
+
+/// A multisig timepoint, the height being `0` when there is none.
+#[derive(Default, Debug, PartialEq, Eq, solidity::Codec)]
+pub struct Timepoint {
+	pub height: u32,
+	pub index: u32,
+}
+
+/// A precompile to create, approve and cancel multisig operations, dispatching only the calls
+/// accepted by `CallFilter`
+pub struct MultisigPrecompile<Runtime, AddressMapper, CallFilter>(
+	PhantomData<(Runtime, AddressMapper, CallFilter)>,
+);
+
+#[precompile_utils::precompile]
+impl<Runtime, AddressMapper, CallFilter> MultisigPrecompile<Runtime, AddressMapper, CallFilter>
+where
+	Runtime: pallet_multisig::Config + pallet_evm::Config + frame_system::Config,
+	AddressMapper: EVMAddressMapping<Runtime::AccountId>,
+	CallFilter: Contains<MultisigCallOf<Runtime>>,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime as frame_system::Config>::RuntimeCall: From<pallet_multisig::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	BlockNumberFor<Runtime>: From<u32>,
+	BalanceOf<Runtime>: Into<U256>,
+{
+	#[precompile::public("asMulti(uint16,address[],(uint32,uint32),bytes,(uint64,uint64))")]
+	#[precompile::public("as_multi(uint16,address[],(uint32,uint32),bytes,(uint64,uint64))")]
+	fn as_multi(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		other_signatories: BoundedVec<Address, GetMaxSignatories<Runtime>>,
+		maybe_timepoint: Timepoint,
+		call: BoundedBytes<GetCallSizeLimit>,
+		max_weight: Weight,
+	) -> EvmResult {
+		let other_signatories =
+			Self::signatories(handle, other_signatories.into(), "otherSignatories")?;
+		let call = Self::decode_call(call.into())?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_multisig::Call::<Runtime>::as_multi {
+			threshold,
+			other_signatories,
+			maybe_timepoint: Self::maybe_timepoint(maybe_timepoint),
+			call,
+			max_weight,
+		};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public(
+		"approveAsMulti(uint16,address[],(uint32,uint32),bytes32,(uint64,uint64))"
+	)]
+	#[precompile::public(
+		"approve_as_multi(uint16,address[],(uint32,uint32),bytes32,(uint64,uint64))"
+	)]
+	fn approve_as_multi(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		other_signatories: BoundedVec<Address, GetMaxSignatories<Runtime>>,
+		maybe_timepoint: Timepoint,
+		call_hash: H256,
+		max_weight: Weight,
+	) -> EvmResult {
+		let other_signatories =
+			Self::signatories(handle, other_signatories.into(), "otherSignatories")?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_multisig::Call::<Runtime>::approve_as_multi {
+			threshold,
+			other_signatories,
+			maybe_timepoint: Self::maybe_timepoint(maybe_timepoint),
+			call_hash: call_hash.into(),
+			max_weight,
+		};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("cancelAsMulti(uint16,address[],(uint32,uint32),bytes32)")]
+	#[precompile::public("cancel_as_multi(uint16,address[],(uint32,uint32),bytes32)")]
+	fn cancel_as_multi(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		other_signatories: BoundedVec<Address, GetMaxSignatories<Runtime>>,
+		timepoint: Timepoint,
+		call_hash: H256,
+	) -> EvmResult {
+		let other_signatories =
+			Self::signatories(handle, other_signatories.into(), "otherSignatories")?;
+		let timepoint = Self::maybe_timepoint(timepoint)
+			.ok_or_else(|| RevertReason::custom("Timepoint is required").in_field("timepoint"))?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call = pallet_multisig::Call::<Runtime>::cancel_as_multi {
+			threshold,
+			other_signatories,
+			timepoint,
+			call_hash: call_hash.into(),
+		};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("asMultiThreshold1(address[],bytes)")]
+	#[precompile::public("as_multi_threshold_1(address[],bytes)")]
+	fn as_multi_threshold_1(
+		handle: &mut impl PrecompileHandle,
+		other_signatories: BoundedVec<Address, GetMaxSignatories<Runtime>>,
+		call: BoundedBytes<GetCallSizeLimit>,
+	) -> EvmResult {
+		let other_signatories =
+			Self::signatories(handle, other_signatories.into(), "otherSignatories")?;
+		let call = Self::decode_call(call.into())?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let call =
+			pallet_multisig::Call::<Runtime>::as_multi_threshold_1 { other_signatories, call };
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("multisigAccount(uint16,address[])")]
+	#[precompile::public("multisig_account(uint16,address[])")]
+	#[precompile::view]
+	fn multisig_account(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		signatories: BoundedVec<Address, GetMaxSignatories<Runtime>>,
+	) -> EvmResult<(Address, UnboundedBytes)> {
+		let account = Self::multi_account_id(handle, threshold, signatories.into())?;
+		handle.record_db_read::<Runtime>(address_mapping_entry_size::<Runtime>())?;
+
+		Ok((Address(AddressMapper::get_evm_address_or_default(&account)), account.encode().into()))
+	}
+
+	#[precompile::public("getMultisig(uint16,address[],bytes32)")]
+	#[precompile::public("get_multisig(uint16,address[],bytes32)")]
+	#[precompile::view]
+	fn get_multisig(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		signatories: BoundedVec<Address, GetMaxSignatories<Runtime>>,
+		call_hash: H256,
+	) -> EvmResult<(bool, Timepoint, U256, Address, Vec<Address>)> {
+		// Multisigs: Twox64(8) + AccountId + Blake2_128(16) + call hash(32) + Multisig
+		handle.record_db_read::<Runtime>(
+			8 + Runtime::AccountId::max_encoded_len() +
+				16 + 32 + MultisigOf::<Runtime>::max_encoded_len(),
+		)?;
+
+		let account = Self::multi_account_id(handle, threshold, signatories.into())?;
+		let details =
+			pallet_multisig::Multisigs::<Runtime>::get(&account, <[u8; 32]>::from(call_hash))
+				.map(|multisig| Self::multisig_details(&multisig))
+				.transpose()?;
+
+		Ok(match details {
+			Some(details) => {
+				// The depositor and every approval are mapped back to EVM addresses.
+				handle.record_db_read::<Runtime>(address_mapping_entry_size::<Runtime>())?;
+				for _ in &details.approvals {
+					handle.record_db_read::<Runtime>(address_mapping_entry_size::<Runtime>())?;
+				}
+
+				(
+					true,
+					Timepoint {
+						height: details.when.height.unique_saturated_into(),
+						index: details.when.index,
+					},
+					details.deposit.into(),
+					Address(AddressMapper::get_evm_address_or_default(&details.depositor)),
+					details
+						.approvals
+						.iter()
+						.map(|account| Address(AddressMapper::get_evm_address_or_default(account)))
+						.collect(),
+				)
+			},
+			None => (false, Timepoint::default(), U256::zero(), Address::default(), Vec::new()),
+		})
+	}
+
+	/// Maps the signatories to substrate accounts, sorted as expected by `pallet_multisig`.
+	///
+	/// Reverts on duplicated signatories instead of silently dropping them, as they would
+	/// otherwise derive the multisig account of a smaller set.
+	fn signatories(
+		handle: &mut impl PrecompileHandle,
+		signatories: Vec<Address>,
+		field: &'static str,
+	) -> EvmResult<Vec<Runtime::AccountId>> {
+		let mut accounts = Vec::with_capacity(signatories.len());
+		for address in signatories {
+			handle.record_db_read::<Runtime>(address_mapping_entry_size::<Runtime>())?;
+			accounts.push(Runtime::AddressMapping::into_account_id(address.into()));
+		}
+
+		accounts.sort();
+		if accounts.windows(2).any(|pair| pair[0] == pair[1]) {
+			return Err(RevertReason::custom("Duplicate signatory").in_field(field).into())
+		}
+
+		Ok(accounts)
+	}
+
+	fn multi_account_id(
+		handle: &mut impl PrecompileHandle,
+		threshold: u16,
+		signatories: Vec<Address>,
+	) -> EvmResult<Runtime::AccountId> {
+		let signatories = Self::signatories(handle, signatories, "signatories")?;
+
+		Ok(pallet_multisig::Pallet::<Runtime>::multi_account_id(&signatories, threshold))
+	}
+
+	/// Reads the fields of a stored multisig, which `pallet_multisig` keeps private.
+	fn multisig_details(
+		multisig: &MultisigOf<Runtime>,
+	) -> EvmResult<MultisigDetails<BlockNumberFor<Runtime>, BalanceOf<Runtime>, Runtime::AccountId>>
+	{
+		MultisigDetails::decode(&mut &multisig.encode()[..])
+			.map_err(|_| revert("Failed decoding multisig"))
+	}
+
+	fn maybe_timepoint(
+		timepoint: Timepoint,
+	) -> Option<pallet_multisig::Timepoint<BlockNumberFor<Runtime>>> {
+		(timepoint.height != 0).then(|| pallet_multisig::Timepoint {
+			height: timepoint.height.into(),
+			index: timepoint.index,
+		})
+	}
+
+	/// Decodes the call to dispatch from the multisig account, which must be accepted by
+	/// `CallFilter`.
+	fn decode_call(call: Vec<u8>) -> EvmResult<Box<MultisigCallOf<Runtime>>> {
+		let call = MultisigCallOf::<Runtime>::decode_with_depth_limit(
+			MAX_CALL_DECODE_DEPTH,
+			&mut &call[..],
+		)
+		.map_err(|_| RevertReason::custom("Failed decoding call").in_field("call"))?;
+
+		if !CallFilter::contains(&call) {
+			return Err(RevertReason::custom("Call filtered out").in_field("call").into())
+		}
+
+		Ok(Box::new(call))
+	}
+}
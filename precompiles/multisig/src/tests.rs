@@ -0,0 +1,379 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use frame_support::assert_ok;
+use precompile_utils::testing::*;
+use sp_core::H160;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn max_weight() -> Weight {
+	Weight::from_parts(1_000_000_000, 1_000_000)
+}
+
+fn transfer_call(dest: impl Into<AccountId>, value: Balance) -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death { dest: dest.into(), value })
+}
+
+fn multi_account_id(threshold: u16, signatories: Vec<AccountId>) -> AccountId {
+	let mut signatories = signatories;
+	signatories.sort();
+	pallet_multisig::Pallet::<Runtime>::multi_account_id(&signatories, threshold)
+}
+
+fn fund(account: AccountId, value: Balance) {
+	assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(Alice.into()), account, value));
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::as_multi_selectors().contains(&0x11749cd4));
+	assert!(PCall::approve_as_multi_selectors().contains(&0x008b964e));
+	assert!(PCall::cancel_as_multi_selectors().contains(&0x2a42b358));
+	assert!(PCall::as_multi_threshold_1_selectors().contains(&0xf9bcdd45));
+	assert!(PCall::multisig_account_selectors().contains(&0xa72a0b23));
+	assert!(PCall::get_multisig_selectors().contains(&0xb5421a7f));
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 1000)])
+		.build()
+		.execute_with(|| {
+			let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+			tester.test_default_modifier(PCall::as_multi_selectors());
+			tester.test_default_modifier(PCall::approve_as_multi_selectors());
+			tester.test_default_modifier(PCall::cancel_as_multi_selectors());
+			tester.test_default_modifier(PCall::as_multi_threshold_1_selectors());
+			tester.test_view_modifier(PCall::multisig_account_selectors());
+			tester.test_view_modifier(PCall::get_multisig_selectors());
+		});
+}
+
+#[test]
+fn multisig_account_sorts_signatories() {
+	ExtBuilder::default().build().execute_with(|| {
+		let multi = multi_account_id(2, vec![Alice.into(), Bob.into(), Charlie.into()]);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::multisig_account {
+					threshold: 2,
+					signatories: vec![
+						Address(Charlie.into()),
+						Address(Alice.into()),
+						Address(Bob.into()),
+					]
+					.into(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns((Address(multi.clone().into()), UnboundedBytes::from(multi.encode())));
+	});
+}
+
+#[test]
+fn multisig_account_rejects_duplicate_signatories() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::multisig_account {
+					threshold: 2,
+					signatories: vec![
+						Address(Alice.into()),
+						Address(Bob.into()),
+						Address(Alice.into()),
+					]
+					.into(),
+				},
+			)
+			.execute_reverts(|output| output == b"signatories: Duplicate signatory");
+	});
+}
+
+#[test]
+fn as_multi_threshold_1_rejects_duplicate_signatories() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::as_multi_threshold_1 {
+						other_signatories: vec![Address(Bob.into()), Address(Bob.into())].into(),
+						call: transfer_call(Charlie, 400).encode().into(),
+					},
+				)
+				.execute_reverts(|output| output == b"otherSignatories: Duplicate signatory");
+		});
+}
+
+#[test]
+fn as_multi_threshold_1_dispatches_call() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			let multi = multi_account_id(1, vec![Alice.into(), Bob.into()]);
+			fund(multi.clone(), 1000);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::as_multi_threshold_1 {
+						other_signatories: vec![Address(Bob.into())].into(),
+						call: transfer_call(Charlie, 400).encode().into(),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(());
+
+			assert_eq!(balance(multi), 600);
+			assert_eq!(balance(Charlie), 400);
+		});
+}
+
+#[test]
+fn approve_then_as_multi_dispatches_call() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000), (Bob.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			let multi = multi_account_id(2, vec![Alice.into(), Bob.into()]);
+			fund(multi.clone(), 1000);
+
+			let call = transfer_call(Charlie, 400);
+			let call_hash = H256::from(sp_io::hashing::blake2_256(&call.encode()));
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::approve_as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Bob.into())].into(),
+						maybe_timepoint: Timepoint::default(),
+						call_hash,
+						max_weight: max_weight(),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(());
+
+			// DepositBase + 2 * DepositFactor
+			let deposit = 12u128;
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::get_multisig {
+						threshold: 2,
+						signatories: vec![Address(Alice.into()), Address(Bob.into())].into(),
+						call_hash,
+					},
+				)
+				.expect_no_logs()
+				.execute_returns((
+					true,
+					Timepoint { height: 1, index: 0 },
+					U256::from(deposit),
+					Address(Alice.into()),
+					vec![Address(Alice.into())],
+				));
+
+			precompiles()
+				.prepare_test(
+					Bob,
+					Precompile1,
+					PCall::as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Alice.into())].into(),
+						maybe_timepoint: Timepoint { height: 1, index: 0 },
+						call: call.encode().into(),
+						max_weight: max_weight(),
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(());
+
+			assert_eq!(balance(multi), 600);
+			assert_eq!(balance(Charlie), 400);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::get_multisig {
+						threshold: 2,
+						signatories: vec![Address(Alice.into()), Address(Bob.into())].into(),
+						call_hash,
+					},
+				)
+				.expect_no_logs()
+				.execute_returns((
+					false,
+					Timepoint::default(),
+					U256::zero(),
+					Address(H160::zero()),
+					Vec::<Address>::new(),
+				));
+		});
+}
+
+#[test]
+fn cancel_as_multi_removes_operation() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			let call_hash =
+				H256::from(sp_io::hashing::blake2_256(&transfer_call(Charlie, 400).encode()));
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::approve_as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Bob.into())].into(),
+						maybe_timepoint: Timepoint::default(),
+						call_hash,
+						max_weight: max_weight(),
+					},
+				)
+				.execute_returns(());
+			assert_eq!(balance(Alice), 10_000 - 12);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::cancel_as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Bob.into())].into(),
+						timepoint: Timepoint { height: 1, index: 0 },
+						call_hash,
+					},
+				)
+				.expect_no_logs()
+				.execute_returns(());
+
+			assert_eq!(balance(Alice), 10_000);
+		});
+}
+
+#[test]
+fn cancel_as_multi_requires_timepoint() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::cancel_as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Bob.into())].into(),
+						timepoint: Timepoint::default(),
+						call_hash: H256::repeat_byte(1),
+					},
+				)
+				.execute_reverts(|output| output == b"timepoint: Timepoint is required");
+		});
+}
+
+#[test]
+fn as_multi_fails_to_decode_call() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Bob.into())].into(),
+						maybe_timepoint: Timepoint::default(),
+						call: vec![0xff, 0xff].into(),
+						max_weight: max_weight(),
+					},
+				)
+				.execute_reverts(|output| output == b"call: Failed decoding call");
+		});
+}
+
+#[test]
+fn as_multi_threshold_1_rejects_filtered_calls() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			let multi = multi_account_id(1, vec![Alice.into(), Bob.into()]);
+			fund(multi.clone(), 1000);
+
+			let evm_call = RuntimeCall::Evm(pallet_evm::Call::call {
+				source: multi.clone().into(),
+				target: Charlie.into(),
+				input: Vec::new(),
+				value: U256::zero(),
+				gas_limit: 100_000,
+				max_fee_per_gas: U256::zero(),
+				max_priority_fee_per_gas: None,
+				nonce: None,
+				access_list: Vec::new(),
+			});
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::as_multi_threshold_1 {
+						other_signatories: vec![Address(Bob.into())].into(),
+						call: evm_call.encode().into(),
+					},
+				)
+				.execute_reverts(|output| output == b"call: Call filtered out");
+
+			assert_eq!(balance(multi), 1000);
+		});
+}
+
+#[test]
+fn as_multi_rejects_filtered_calls() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 10_000)])
+		.build()
+		.execute_with(|| {
+			let evm_call =
+				RuntimeCall::Evm(pallet_evm::Call::withdraw { address: Alice.into(), value: 1 });
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::as_multi {
+						threshold: 2,
+						other_signatories: vec![Address(Bob.into())].into(),
+						maybe_timepoint: Timepoint::default(),
+						call: evm_call.encode().into(),
+						max_weight: max_weight(),
+					},
+				)
+				.execute_reverts(|output| output == b"call: Call filtered out");
+		});
+}
@@ -20,6 +20,27 @@ interface DID {
         bytes memory name
     ) external view returns (Attribute memory);
 
+    /// Reads the attributes of a DID among the given names, skipping the ones it does not have.
+    /// The DID pallet keys attributes by a hash of the DID and the name, so they cannot be
+    /// enumerated and are read by name.
+    function readAttributes(
+        address did_account,
+        bytes[] memory names
+    ) external view returns (Attribute[] memory);
+
+    /// Whether a DID has the attribute and it has not expired at the current block.
+    function isValid(
+        address did_account,
+        bytes memory name
+    ) external view returns (bool);
+
+    /// Returns the W3C-style JSON document of a DID, built from its valid attributes among the
+    /// given names.
+    function readDocument(
+        address did_account,
+        bytes[] memory names
+    ) external view returns (bytes memory);
+
     function addAttribute(
         address did_account,
         bytes memory name,
@@ -39,6 +60,55 @@ interface DID {
         bytes memory name
     ) external returns (bool);
 
+    /// Adds an attribute on behalf of the owner, who signed it with EIP-712
+    /// AddAttribute(address owner,address didAccount,bytes name,bytes value,uint32 validFor,uint256 nonce,uint256 deadline)
+    /// so that a relayer can send the transaction and pay its gas.
+    function addAttributeWithSignature(
+        address owner,
+        address did_account,
+        bytes memory name,
+        bytes memory value,
+        uint32 validity_for,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bool);
+
+    /// Updates an attribute on behalf of the owner, who signed it with EIP-712
+    /// UpdateAttribute(address owner,address didAccount,bytes name,bytes value,uint32 validFor,uint256 nonce,uint256 deadline)
+    function updateAttributeWithSignature(
+        address owner,
+        address did_account,
+        bytes memory name,
+        bytes memory value,
+        uint32 validity_for,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bool);
+
+    /// Removes an attribute on behalf of the owner, who signed it with EIP-712
+    /// RemoveAttribute(address owner,address didAccount,bytes name,uint256 nonce,uint256 deadline)
+    function removeAttributeWithSignature(
+        address owner,
+        address did_account,
+        bytes memory name,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bool);
+
+    /// Returns the nonce the next signed write of the owner must use.
+    function nonces(address owner) external view returns (uint256);
+
+    /// Returns the EIP-712 domain separator, for the domain
+    /// EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)
+    /// named "peaq DID" at version "1".
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+
     event AddAttribute(
         address sender,
         address did_account,
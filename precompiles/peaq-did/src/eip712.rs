@@ -0,0 +1,150 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! EIP-712 signatures allowing a relayer to write attributes on behalf of a DID owner.
+
+use super::*;
+use frame_support::traits::StorageInstance;
+use precompile_utils::eip712::Eip712Domain;
+use sp_core::{H160, H256};
+use sp_io::hashing::keccak_256;
+
+/// EIP-712 typehash of a signed `addAttribute`.
+pub const ADD_ATTRIBUTE_TYPEHASH: [u8; 32] = keccak256!(
+	"AddAttribute(address owner,address didAccount,bytes name,bytes value,uint32 validFor,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 typehash of a signed `updateAttribute`.
+pub const UPDATE_ATTRIBUTE_TYPEHASH: [u8; 32] = keccak256!(
+	"UpdateAttribute(address owner,address didAccount,bytes name,bytes value,uint32 validFor,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 typehash of a signed `removeAttribute`.
+pub const REMOVE_ATTRIBUTE_TYPEHASH: [u8; 32] = keccak256!(
+	"RemoveAttribute(address owner,address didAccount,bytes name,uint256 nonce,uint256 deadline)"
+);
+
+pub struct Nonces;
+
+impl StorageInstance for Nonces {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"PeaqDIDPrecompile"
+	}
+}
+
+pub struct DidDomain;
+
+impl Eip712Domain for DidDomain {
+	const NAME: &'static str = "peaq DID";
+
+	type Nonces = Nonces;
+}
+
+/// Storage type used to store the nonces of the signed writes, protecting against replays.
+pub type NoncesStorage = precompile_utils::eip712::NoncesStorage<Nonces>;
+
+/// Checks the signatures and serves the nonces and the domain separator of the precompile.
+pub type Signatures<Runtime> = precompile_utils::eip712::Eip712<Runtime, DidDomain>;
+
+/// Digests of the typed writes of the precompile.
+pub struct Eip712<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Eip712<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	#[allow(clippy::too_many_arguments)]
+	pub fn add_attribute_digest(
+		address: H160,
+		owner: H160,
+		did_account: H160,
+		name: &[u8],
+		value: &[u8],
+		valid_for: u32,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		Self::attribute_digest(
+			ADD_ATTRIBUTE_TYPEHASH,
+			address,
+			owner,
+			did_account,
+			name,
+			value,
+			valid_for,
+			nonce,
+			deadline,
+		)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn update_attribute_digest(
+		address: H160,
+		owner: H160,
+		did_account: H160,
+		name: &[u8],
+		value: &[u8],
+		valid_for: u32,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		Self::attribute_digest(
+			UPDATE_ATTRIBUTE_TYPEHASH,
+			address,
+			owner,
+			did_account,
+			name,
+			value,
+			valid_for,
+			nonce,
+			deadline,
+		)
+	}
+
+	pub fn remove_attribute_digest(
+		address: H160,
+		owner: H160,
+		did_account: H160,
+		name: &[u8],
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let struct_hash = keccak_256(&solidity::encode_arguments((
+			H256::from(REMOVE_ATTRIBUTE_TYPEHASH),
+			Address(owner),
+			Address(did_account),
+			H256::from(keccak_256(name)),
+			nonce,
+			deadline,
+		)));
+
+		Signatures::<Runtime>::generate_digest(address, struct_hash)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn attribute_digest(
+		typehash: [u8; 32],
+		address: H160,
+		owner: H160,
+		did_account: H160,
+		name: &[u8],
+		value: &[u8],
+		valid_for: u32,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let struct_hash = keccak_256(&solidity::encode_arguments((
+			H256::from(typehash),
+			Address(owner),
+			Address(did_account),
+			H256::from(keccak_256(name)),
+			H256::from(keccak_256(value)),
+			valid_for,
+			nonce,
+			deadline,
+		)));
+
+		Signatures::<Runtime>::generate_digest(address, struct_hash)
+	}
+}
@@ -9,8 +9,8 @@ use frame_support::{
 	BoundedVec,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
-use sp_core::{Decode, U256};
-use sp_runtime::traits::Dispatchable;
+use sp_core::{Decode, Encode, H160, H256, U256};
+use sp_runtime::traits::{Dispatchable, UniqueSaturatedInto};
 use sp_std::{marker::PhantomData, vec::Vec};
 
 use fp_evm::PrecompileHandle;
@@ -20,22 +20,33 @@ use pallet_evm::AddressMapping;
 use peaq_pallet_did::{
 	did::Did as PeaqDidT,
 	pallet::{MAX_NAME_SIZE as MAX_DID_NAME_SIZE, MAX_VALUE_SIZE as MAX_DID_VALUE_SIZE},
+	structs::Attribute,
 };
+use peaq_primitives_did::{self as did_document, DocumentAttribute};
 use precompile_utils::{
 	keccak256,
 	prelude::{
-		log1, Address, BoundedBytes, LogExt, Revert, RevertReason, RuntimeHelper, String,
-		UnboundedBytes,
+		log1, Address, BoundedBytes, BoundedVec as SolidityBoundedVec, LogExt, Revert,
+		RevertReason, RuntimeHelper, String, UnboundedBytes,
 	},
 	solidity, EvmResult,
 };
 
+mod eip712;
+pub use eip712::{Eip712, NoncesStorage, Signatures};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 type MaxValueSize = ConstU32<{ MAX_DID_VALUE_SIZE as u32 }>;
 type MaxNameSize = ConstU32<{ MAX_DID_NAME_SIZE as u32 }>;
 type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
 type MomentOf<Runtime> = <Runtime as pallet_timestamp::Config>::Moment;
 
 type GetBytesLimit = ConstU32<{ 2u32.pow(16) }>;
+type GetArrayLimit = ConstU32<64>;
 pub(crate) const SELECTOR_LOG_ADD_ATTRIBUTE: [u8; 32] =
 	keccak256!("AddAttribute(address,address,bytes,bytes,uint32)");
 
@@ -47,7 +58,7 @@ pub(crate) const SELECTOR_LOG_REMOVE_ATTRIBUTE: [u8; 32] =
 
 pub struct PeaqDIDPrecompile<Runtime>(PhantomData<Runtime>);
 
-#[derive(Default, Debug, solidity::Codec)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, solidity::Codec)]
 pub struct EVMAttribute {
 	name: UnboundedBytes,
 	value: UnboundedBytes,
@@ -83,13 +94,97 @@ where
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 		let did_account = Runtime::AddressMapping::into_account_id(did_account.into());
 		match peaq_pallet_did::Pallet::<Runtime>::read(&did_account, &Vec::<u8>::from(name)) {
-			Some(v) => Ok(EVMAttribute {
-				name: v.name.to_vec().into(),
-				value: v.value.to_vec().into(),
+			Some(v) => Ok(Self::evm_attribute(v)),
+			None => Err(Revert::new(RevertReason::custom("Cannot find the item")).into()),
+		}
+	}
+
+	/// Reads the attributes of a DID among the given names, skipping the ones it does not have.
+	///
+	/// The DID pallet keys attributes by a hash of the DID and the name, so the attributes of a
+	/// DID cannot be enumerated and are read by name.
+	#[precompile::public("readAttributes(address,bytes[])")]
+	#[precompile::public("read_attributes(address,bytes[])")]
+	#[precompile::view]
+	fn read_attributes(
+		handle: &mut impl PrecompileHandle,
+		did_account: Address,
+		names: SolidityBoundedVec<BoundedBytes<GetBytesLimit>, GetArrayLimit>,
+	) -> EvmResult<Vec<EVMAttribute>> {
+		let names: Vec<_> = names.into();
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(names.len() as u64),
+		)?;
+
+		let did_account = Runtime::AddressMapping::into_account_id(did_account.into());
+		Ok(Self::read_all(&did_account, names).map(Self::evm_attribute).collect())
+	}
+
+	/// Whether a DID has the attribute and it has not expired at the current block.
+	#[precompile::public("isValid(address,bytes)")]
+	#[precompile::public("is_valid(address,bytes)")]
+	#[precompile::view]
+	fn is_valid(
+		handle: &mut impl PrecompileHandle,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let did_account = Runtime::AddressMapping::into_account_id(did_account.into());
+		let now = frame_system::Pallet::<Runtime>::block_number().into();
+
+		Ok(peaq_pallet_did::Pallet::<Runtime>::read(&did_account, &Vec::<u8>::from(name))
+			.map_or(false, |v| did_document::is_valid(v.validity.into(), now)))
+	}
+
+	/// Assembles the W3C-style JSON document of a DID from its valid attributes among the given
+	/// names.
+	#[precompile::public("readDocument(address,bytes[])")]
+	#[precompile::public("read_document(address,bytes[])")]
+	#[precompile::view]
+	fn read_document(
+		handle: &mut impl PrecompileHandle,
+		did_account: Address,
+		names: SolidityBoundedVec<BoundedBytes<GetBytesLimit>, GetArrayLimit>,
+	) -> EvmResult<UnboundedBytes> {
+		let names: Vec<_> = names.into();
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(names.len() as u64),
+		)?;
+
+		let did_account = Runtime::AddressMapping::into_account_id(did_account.into());
+		let now = frame_system::Pallet::<Runtime>::block_number().into();
+		let attributes: Vec<_> = Self::read_all(&did_account, names)
+			.filter(|v| did_document::is_valid(v.validity.into(), now))
+			.collect();
+
+		Ok(did_document::document(
+			&did_account.encode(),
+			attributes.iter().map(|v| DocumentAttribute {
+				name: &v.name,
+				value: &v.value,
 				validity: v.validity.into(),
-				created: v.created.into(),
+				created: v.created.unique_saturated_into(),
 			}),
-			None => Err(Revert::new(RevertReason::custom("Cannot find the item")).into()),
+		)
+		.into())
+	}
+
+	fn read_all(
+		did_account: &AccountIdOf<Runtime>,
+		names: Vec<BoundedBytes<GetBytesLimit>>,
+	) -> impl Iterator<Item = Attribute<BlockNumberFor<Runtime>, MomentOf<Runtime>>> + '_ {
+		names.into_iter().filter_map(move |name| {
+			peaq_pallet_did::Pallet::<Runtime>::read(did_account, &Vec::<u8>::from(name))
+		})
+	}
+
+	fn evm_attribute(v: Attribute<BlockNumberFor<Runtime>, MomentOf<Runtime>>) -> EVMAttribute {
+		EVMAttribute {
+			name: v.name.to_vec().into(),
+			value: v.value.to_vec().into(),
+			validity: v.validity.into(),
+			created: v.created.into(),
 		}
 	}
 
@@ -101,11 +196,157 @@ where
 		name: BoundedBytes<GetBytesLimit>,
 		value: BoundedBytes<GetBytesLimit>,
 		valid_for: u32,
+	) -> EvmResult<bool> {
+		let caller = handle.context().caller;
+		Self::do_add_attribute(handle, caller, did_account, name, value, valid_for)
+	}
+
+	#[precompile::public("updateAttribute(address,bytes,bytes,uint32)")]
+	#[precompile::public("update_attribute(address,bytes,bytes,uint32)")]
+	fn update_attribute(
+		handle: &mut impl PrecompileHandle,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+		value: BoundedBytes<GetBytesLimit>,
+		valid_for: u32,
+	) -> EvmResult<bool> {
+		let caller = handle.context().caller;
+		Self::do_update_attribute(handle, caller, did_account, name, value, valid_for)
+	}
+
+	#[precompile::public("removeAttribute(address,bytes)")]
+	#[precompile::public("remove_attribute(address,bytes)")]
+	fn remove_attribute(
+		handle: &mut impl PrecompileHandle,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+	) -> EvmResult<bool> {
+		let caller = handle.context().caller;
+		Self::do_remove_attribute(handle, caller, did_account, name)
+	}
+
+	/// Adds an attribute on behalf of `owner`, who signed the write with EIP-712 so that a
+	/// relayer can send it and pay its gas.
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public(
+		"addAttributeWithSignature(address,address,bytes,bytes,uint32,uint256,uint8,bytes32,bytes32)"
+	)]
+	fn add_attribute_with_signature(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+		value: BoundedBytes<GetBytesLimit>,
+		valid_for: u32,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<bool> {
+		let address = handle.context().address;
+		Signatures::<Runtime>::use_signature(handle, owner.into(), deadline, v, r, s, |nonce| {
+			Eip712::<Runtime>::add_attribute_digest(
+				address,
+				owner.into(),
+				did_account.into(),
+				name.as_bytes(),
+				value.as_bytes(),
+				valid_for,
+				nonce,
+				deadline,
+			)
+		})?;
+
+		Self::do_add_attribute(handle, owner.into(), did_account, name, value, valid_for)
+	}
+
+	/// Updates an attribute on behalf of `owner`, who signed the write with EIP-712.
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public(
+		"updateAttributeWithSignature(address,address,bytes,bytes,uint32,uint256,uint8,bytes32,bytes32)"
+	)]
+	fn update_attribute_with_signature(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+		value: BoundedBytes<GetBytesLimit>,
+		valid_for: u32,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<bool> {
+		let address = handle.context().address;
+		Signatures::<Runtime>::use_signature(handle, owner.into(), deadline, v, r, s, |nonce| {
+			Eip712::<Runtime>::update_attribute_digest(
+				address,
+				owner.into(),
+				did_account.into(),
+				name.as_bytes(),
+				value.as_bytes(),
+				valid_for,
+				nonce,
+				deadline,
+			)
+		})?;
+
+		Self::do_update_attribute(handle, owner.into(), did_account, name, value, valid_for)
+	}
+
+	/// Removes an attribute on behalf of `owner`, who signed the write with EIP-712.
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public(
+		"removeAttributeWithSignature(address,address,bytes,uint256,uint8,bytes32,bytes32)"
+	)]
+	fn remove_attribute_with_signature(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<bool> {
+		let address = handle.context().address;
+		Signatures::<Runtime>::use_signature(handle, owner.into(), deadline, v, r, s, |nonce| {
+			Eip712::<Runtime>::remove_attribute_digest(
+				address,
+				owner.into(),
+				did_account.into(),
+				name.as_bytes(),
+				nonce,
+				deadline,
+			)
+		})?;
+
+		Self::do_remove_attribute(handle, owner.into(), did_account, name)
+	}
+
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		Signatures::<Runtime>::nonces(handle, owner)
+	}
+
+	#[precompile::public("DOMAIN_SEPARATOR()")]
+	#[precompile::view]
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		Signatures::<Runtime>::domain_separator(handle)
+	}
+
+	fn do_add_attribute(
+		handle: &mut impl PrecompileHandle,
+		sender: H160,
+		did_account: Address,
+		name: BoundedBytes<GetBytesLimit>,
+		value: BoundedBytes<GetBytesLimit>,
+		valid_for: u32,
 	) -> EvmResult<bool> {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
 
-		let caller: AccountIdOf<Runtime> =
-			Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let caller: AccountIdOf<Runtime> = Runtime::AddressMapping::into_account_id(sender);
 
 		let did_account_addr = Runtime::AddressMapping::into_account_id(did_account.into());
 		let valid_for_opt: Option<BlockNumberFor<Runtime>> = match valid_for {
@@ -134,7 +375,7 @@ where
 			handle.context().address,
 			SELECTOR_LOG_ADD_ATTRIBUTE,
 			solidity::encode_event_data((
-				Address::from(handle.context().caller),
+				Address::from(sender),
 				did_account,
 				name,
 				value,
@@ -146,10 +387,9 @@ where
 		Ok(true)
 	}
 
-	#[precompile::public("updateAttribute(address,bytes,bytes,uint32)")]
-	#[precompile::public("update_attribute(address,bytes,bytes,uint32)")]
-	fn update_attribute(
+	fn do_update_attribute(
 		handle: &mut impl PrecompileHandle,
+		sender: H160,
 		did_account: Address,
 		name: BoundedBytes<GetBytesLimit>,
 		value: BoundedBytes<GetBytesLimit>,
@@ -157,8 +397,7 @@ where
 	) -> EvmResult<bool> {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
 
-		let caller: AccountIdOf<Runtime> =
-			Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let caller: AccountIdOf<Runtime> = Runtime::AddressMapping::into_account_id(sender);
 
 		let did_account_addr = Runtime::AddressMapping::into_account_id(did_account.into());
 		let valid_for_opt: Option<BlockNumberFor<Runtime>> = match valid_for {
@@ -186,7 +425,7 @@ where
 			handle.context().address,
 			SELECTOR_LOG_UPDATE_ATTRIBUTE,
 			solidity::encode_event_data((
-				Address::from(handle.context().caller),
+				Address::from(sender),
 				did_account,
 				name,
 				value,
@@ -198,17 +437,15 @@ where
 		Ok(true)
 	}
 
-	#[precompile::public("removeAttribute(address,bytes)")]
-	#[precompile::public("remove_attribute(address,bytes)")]
-	fn remove_attribute(
+	fn do_remove_attribute(
 		handle: &mut impl PrecompileHandle,
+		sender: H160,
 		did_account: Address,
 		name: BoundedBytes<GetBytesLimit>,
 	) -> EvmResult<bool> {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
 
-		let caller: AccountIdOf<Runtime> =
-			Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let caller: AccountIdOf<Runtime> = Runtime::AddressMapping::into_account_id(sender);
 
 		let name_vec = BoundedVec::<u8, MaxNameSize>::try_from(name.as_bytes().to_vec())
 			.map_err(|_| Revert::new(RevertReason::custom("Name too long")))?;
@@ -0,0 +1,26 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Test utilities
+use super::*;
+
+precompile_utils::mock_peaq_runtime! {
+	pallets: { PeaqDid: peaq_pallet_did },
+	precompile: PeaqDIDPrecompile,
+	call: PeaqDIDPrecompileCall,
+}
+
+parameter_types! {
+	pub const DidStorageDepositBase: Balance = 10;
+	pub const DidStorageDepositPerByte: Balance = 0;
+	pub const DIDReserveIdentifier: [u8; 8] = *b"peaq_did";
+}
+
+impl peaq_pallet_did::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Time = Timestamp;
+	type WeightInfo = peaq_pallet_did::weights::WeightInfo<Runtime>;
+	type Currency = Balances;
+	type StorageDepositBase = DidStorageDepositBase;
+	type StorageDepositPerByte = DidStorageDepositPerByte;
+	type ReserveIdentifier = DIDReserveIdentifier;
+}
@@ -0,0 +1,399 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use libsecp256k1::{sign, Message, SecretKey};
+use precompile_utils::testing::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn funded() -> ExtBuilder {
+	ExtBuilder::default().with_balances(vec![(CryptoAlith.into(), 1000), (Bob.into(), 1000)])
+}
+
+fn sign_digest(digest: [u8; 32]) -> (u8, H256, H256) {
+	let secret_key = SecretKey::parse(&alith_secret_key()).unwrap();
+	let (rs, v) = sign(&Message::parse(&digest), &secret_key);
+	(v.serialize(), rs.r.b32().into(), rs.s.b32().into())
+}
+
+fn signed_add_attribute(nonce: U256, deadline: U256) -> PCall {
+	let (v, r, s) = sign_digest(Eip712::<Runtime>::add_attribute_digest(
+		Precompile1.into(),
+		CryptoAlith.into(),
+		CryptoAlith.into(),
+		b"name",
+		b"value",
+		0,
+		nonce,
+		deadline,
+	));
+
+	PCall::add_attribute_with_signature {
+		owner: Address(CryptoAlith.into()),
+		did_account: Address(CryptoAlith.into()),
+		name: b"name".into(),
+		value: b"value".into(),
+		valid_for: 0,
+		deadline,
+		v,
+		r,
+		s,
+	}
+}
+
+fn add_attribute(name: &[u8], valid_for: u32) {
+	precompiles()
+		.prepare_test(
+			CryptoAlith,
+			Precompile1,
+			PCall::add_attribute {
+				did_account: Address(CryptoAlith.into()),
+				name: name.into(),
+				value: b"value".into(),
+				valid_for,
+			},
+		)
+		.execute_returns(true);
+}
+
+fn attribute(name: &[u8]) -> EVMAttribute {
+	PeaqDIDPrecompile::<Runtime>::evm_attribute(
+		PeaqDid::read(&CryptoAlith.into(), &name.to_vec()).unwrap(),
+	)
+}
+
+fn has_attribute(name: &[u8]) -> bool {
+	PeaqDid::read(&CryptoAlith.into(), &name.to_vec()).is_some()
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::read_attribute_selectors().contains(&0xb2028b7d));
+	assert!(PCall::read_attributes_selectors().contains(&0x7c7878dc));
+	assert!(PCall::is_valid_selectors().contains(&0xee88d8fc));
+	assert!(PCall::read_document_selectors().contains(&0xca5e7258));
+	assert!(PCall::add_attribute_selectors().contains(&0xcc4a70ca));
+	assert!(PCall::update_attribute_selectors().contains(&0x68b4b2c1));
+	assert!(PCall::remove_attribute_selectors().contains(&0xe8a81690));
+	assert!(PCall::add_attribute_with_signature_selectors().contains(&0xf8a9a661));
+	assert!(PCall::update_attribute_with_signature_selectors().contains(&0xc1afdc76));
+	assert!(PCall::remove_attribute_with_signature_selectors().contains(&0xc12c3423));
+	assert!(PCall::nonces_selectors().contains(&0x7ecebe00));
+	assert!(PCall::domain_separator_selectors().contains(&0x3644e515));
+}
+
+#[test]
+fn modifiers() {
+	funded().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Bob, Precompile1);
+
+		tester.test_view_modifier(PCall::read_attribute_selectors());
+		tester.test_view_modifier(PCall::read_attributes_selectors());
+		tester.test_view_modifier(PCall::is_valid_selectors());
+		tester.test_view_modifier(PCall::read_document_selectors());
+		tester.test_default_modifier(PCall::add_attribute_with_signature_selectors());
+		tester.test_default_modifier(PCall::update_attribute_with_signature_selectors());
+		tester.test_default_modifier(PCall::remove_attribute_with_signature_selectors());
+		tester.test_view_modifier(PCall::nonces_selectors());
+		tester.test_view_modifier(PCall::domain_separator_selectors());
+	});
+}
+
+#[test]
+fn add_attribute_with_signature() {
+	funded().build().execute_with(|| {
+		// Bob relays the write signed by the owner of the DID.
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_attribute(U256::zero(), U256::zero()))
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_LOG_ADD_ATTRIBUTE,
+				solidity::encode_event_data((
+					Address(CryptoAlith.into()),
+					Address(CryptoAlith.into()),
+					BoundedBytes::<GetBytesLimit>::from(b"name"),
+					BoundedBytes::<GetBytesLimit>::from(b"value"),
+					0u32,
+				)),
+			))
+			.execute_returns(true);
+
+		assert!(has_attribute(b"name"));
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, PCall::nonces { owner: Address(CryptoAlith.into()) })
+			.expect_no_logs()
+			.execute_returns(U256::one());
+	});
+}
+
+#[test]
+fn signature_cannot_be_replayed() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_attribute(U256::zero(), U256::zero()))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_attribute(U256::zero(), U256::zero()))
+			.execute_reverts(|output| output == b"Invalid signature");
+	});
+}
+
+#[test]
+fn expired_signature() {
+	funded().build().execute_with(|| {
+		Timestamp::set_timestamp(10_000);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_attribute(U256::zero(), U256::from(9)))
+			.execute_reverts(|output| output == b"Signature expired");
+
+		assert!(!has_attribute(b"name"));
+	});
+}
+
+#[test]
+fn signature_of_another_owner() {
+	funded().build().execute_with(|| {
+		let (v, r, s) = sign_digest(Eip712::<Runtime>::add_attribute_digest(
+			Precompile1.into(),
+			Bob.into(),
+			Bob.into(),
+			b"name",
+			b"value",
+			0,
+			U256::zero(),
+			U256::zero(),
+		));
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::add_attribute_with_signature {
+					owner: Address(Bob.into()),
+					did_account: Address(Bob.into()),
+					name: b"name".into(),
+					value: b"value".into(),
+					valid_for: 0,
+					deadline: U256::zero(),
+					v,
+					r,
+					s,
+				},
+			)
+			.execute_reverts(|output| output == b"Invalid signature");
+	});
+}
+
+#[test]
+fn update_and_remove_attribute_with_signature() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_attribute(U256::zero(), U256::zero()))
+			.execute_returns(true);
+
+		let (v, r, s) = sign_digest(Eip712::<Runtime>::update_attribute_digest(
+			Precompile1.into(),
+			CryptoAlith.into(),
+			CryptoAlith.into(),
+			b"name",
+			b"other",
+			0,
+			U256::one(),
+			U256::zero(),
+		));
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::update_attribute_with_signature {
+					owner: Address(CryptoAlith.into()),
+					did_account: Address(CryptoAlith.into()),
+					name: b"name".into(),
+					value: b"other".into(),
+					valid_for: 0,
+					deadline: U256::zero(),
+					v,
+					r,
+					s,
+				},
+			)
+			.execute_returns(true);
+
+		let (v, r, s) = sign_digest(Eip712::<Runtime>::remove_attribute_digest(
+			Precompile1.into(),
+			CryptoAlith.into(),
+			CryptoAlith.into(),
+			b"name",
+			U256::from(2),
+			U256::zero(),
+		));
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::remove_attribute_with_signature {
+					owner: Address(CryptoAlith.into()),
+					did_account: Address(CryptoAlith.into()),
+					name: b"name".into(),
+					deadline: U256::zero(),
+					v,
+					r,
+					s,
+				},
+			)
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_LOG_REMOVE_ATTRIBUTE,
+				solidity::encode_event_data((
+					Address(CryptoAlith.into()),
+					BoundedBytes::<GetBytesLimit>::from(b"name"),
+				)),
+			))
+			.execute_returns(true);
+
+		assert!(!has_attribute(b"name"));
+		assert_eq!(NoncesStorage::get(H160::from(CryptoAlith)), U256::from(3));
+	});
+}
+
+#[test]
+fn domain_separator() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Bob, Precompile1, PCall::domain_separator {})
+			.expect_no_logs()
+			.execute_returns(H256::from(Signatures::<Runtime>::compute_domain_separator(
+				Precompile1.into(),
+			)));
+	});
+}
+
+#[test]
+fn is_valid() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::is_valid { did_account: Address(CryptoAlith.into()), name: b"name".into() },
+			)
+			.expect_no_logs()
+			.execute_returns(false);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_attribute(U256::zero(), U256::zero()))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::is_valid { did_account: Address(CryptoAlith.into()), name: b"name".into() },
+			)
+			.expect_no_logs()
+			.execute_returns(true);
+	});
+}
+
+#[test]
+fn is_valid_expires_with_the_attribute() {
+	funded().build().execute_with(|| {
+		add_attribute(b"name", 5);
+		let validity = attribute(b"name").validity;
+
+		System::set_block_number(validity);
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::is_valid { did_account: Address(CryptoAlith.into()), name: b"name".into() },
+			)
+			.expect_no_logs()
+			.execute_returns(true);
+
+		System::set_block_number(validity + 1);
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::is_valid { did_account: Address(CryptoAlith.into()), name: b"name".into() },
+			)
+			.expect_no_logs()
+			.execute_returns(false);
+	});
+}
+
+#[test]
+fn read_attributes_skips_missing_names() {
+	funded().build().execute_with(|| {
+		add_attribute(b"first", 0);
+		add_attribute(b"second", 0);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::read_attributes {
+					did_account: Address(CryptoAlith.into()),
+					names: vec![
+						BoundedBytes::from(b"second"),
+						BoundedBytes::from(b"missing"),
+						BoundedBytes::from(b"first"),
+					]
+					.into(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(vec![attribute(b"second"), attribute(b"first")]);
+	});
+}
+
+#[test]
+fn read_document_lists_the_valid_attributes() {
+	funded().build().execute_with(|| {
+		add_attribute(b"endpoint", 0);
+		add_attribute(b"expiring", 1);
+
+		let expected = |names: &[&[u8]]| {
+			let attributes: Vec<_> = names
+				.iter()
+				.map(|name| PeaqDid::read(&CryptoAlith.into(), &name.to_vec()).unwrap())
+				.collect();
+			UnboundedBytes::from(did_document::document(
+				&AccountId::from(CryptoAlith).encode(),
+				attributes.iter().map(|v| DocumentAttribute {
+					name: &v.name,
+					value: &v.value,
+					validity: v.validity,
+					created: v.created.into(),
+				}),
+			))
+		};
+		let read_document = || PCall::read_document {
+			did_account: Address(CryptoAlith.into()),
+			names: vec![
+				BoundedBytes::from(b"endpoint"),
+				BoundedBytes::from(b"expiring"),
+				BoundedBytes::from(b"missing"),
+			]
+			.into(),
+		};
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, read_document())
+			.expect_no_logs()
+			.execute_returns(expected(&[b"endpoint", b"expiring"]));
+
+		System::set_block_number(attribute(b"expiring").validity + 1);
+		precompiles()
+			.prepare_test(Bob, Precompile1, read_document())
+			.expect_no_logs()
+			.execute_returns(expected(&[b"endpoint"]));
+	});
+}
@@ -32,6 +32,11 @@ interface RBAC {
         bytes32 group;
     }
 
+    struct Role2Group {
+        bytes32 role;
+        bytes32 group;
+    }
+
     // ======================= Entry Points ======================= //
 
     function fetchRole(
@@ -163,6 +168,107 @@ interface RBAC {
         bytes32 group_id
     ) external view returns (Entity[] memory);
 
+    // ======================= Paged Queries ======================= //
+    // Each query skips the first `offset` entries and returns at most `limit` of them.
+    // Paging only bounds the returned data, not the gas: the RBAC pallet loads every entry of
+    // the owner (or of the user, role or group) before the page is built, and one storage read
+    // is charged per loaded entry, whatever the offset and limit.
+
+    function fetchRolesPaged(
+        address owner,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchPermissionsPaged(
+        address owner,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchGroupsPaged(
+        address owner,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchUserRolesPaged(
+        address owner,
+        bytes32 user_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Role2User[] memory);
+
+    function fetchUserGroupsPaged(
+        address owner,
+        bytes32 user_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (User2Group[] memory);
+
+    function fetchUserPermissionsPaged(
+        address owner,
+        bytes32 user_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchRolePermissionsPaged(
+        address owner,
+        bytes32 role_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Permission2Role[] memory);
+
+    function fetchGroupRolesPaged(
+        address owner,
+        bytes32 group_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Role2Group[] memory);
+
+    function fetchGroupPermissionsPaged(
+        address owner,
+        bytes32 group_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    // Same as the paged queries, leaving out the disabled entities before paging.
+
+    function fetchEnabledRoles(
+        address owner,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchEnabledPermissions(
+        address owner,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchEnabledGroups(
+        address owner,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    function fetchEnabledUserPermissions(
+        address owner,
+        bytes32 user_id,
+        uint32 offset,
+        uint32 limit
+    ) external view returns (Entity[] memory);
+
+    // Whether the permission is enabled and granted to the user by one of its enabled roles,
+    // either directly or through one of its enabled groups.
+    function hasPermission(
+        address owner,
+        bytes32 user_id,
+        bytes32 permission_id
+    ) external view returns (bool);
+
     // ======================= Events ======================= //
 
     event RoleAdded(address sender, bytes32 role_id, bytes name);
@@ -11,7 +11,8 @@ use frame_support::{
 };
 use peaq_pallet_rbac::{
 	error::{RbacError, RbacErrorType},
-	rbac::{Rbac, Role},
+	rbac::{Rbac, Result as RbacResult, Role},
+	structs::Entity as RbacEntity,
 };
 use sp_core::{Decode, H256};
 use sp_runtime::traits::Dispatchable;
@@ -31,6 +32,11 @@ pub use structs::*;
 pub mod selectors;
 pub use selectors::*;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 type EntityIdOf<Runtime> = <Runtime as peaq_pallet_rbac::Config>::EntityId;
 
 type GetBytesLimit = ConstU32<{ 2u32.pow(16) }>;
@@ -886,4 +892,415 @@ where
 
 		result
 	}
+
+	#[precompile::public("fetchRolesPaged(address,uint32,uint32)")]
+	#[precompile::public("fetch_roles_paged(address,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_roles_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_roles(&owner),
+			|_| true,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchPermissionsPaged(address,uint32,uint32)")]
+	#[precompile::public("fetch_permissions_paged(address,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_permissions_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_permissions(&owner),
+			|_| true,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchGroupsPaged(address,uint32,uint32)")]
+	#[precompile::public("fetch_groups_paged(address,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_groups_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_groups(&owner),
+			|_| true,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchUserRolesPaged(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_user_roles_paged(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_user_roles_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		user_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Role2User>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let user_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(user_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_user_roles(&owner, user_id),
+			|_| true,
+			|val| Role2User { role: val.role.into(), user: val.user.into() },
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchUserGroupsPaged(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_user_groups_paged(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_user_groups_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		user_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<User2Group>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let user_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(user_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_user_groups(&owner, user_id),
+			|_| true,
+			|val| User2Group { user: val.user.into(), group: val.group.into() },
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchUserPermissionsPaged(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_user_permissions_paged(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_user_permissions_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		user_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let user_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(user_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_user_permissions(&owner, user_id),
+			|_| true,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchRolePermissionsPaged(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_role_permissions_paged(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_role_permissions_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		role_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Permission2Role>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let role_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(role_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_role_permissions(&owner, role_id),
+			|_| true,
+			|val| Permission2Role { permission: val.permission.into(), role: val.role.into() },
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchGroupRolesPaged(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_group_roles_paged(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_group_roles_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		group_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Role2Group>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let group_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(group_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_group_roles(&owner, group_id),
+			|_| true,
+			|val| Role2Group { role: val.role.into(), group: val.group.into() },
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchGroupPermissionsPaged(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_group_permissions_paged(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_group_permissions_paged(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		group_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let group_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(group_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_group_permissions(&owner, group_id),
+			|_| true,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchEnabledRoles(address,uint32,uint32)")]
+	#[precompile::public("fetch_enabled_roles(address,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_enabled_roles(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_roles(&owner),
+			Self::is_enabled_entity,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchEnabledPermissions(address,uint32,uint32)")]
+	#[precompile::public("fetch_enabled_permissions(address,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_enabled_permissions(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_permissions(&owner),
+			Self::is_enabled_entity,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchEnabledGroups(address,uint32,uint32)")]
+	#[precompile::public("fetch_enabled_groups(address,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_enabled_groups(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_groups(&owner),
+			Self::is_enabled_entity,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	#[precompile::public("fetchEnabledUserPermissions(address,bytes32,uint32,uint32)")]
+	#[precompile::public("fetch_enabled_user_permissions(address,bytes32,uint32,uint32)")]
+	#[precompile::view]
+	fn fetch_enabled_user_permissions(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		user_id: H256,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Entity>> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let user_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(user_id.to_fixed_bytes());
+
+		Self::paged(
+			handle,
+			peaq_pallet_rbac::Pallet::<Runtime>::get_user_permissions(&owner, user_id),
+			Self::is_enabled_entity,
+			Self::entity,
+			offset,
+			limit,
+		)
+	}
+
+	/// Checks whether a user is granted an enabled permission, either through one of its roles
+	/// or through the roles of one of its groups. Disabled roles and groups grant nothing.
+	#[precompile::public("hasPermission(address,bytes32,bytes32)")]
+	#[precompile::public("has_permission(address,bytes32,bytes32)")]
+	#[precompile::view]
+	fn has_permission(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		user_id: H256,
+		permission_id: H256,
+	) -> EvmResult<bool> {
+		let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner.into());
+		let user_id: EntityIdOf<Runtime> = EntityIdOf::<Runtime>::from(user_id.to_fixed_bytes());
+		let permission_id: EntityIdOf<Runtime> =
+			EntityIdOf::<Runtime>::from(permission_id.to_fixed_bytes());
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		if !Self::is_enabled(peaq_pallet_rbac::Pallet::<Runtime>::get_permission(
+			&owner,
+			permission_id,
+		)) {
+			return Ok(false);
+		}
+
+		// Roles assigned to the user directly
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let mut role_ids: Vec<EntityIdOf<Runtime>> =
+			peaq_pallet_rbac::Pallet::<Runtime>::get_user_roles(&owner, user_id)
+				.unwrap_or_default()
+				.iter()
+				.map(|val| val.role)
+				.collect();
+
+		// Roles assigned to the enabled groups of the user
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let user_groups = peaq_pallet_rbac::Pallet::<Runtime>::get_user_groups(&owner, user_id)
+			.unwrap_or_default();
+		for user_group in user_groups.iter() {
+			handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+			if !Self::is_enabled(peaq_pallet_rbac::Pallet::<Runtime>::get_group(
+				&owner,
+				user_group.group,
+			)) {
+				continue;
+			}
+			role_ids.extend(
+				peaq_pallet_rbac::Pallet::<Runtime>::get_group_roles(&owner, user_group.group)
+					.unwrap_or_default()
+					.iter()
+					.map(|val| val.role),
+			);
+		}
+
+		for role_id in role_ids {
+			handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+			if !Self::is_enabled(peaq_pallet_rbac::Pallet::<Runtime>::get_role(&owner, role_id)) {
+				continue;
+			}
+			let granted =
+				peaq_pallet_rbac::Pallet::<Runtime>::get_role_permissions(&owner, role_id)
+					.unwrap_or_default()
+					.iter()
+					.any(|val| val.permission == permission_id);
+			if granted {
+				return Ok(true);
+			}
+		}
+
+		Ok(false)
+	}
+
+	fn is_enabled(entity: RbacResult<RbacEntity<EntityIdOf<Runtime>>>) -> bool {
+		matches!(entity, Ok(entity) if entity.enabled)
+	}
+
+	fn is_enabled_entity(entity: &RbacEntity<EntityIdOf<Runtime>>) -> bool {
+		entity.enabled
+	}
+
+	fn entity(entity: &RbacEntity<EntityIdOf<Runtime>>) -> Entity {
+		Entity { id: entity.id.into(), name: entity.name.to_vec().into(), enabled: entity.enabled }
+	}
+
+	/// Returns at most `limit` of the entries matching `filter`, skipping the first `offset`
+	/// ones.
+	///
+	/// The pallet getters load every entry listed in the owner index, and its storage layout is
+	/// private, so the entries cannot be read page by page: one read is charged per loaded entry,
+	/// whatever the `offset` and `limit`. Paging bounds the output, not the cost.
+	fn paged<Item, Output>(
+		handle: &mut impl PrecompileHandle,
+		result: RbacResult<Vec<Item>>,
+		filter: impl Fn(&Item) -> bool,
+		convert: impl Fn(&Item) -> Output,
+		offset: u32,
+		limit: u32,
+	) -> EvmResult<Vec<Output>> {
+		let items = result.map_err(|e| Revert::new(RevertReason::custom(err2str(&e))))?;
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(items.len() as u64),
+		)?;
+
+		Ok(items
+			.iter()
+			.filter(|item| filter(item))
+			.skip(offset as usize)
+			.take(limit as usize)
+			.map(convert)
+			.collect())
+	}
 }
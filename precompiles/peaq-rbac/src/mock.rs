@@ -0,0 +1,28 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Test utilities
+use super::*;
+use frame_support::traits::ConstU32;
+
+precompile_utils::mock_peaq_runtime! {
+	pallets: { PeaqRbac: peaq_pallet_rbac },
+	precompile: PeaqRbacPrecompile,
+	call: PeaqRbacPrecompileCall,
+}
+
+parameter_types! {
+	pub const StorageDepositBase: Balance = 10;
+	pub const StorageDepositPerByte: Balance = 1;
+	pub const RbacReserveIdentifier: [u8; 8] = *b"peaqrbac";
+}
+
+impl peaq_pallet_rbac::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type EntityId = RbacEntityId;
+	type BoundedDataLen = ConstU32<256>;
+	type WeightInfo = peaq_pallet_rbac::weights::WeightInfo<Runtime>;
+	type Currency = Balances;
+	type StorageDepositBase = StorageDepositBase;
+	type StorageDepositPerByte = StorageDepositPerByte;
+	type ReserveIdentifier = RbacReserveIdentifier;
+}
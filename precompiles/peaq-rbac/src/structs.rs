@@ -4,7 +4,7 @@ use precompile_utils::{
 };
 use sp_core::H256;
 
-#[derive(Default, Debug, Codec)]
+#[derive(Clone, Default, Debug, Codec)]
 pub struct Entity {
 	pub id: H256,
 	pub name: UnboundedBytes,
@@ -0,0 +1,165 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use precompile_utils::testing::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn funded() -> ExtBuilder {
+	ExtBuilder::default().with_balances(vec![(Alice.into(), 100_000)])
+}
+
+fn id(n: u8) -> H256 {
+	H256::repeat_byte(n)
+}
+
+fn dispatch(call: PCall) {
+	precompiles().prepare_test(Alice, Precompile1, call).execute_returns(true);
+}
+
+fn add_roles(ids: &[u8]) {
+	for n in ids {
+		dispatch(PCall::add_role { role_id: id(*n), name: b"role".into() });
+	}
+}
+
+fn roles() -> Vec<Entity> {
+	PeaqRbac::get_roles(&Alice.into())
+		.unwrap()
+		.iter()
+		.map(PeaqRbacPrecompile::<Runtime>::entity)
+		.collect()
+}
+
+fn has_permission(user: u8, permission: u8) -> PCall {
+	PCall::has_permission {
+		owner: Address(Alice.into()),
+		user_id: id(user),
+		permission_id: id(permission),
+	}
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::fetch_roles_paged_selectors().contains(&0xa8e67fca));
+	assert!(PCall::fetch_enabled_roles_selectors().contains(&0xa04ab2d8));
+	assert!(PCall::has_permission_selectors().contains(&0x448e7bf7));
+}
+
+#[test]
+fn fetch_roles_paged_respects_offset_and_limit() {
+	funded().build().execute_with(|| {
+		add_roles(&[1, 2, 3, 4]);
+		let roles = roles();
+
+		for (offset, limit, page) in [
+			(0, 2, &roles[0..2]),
+			(1, 2, &roles[1..3]),
+			(2, 10, &roles[2..4]),
+			(4, 1, &roles[4..4]),
+			(10, 1, &roles[4..4]),
+			(0, 0, &roles[0..0]),
+		] {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::fetch_roles_paged { owner: Address(Alice.into()), offset, limit },
+				)
+				.expect_no_logs()
+				.execute_returns(page.to_vec());
+		}
+	});
+}
+
+#[test]
+fn fetch_enabled_roles_skips_disabled_roles() {
+	funded().build().execute_with(|| {
+		add_roles(&[1, 2, 3]);
+		dispatch(PCall::disable_role { role_id: id(2) });
+
+		let enabled: Vec<Entity> = roles().into_iter().filter(|role| role.enabled).collect();
+		assert_eq!(enabled.len(), 2);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::fetch_enabled_roles { owner: Address(Alice.into()), offset: 0, limit: 10 },
+			)
+			.expect_no_logs()
+			.execute_returns(enabled.clone());
+
+		// The offset applies to the enabled roles only
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::fetch_enabled_roles { owner: Address(Alice.into()), offset: 1, limit: 10 },
+			)
+			.expect_no_logs()
+			.execute_returns(enabled[1..].to_vec());
+	});
+}
+
+#[test]
+fn has_permission_walks_user_and_group_roles() {
+	funded().build().execute_with(|| {
+		// Permission 10 granted through role 1, permission 11 through role 2 of group 20
+		add_roles(&[1, 2]);
+		dispatch(PCall::add_permission { permission_id: id(10), name: b"perm".into() });
+		dispatch(PCall::add_permission { permission_id: id(11), name: b"perm".into() });
+		dispatch(PCall::add_group { group_id: id(20), name: b"group".into() });
+		dispatch(PCall::assign_permission_to_role { permission_id: id(10), role_id: id(1) });
+		dispatch(PCall::assign_permission_to_role { permission_id: id(11), role_id: id(2) });
+		dispatch(PCall::assign_role_to_user { role_id: id(1), user_id: id(30) });
+		dispatch(PCall::assign_role_to_group { role_id: id(2), group_id: id(20) });
+		dispatch(PCall::assign_user_to_group { user_id: id(30), group_id: id(20) });
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 10))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 11))
+			.execute_returns(true);
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(31, 10))
+			.execute_returns(false);
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 12))
+			.execute_returns(false);
+
+		// A disabled group grants nothing
+		dispatch(PCall::disable_group { group_id: id(20) });
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 11))
+			.execute_returns(false);
+
+		// Neither does a disabled role
+		dispatch(PCall::disable_role { role_id: id(1) });
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 10))
+			.execute_returns(false);
+	});
+}
+
+#[test]
+fn has_permission_ignores_disabled_permissions() {
+	funded().build().execute_with(|| {
+		add_roles(&[1]);
+		dispatch(PCall::add_permission { permission_id: id(10), name: b"perm".into() });
+		dispatch(PCall::assign_permission_to_role { permission_id: id(10), role_id: id(1) });
+		dispatch(PCall::assign_role_to_user { role_id: id(1), user_id: id(30) });
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 10))
+			.execute_returns(true);
+
+		dispatch(PCall::disable_permission { permission_id: id(10) });
+		precompiles()
+			.prepare_test(Alice, Precompile1, has_permission(30, 10))
+			.execute_returns(false);
+	});
+}
@@ -0,0 +1,93 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! EIP-712 signatures allowing a relayer to write items on behalf of their owner.
+
+use super::*;
+use frame_support::traits::StorageInstance;
+use precompile_utils::eip712::Eip712Domain;
+use sp_core::{H160, H256};
+use sp_io::hashing::keccak_256;
+
+/// EIP-712 typehash of a signed `addItem`.
+pub const ADD_ITEM_TYPEHASH: [u8; 32] =
+	keccak256!("AddItem(address owner,bytes itemType,bytes item,uint256 nonce,uint256 deadline)");
+
+/// EIP-712 typehash of a signed `updateItem`.
+pub const UPDATE_ITEM_TYPEHASH: [u8; 32] = keccak256!(
+	"UpdateItem(address owner,bytes itemType,bytes item,uint256 nonce,uint256 deadline)"
+);
+
+pub struct Nonces;
+
+impl StorageInstance for Nonces {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"PeaqStoragePrecompile"
+	}
+}
+
+pub struct StorageDomain;
+
+impl Eip712Domain for StorageDomain {
+	const NAME: &'static str = "peaq Storage";
+
+	type Nonces = Nonces;
+}
+
+/// Storage type used to store the nonces of the signed writes, protecting against replays.
+pub type NoncesStorage = precompile_utils::eip712::NoncesStorage<Nonces>;
+
+/// Checks the signatures and serves the nonces and the domain separator of the precompile.
+pub type Signatures<Runtime> = precompile_utils::eip712::Eip712<Runtime, StorageDomain>;
+
+/// Digests of the typed writes of the precompile.
+pub struct Eip712<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Eip712<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	pub fn add_item_digest(
+		address: H160,
+		owner: H160,
+		item_type: &[u8],
+		item: &[u8],
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		Self::item_digest(ADD_ITEM_TYPEHASH, address, owner, item_type, item, nonce, deadline)
+	}
+
+	pub fn update_item_digest(
+		address: H160,
+		owner: H160,
+		item_type: &[u8],
+		item: &[u8],
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		Self::item_digest(UPDATE_ITEM_TYPEHASH, address, owner, item_type, item, nonce, deadline)
+	}
+
+	fn item_digest(
+		typehash: [u8; 32],
+		address: H160,
+		owner: H160,
+		item_type: &[u8],
+		item: &[u8],
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let struct_hash = keccak_256(&solidity::encode_arguments((
+			H256::from(typehash),
+			Address(owner),
+			H256::from(keccak_256(item_type)),
+			H256::from(keccak_256(item)),
+			nonce,
+			deadline,
+		)));
+
+		Signatures::<Runtime>::generate_digest(address, struct_hash)
+	}
+}
@@ -8,7 +8,7 @@ use frame_support::{
 	traits::ConstU32,
 	BoundedVec,
 };
-use sp_core::Decode;
+use sp_core::{Decode, H160, H256, U256};
 use sp_runtime::traits::Dispatchable;
 use sp_std::{marker::PhantomData, vec::Vec};
 
@@ -25,6 +25,14 @@ use precompile_utils::{
 	solidity, EvmResult,
 };
 
+mod eip712;
+pub use eip712::{Eip712, NoncesStorage, Signatures};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 type AccountIdOf<Runtime> = <Runtime as frame_system::Config>::AccountId;
 
 type GetBytesLimit = ConstU32<{ 2u32.pow(16) }>;
@@ -33,6 +41,8 @@ pub(crate) const SELECTOR_LOG_ITEM_ADDED: [u8; 32] = keccak256!("ItemAdded(addre
 pub(crate) const SELECTOR_LOG_ITEM_UPDATED: [u8; 32] =
 	keccak256!("ItemUpdated(address,bytes,bytes)");
 
+pub(crate) const SELECTOR_LOG_ITEM_REMOVED: [u8; 32] = keccak256!("ItemRemoved(address,bytes)");
+
 pub struct PeaqStoragePrecompile<Runtime>(PhantomData<Runtime>);
 
 #[precompile_utils::precompile]
@@ -45,6 +55,8 @@ where
 	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<AccountIdOf<Runtime>>>,
 	AccountIdOf<Runtime>: From<[u8; 32]> + AsRef<[u8]>,
 {
+	/// The storage pallet keys items by a hash of the owner and the item type, so the items of
+	/// an account cannot be enumerated and are read by type.
 	#[precompile::public("getItem(address,bytes)")]
 	#[precompile::public("get_item(address,bytes)")]
 	#[precompile::view]
@@ -56,7 +68,11 @@ where
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 		let account = Runtime::AddressMapping::into_account_id(account.into());
 		match peaq_pallet_storage::Pallet::<Runtime>::read(&account, &Vec::<u8>::from(item_type)) {
-			Some(v) => Ok(v.into()),
+			Some(v) => {
+				// The proof grows with the item.
+				handle.record_external_cost(None, Some(v.len() as u64), None)?;
+				Ok(v.into())
+			},
 			None => Err(Revert::new(RevertReason::custom("Cannot find the item")).into()),
 		}
 	}
@@ -67,17 +83,141 @@ where
 		handle: &mut impl PrecompileHandle,
 		item_type: BoundedBytes<GetBytesLimit>,
 		item: BoundedBytes<GetBytesLimit>,
+	) -> EvmResult<bool> {
+		let caller = handle.context().caller;
+		Self::do_add_item(handle, caller, item_type, item)
+	}
+
+	#[precompile::public("updateItem(bytes,bytes)")]
+	#[precompile::public("update_item(bytes,bytes)")]
+	fn update_item(
+		handle: &mut impl PrecompileHandle,
+		item_type: BoundedBytes<GetBytesLimit>,
+		item: BoundedBytes<GetBytesLimit>,
+	) -> EvmResult<bool> {
+		let caller = handle.context().caller;
+		Self::do_update_item(handle, caller, item_type, item)
+	}
+
+	/// Removes an item of the caller, refunding its storage deposit.
+	#[precompile::public("removeItem(bytes)")]
+	#[precompile::public("remove_item(bytes)")]
+	fn remove_item(
+		handle: &mut impl PrecompileHandle,
+		item_type: BoundedBytes<GetBytesLimit>,
 	) -> EvmResult<bool> {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
 
-		let caller: AccountIdOf<Runtime> =
-			Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let sender = handle.context().caller;
+		let caller: AccountIdOf<Runtime> = Runtime::AddressMapping::into_account_id(sender);
+		let item_type_bounded =
+			BoundedVec::<u8, <Runtime>::BoundedDataLen>::try_from(item_type.as_bytes().to_vec())
+				.map_err(|_| Revert::new(RevertReason::custom("Item type too long")))?;
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(caller).into(),
+			peaq_pallet_storage::Call::<Runtime>::remove_item { item_type: item_type_bounded },
+			0,
+		)?;
+
+		let event = log1(
+			handle.context().address,
+			SELECTOR_LOG_ITEM_REMOVED,
+			solidity::encode_event_data((Address::from(sender), item_type)),
+		);
+		event.record(handle)?;
+
+		Ok(true)
+	}
+
+	/// Adds an item on behalf of `owner`, who signed the write with EIP-712 so that a relayer
+	/// can send it and pay its gas.
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public("addItemWithSignature(address,bytes,bytes,uint256,uint8,bytes32,bytes32)")]
+	fn add_item_with_signature(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		item_type: BoundedBytes<GetBytesLimit>,
+		item: BoundedBytes<GetBytesLimit>,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<bool> {
+		let address = handle.context().address;
+		Signatures::<Runtime>::use_signature(handle, owner.into(), deadline, v, r, s, |nonce| {
+			Eip712::<Runtime>::add_item_digest(
+				address,
+				owner.into(),
+				item_type.as_bytes(),
+				item.as_bytes(),
+				nonce,
+				deadline,
+			)
+		})?;
+
+		Self::do_add_item(handle, owner.into(), item_type, item)
+	}
+
+	/// Updates an item on behalf of `owner`, who signed the write with EIP-712.
+	#[allow(clippy::too_many_arguments)]
+	#[precompile::public(
+		"updateItemWithSignature(address,bytes,bytes,uint256,uint8,bytes32,bytes32)"
+	)]
+	fn update_item_with_signature(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		item_type: BoundedBytes<GetBytesLimit>,
+		item: BoundedBytes<GetBytesLimit>,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<bool> {
+		let address = handle.context().address;
+		Signatures::<Runtime>::use_signature(handle, owner.into(), deadline, v, r, s, |nonce| {
+			Eip712::<Runtime>::update_item_digest(
+				address,
+				owner.into(),
+				item_type.as_bytes(),
+				item.as_bytes(),
+				nonce,
+				deadline,
+			)
+		})?;
+
+		Self::do_update_item(handle, owner.into(), item_type, item)
+	}
+
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		Signatures::<Runtime>::nonces(handle, owner)
+	}
+
+	#[precompile::public("DOMAIN_SEPARATOR()")]
+	#[precompile::view]
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		Signatures::<Runtime>::domain_separator(handle)
+	}
+
+	fn do_add_item(
+		handle: &mut impl PrecompileHandle,
+		sender: H160,
+		item_type: BoundedBytes<GetBytesLimit>,
+		item: BoundedBytes<GetBytesLimit>,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+		let caller: AccountIdOf<Runtime> = Runtime::AddressMapping::into_account_id(sender);
 		let item_type_bounded =
 			BoundedVec::<u8, <Runtime>::BoundedDataLen>::try_from(item_type.as_bytes().to_vec())
 				.map_err(|_| Revert::new(RevertReason::custom("Item type too long")))?;
 		let item_bounded =
 			BoundedVec::<u8, <Runtime>::BoundedDataLen>::try_from(item.as_bytes().to_vec())
 				.map_err(|_| Revert::new(RevertReason::custom("Item too long")))?;
+		let storage_growth = (item_type_bounded.len() + item_bounded.len()) as u64;
 
 		RuntimeHelper::<Runtime>::try_dispatch(
 			handle,
@@ -86,36 +226,40 @@ where
 				item_type: item_type_bounded,
 				item: item_bounded,
 			},
-			0,
+			storage_growth,
 		)?;
 
 		let event = log1(
 			handle.context().address,
 			SELECTOR_LOG_ITEM_ADDED,
-			solidity::encode_event_data((Address::from(handle.context().caller), item_type, item)),
+			solidity::encode_event_data((Address::from(sender), item_type, item)),
 		);
 		event.record(handle)?;
 
 		Ok(true)
 	}
 
-	#[precompile::public("updateItem(bytes,bytes)")]
-	#[precompile::public("update_item(bytes,bytes)")]
-	fn update_item(
+	fn do_update_item(
 		handle: &mut impl PrecompileHandle,
+		sender: H160,
 		item_type: BoundedBytes<GetBytesLimit>,
 		item: BoundedBytes<GetBytesLimit>,
 	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
 
-		let caller: AccountIdOf<Runtime> =
-			Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let caller: AccountIdOf<Runtime> = Runtime::AddressMapping::into_account_id(sender);
 		let item_type_bounded =
 			BoundedVec::<u8, <Runtime>::BoundedDataLen>::try_from(item_type.as_bytes().to_vec())
 				.map_err(|_| Revert::new(RevertReason::custom("Item type too long")))?;
 		let item_bounded =
 			BoundedVec::<u8, <Runtime>::BoundedDataLen>::try_from(item.as_bytes().to_vec())
 				.map_err(|_| Revert::new(RevertReason::custom("Item too long")))?;
+		// Only a longer item grows the storage.
+		let previous_len =
+			peaq_pallet_storage::Pallet::<Runtime>::read(&caller, &item_type_bounded)
+				.map_or(0, |previous| previous.len());
+		let storage_growth = item_bounded.len().saturating_sub(previous_len) as u64;
 
 		RuntimeHelper::<Runtime>::try_dispatch(
 			handle,
@@ -124,13 +268,13 @@ where
 				item_type: item_type_bounded,
 				item: item_bounded,
 			},
-			0,
+			storage_growth,
 		)?;
 
 		let event = log1(
 			handle.context().address,
 			SELECTOR_LOG_ITEM_UPDATED,
-			solidity::encode_event_data((Address::from(handle.context().caller), item_type, item)),
+			solidity::encode_event_data((Address::from(sender), item_type, item)),
 		);
 		event.record(handle)?;
 
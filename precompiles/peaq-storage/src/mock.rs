@@ -0,0 +1,27 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Test utilities
+use super::*;
+use frame_support::traits::ConstU32;
+
+precompile_utils::mock_peaq_runtime! {
+	pallets: { PeaqStorage: peaq_pallet_storage },
+	precompile: PeaqStoragePrecompile,
+	call: PeaqStoragePrecompileCall,
+}
+
+parameter_types! {
+	pub const StorageDepositBase: Balance = 10;
+	pub const StorageDepositPerByte: Balance = 1;
+	pub const StorageReserveIdentifier: [u8; 8] = *b"peaqstor";
+}
+
+impl peaq_pallet_storage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = peaq_pallet_storage::weights::WeightInfo<Runtime>;
+	type BoundedDataLen = ConstU32<256>;
+	type Currency = Balances;
+	type StorageDepositBase = StorageDepositBase;
+	type StorageDepositPerByte = StorageDepositPerByte;
+	type ReserveIdentifier = StorageReserveIdentifier;
+}
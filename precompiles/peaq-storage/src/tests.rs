@@ -0,0 +1,231 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use frame_support::traits::ReservableCurrency;
+use libsecp256k1::{sign, Message, SecretKey};
+use precompile_utils::testing::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn funded() -> ExtBuilder {
+	ExtBuilder::default().with_balances(vec![(CryptoAlith.into(), 1000), (Bob.into(), 1000)])
+}
+
+fn sign_digest(digest: [u8; 32]) -> (u8, H256, H256) {
+	let secret_key = SecretKey::parse(&alith_secret_key()).unwrap();
+	let (rs, v) = sign(&Message::parse(&digest), &secret_key);
+	(v.serialize(), rs.r.b32().into(), rs.s.b32().into())
+}
+
+fn signed_add_item(nonce: U256, deadline: U256) -> PCall {
+	let (v, r, s) = sign_digest(Eip712::<Runtime>::add_item_digest(
+		Precompile1.into(),
+		CryptoAlith.into(),
+		b"type",
+		b"item",
+		nonce,
+		deadline,
+	));
+
+	PCall::add_item_with_signature {
+		owner: Address(CryptoAlith.into()),
+		item_type: b"type".into(),
+		item: b"item".into(),
+		deadline,
+		v,
+		r,
+		s,
+	}
+}
+
+fn item() -> Option<Vec<u8>> {
+	PeaqStorage::read(&CryptoAlith.into(), &b"type".to_vec())
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::get_item_selectors().contains(&0x30878a43));
+	assert!(PCall::add_item_selectors().contains(&0x257c3c03));
+	assert!(PCall::update_item_selectors().contains(&0x1cd4bf09));
+	assert!(PCall::remove_item_selectors().contains(&0x2c2decc1));
+	assert!(PCall::add_item_with_signature_selectors().contains(&0xe525593e));
+	assert!(PCall::update_item_with_signature_selectors().contains(&0xb081cf53));
+	assert!(PCall::nonces_selectors().contains(&0x7ecebe00));
+	assert!(PCall::domain_separator_selectors().contains(&0x3644e515));
+}
+
+#[test]
+fn modifiers() {
+	funded().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Bob, Precompile1);
+
+		tester.test_default_modifier(PCall::remove_item_selectors());
+		tester.test_default_modifier(PCall::add_item_with_signature_selectors());
+		tester.test_default_modifier(PCall::update_item_with_signature_selectors());
+		tester.test_view_modifier(PCall::nonces_selectors());
+		tester.test_view_modifier(PCall::domain_separator_selectors());
+	});
+}
+
+#[test]
+fn add_item_with_signature() {
+	funded().build().execute_with(|| {
+		// Bob relays the write signed by the owner of the item.
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_item(U256::zero(), U256::zero()))
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_LOG_ITEM_ADDED,
+				solidity::encode_event_data((
+					Address(CryptoAlith.into()),
+					BoundedBytes::<GetBytesLimit>::from(b"type"),
+					BoundedBytes::<GetBytesLimit>::from(b"item"),
+				)),
+			))
+			.execute_returns(true);
+
+		assert_eq!(item(), Some(b"item".to_vec()));
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, PCall::nonces { owner: Address(CryptoAlith.into()) })
+			.expect_no_logs()
+			.execute_returns(U256::one());
+	});
+}
+
+#[test]
+fn signature_cannot_be_replayed() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_item(U256::zero(), U256::zero()))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_item(U256::zero(), U256::zero()))
+			.execute_reverts(|output| output == b"Invalid signature");
+	});
+}
+
+#[test]
+fn expired_signature() {
+	funded().build().execute_with(|| {
+		Timestamp::set_timestamp(10_000);
+
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_item(U256::zero(), U256::from(9)))
+			.execute_reverts(|output| output == b"Signature expired");
+
+		assert_eq!(item(), None);
+	});
+}
+
+#[test]
+fn signature_of_another_owner() {
+	funded().build().execute_with(|| {
+		let (v, r, s) = sign_digest(Eip712::<Runtime>::add_item_digest(
+			Precompile1.into(),
+			Bob.into(),
+			b"type",
+			b"item",
+			U256::zero(),
+			U256::zero(),
+		));
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::add_item_with_signature {
+					owner: Address(Bob.into()),
+					item_type: b"type".into(),
+					item: b"item".into(),
+					deadline: U256::zero(),
+					v,
+					r,
+					s,
+				},
+			)
+			.execute_reverts(|output| output == b"Invalid signature");
+	});
+}
+
+#[test]
+fn update_item_with_signature() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Bob, Precompile1, signed_add_item(U256::zero(), U256::zero()))
+			.execute_returns(true);
+
+		let (v, r, s) = sign_digest(Eip712::<Runtime>::update_item_digest(
+			Precompile1.into(),
+			CryptoAlith.into(),
+			b"type",
+			b"other",
+			U256::one(),
+			U256::zero(),
+		));
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::update_item_with_signature {
+					owner: Address(CryptoAlith.into()),
+					item_type: b"type".into(),
+					item: b"other".into(),
+					deadline: U256::zero(),
+					v,
+					r,
+					s,
+				},
+			)
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_LOG_ITEM_UPDATED,
+				solidity::encode_event_data((
+					Address(CryptoAlith.into()),
+					BoundedBytes::<GetBytesLimit>::from(b"type"),
+					BoundedBytes::<GetBytesLimit>::from(b"other"),
+				)),
+			))
+			.execute_returns(true);
+
+		assert_eq!(item(), Some(b"other".to_vec()));
+		assert_eq!(NoncesStorage::get(H160::from(CryptoAlith)), U256::from(2));
+	});
+}
+
+#[test]
+fn remove_item_refunds_deposit() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::add_item { item_type: b"type".into(), item: b"item".into() },
+			)
+			.execute_returns(true);
+		assert!(Balances::reserved_balance(AccountId::from(CryptoAlith)) > 0);
+
+		precompiles()
+			.prepare_test(
+				CryptoAlith,
+				Precompile1,
+				PCall::remove_item { item_type: b"type".into() },
+			)
+			.expect_log(log1(
+				Precompile1,
+				SELECTOR_LOG_ITEM_REMOVED,
+				solidity::encode_event_data((
+					Address(CryptoAlith.into()),
+					BoundedBytes::<GetBytesLimit>::from(b"type"),
+				)),
+			))
+			.execute_returns(true);
+
+		assert_eq!(item(), None);
+		assert_eq!(Balances::reserved_balance(AccountId::from(CryptoAlith)), 0);
+	});
+}
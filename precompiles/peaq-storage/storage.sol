@@ -8,6 +8,8 @@ Storage constant Storage_CONTRACT = Storage(PRECOMPILE_ADDR);
 
 interface Storage {
 
+    /// The storage pallet keys items by a hash of the owner and the item type, so the items of
+    /// an account cannot be enumerated and are read by type.
     function getItem(
         address account,
         bytes memory item_type
@@ -23,6 +25,44 @@ interface Storage {
         bytes memory item
     ) external returns (bool);
 
+    /// Removes an item of the caller and refunds its storage deposit.
+    function removeItem(
+        bytes memory item_type
+    ) external returns (bool);
+
+    /// Adds an item on behalf of the owner, who signed it with EIP-712
+    /// AddItem(address owner,bytes itemType,bytes item,uint256 nonce,uint256 deadline)
+    /// so that a relayer can send the transaction and pay its gas.
+    function addItemWithSignature(
+        address owner,
+        bytes memory item_type,
+        bytes memory item,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bool);
+
+    /// Updates an item on behalf of the owner, who signed it with EIP-712
+    /// UpdateItem(address owner,bytes itemType,bytes item,uint256 nonce,uint256 deadline)
+    function updateItemWithSignature(
+        address owner,
+        bytes memory item_type,
+        bytes memory item,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bool);
+
+    /// Returns the nonce the next signed write of the owner must use.
+    function nonces(address owner) external view returns (uint256);
+
+    /// Returns the EIP-712 domain separator, for the domain
+    /// EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)
+    /// named "peaq Storage" at version "1".
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+
     event ItemAdded(
         address sender,
         bytes item_type,
@@ -33,4 +73,8 @@ interface Storage {
         bytes item_type,
         bytes item
     );
+    event ItemRemoved(
+        address sender,
+        bytes item_type
+    );
 }
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Treasury contract's address.
+address constant TREASURY_ADDRESS = 0x000000000000000000000000000000000000080E;
+
+/// @dev The Treasury contract's instance.
+Treasury constant TREASURY_CONTRACT = Treasury(TREASURY_ADDRESS);
+
+/// @author The Peaq Team
+/// @title Treasury Interface
+/// The interface through which solidity contracts will propose treasury spends and query the
+/// treasury. Proposals are approved or rejected by the council.
+/// @custom:address 0x000000000000000000000000000000000000080E
+interface Treasury {
+    /// Proposes a spend from the treasury and reserves the proposal bond from the caller
+    /// @custom:selector 76fd21a6
+    /// @param value The amount to spend
+    /// @param beneficiary The account receiving the amount once the proposal is approved
+    /// @return proposalIndex The index of the proposal
+    function proposeSpend(uint256 value, address beneficiary)
+        external
+        returns (uint32 proposalIndex);
+
+    /// Returns the number of spend proposals made so far
+    /// @custom:selector da35c664
+    function proposalCount() external view returns (uint32);
+
+    /// Returns a spend proposal
+    /// @custom:selector ccf58d0d
+    /// @param proposalIndex The index of the proposal
+    /// @return exists Whether the proposal is pending
+    /// @return proposer The account which made the proposal
+    /// @return value The amount to spend
+    /// @return beneficiary The account receiving the amount
+    /// @return bond The amount reserved from the proposer
+    function proposal(uint32 proposalIndex)
+        external
+        view
+        returns (
+            bool exists,
+            address proposer,
+            uint256 value,
+            address beneficiary,
+            uint256 bond
+        );
+
+    /// Returns the indexes of the approved proposals, paid out at the next spend period
+    /// @custom:selector b05dba96
+    function approvals() external view returns (uint32[] memory);
+
+    /// Returns the spendable funds of the treasury
+    /// @custom:selector 4ba2363a
+    function pot() external view returns (uint256);
+
+    /// @dev Emitted when a spend is proposed
+    /// @param proposalIndex The index of the proposal
+    /// @param proposer The account which made the proposal
+    /// @param beneficiary The account receiving the amount
+    /// @param value The amount to spend
+    event SpendProposed(
+        uint32 indexed proposalIndex,
+        address indexed proposer,
+        address indexed beneficiary,
+        uint256 value
+    );
+}
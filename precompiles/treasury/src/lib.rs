@@ -0,0 +1,161 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Precompile to propose treasury spends and query the treasury via the EVM
+//!
+//! Proposers and beneficiaries are given as EVM addresses and mapped to substrate accounts with
+//! the runtime `AddressMapping`, which is backed by address-unification. Proposals are approved
+//! or rejected by the council, e.g. through the collective precompile.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use address_unification::EVMAddressMapping;
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::Get,
+};
+use pallet_evm::AddressMapping;
+use pallet_treasury::BalanceOf;
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use precompile_utils::{keccak256, prelude::*};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{Dispatchable, StaticLookup};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the SpendProposed log.
+pub const SELECTOR_LOG_SPEND_PROPOSED: [u8; 32] =
+	keccak256!("SpendProposed(uint32,address,address,uint256)");
+
+/// Same encoding as `pallet_treasury::Proposal`, whose fields are private to the pallet.
+#[derive(Decode)]
+struct ProposalDetails<AccountId, Balance> {
+	proposer: AccountId,
+	value: Balance,
+	beneficiary: AccountId,
+	bond: Balance,
+}
+
+/// A precompile to propose treasury spends and query the treasury
+pub struct TreasuryPrecompile<Runtime, AddressMapper>(PhantomData<(Runtime, AddressMapper)>);
+
+#[precompile_utils::precompile]
+impl<Runtime, AddressMapper> TreasuryPrecompile<Runtime, AddressMapper>
+where
+	Runtime: pallet_treasury::Config + pallet_evm::Config + frame_system::Config,
+	AddressMapper: EVMAddressMapping<Runtime::AccountId>,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime as frame_system::Config>::RuntimeCall: From<pallet_treasury::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+{
+	#[precompile::public("proposeSpend(uint256,address)")]
+	#[precompile::public("propose_spend(uint256,address)")]
+	fn propose_spend(
+		handle: &mut impl PrecompileHandle,
+		value: U256,
+		beneficiary: Address,
+	) -> EvmResult<u32> {
+		handle.record_log_costs_manual(4, 32)?;
+
+		let amount: BalanceOf<Runtime> = value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("value"))?;
+		let beneficiary: H160 = beneficiary.into();
+
+		// ProposalCount: u32(4)
+		handle.record_db_read::<Runtime>(4)?;
+		let proposal_index = pallet_treasury::Pallet::<Runtime>::proposal_count();
+
+		let caller = handle.context().caller;
+		let origin = Runtime::AddressMapping::into_account_id(caller);
+		let beneficiary_account = Runtime::AddressMapping::into_account_id(beneficiary);
+
+		// Spend proposals are deprecated in favor of `spend`, which is not open to signed origins.
+		#[allow(deprecated)]
+		let call = pallet_treasury::Call::<Runtime>::propose_spend {
+			value: amount,
+			beneficiary: Runtime::Lookup::unlookup(beneficiary_account),
+		};
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		log4(
+			handle.context().address,
+			SELECTOR_LOG_SPEND_PROPOSED,
+			H256::from_low_u64_be(proposal_index as u64),
+			caller,
+			beneficiary,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(proposal_index)
+	}
+
+	#[precompile::public("proposalCount()")]
+	#[precompile::public("proposal_count()")]
+	#[precompile::view]
+	fn proposal_count(handle: &mut impl PrecompileHandle) -> EvmResult<u32> {
+		// ProposalCount: u32(4)
+		handle.record_db_read::<Runtime>(4)?;
+
+		Ok(pallet_treasury::Pallet::<Runtime>::proposal_count())
+	}
+
+	#[precompile::public("proposal(uint32)")]
+	#[precompile::view]
+	fn proposal(
+		handle: &mut impl PrecompileHandle,
+		proposal_index: u32,
+	) -> EvmResult<(bool, Address, U256, Address, U256)> {
+		// Proposals: Twox64(8) + ProposalIndex(4) + Proposal
+		handle.record_db_read::<Runtime>(
+			12 + pallet_treasury::Proposal::<Runtime::AccountId, BalanceOf<Runtime>>::max_encoded_len(),
+		)?;
+
+		let details =
+			pallet_treasury::Pallet::<Runtime>::proposals(proposal_index).and_then(|proposal| {
+				ProposalDetails::<Runtime::AccountId, BalanceOf<Runtime>>::decode(
+					&mut &proposal.encode()[..],
+				)
+				.ok()
+			});
+
+		Ok(match details {
+			Some(details) => (
+				true,
+				Address(AddressMapper::get_evm_address_or_default(&details.proposer)),
+				details.value.into(),
+				Address(AddressMapper::get_evm_address_or_default(&details.beneficiary)),
+				details.bond.into(),
+			),
+			None =>
+				(false, Address(H160::zero()), U256::zero(), Address(H160::zero()), U256::zero()),
+		})
+	}
+
+	#[precompile::public("approvals()")]
+	#[precompile::view]
+	fn approvals(handle: &mut impl PrecompileHandle) -> EvmResult<Vec<u32>> {
+		// Approvals: MaxApprovals * ProposalIndex(4)
+		handle.record_db_read::<Runtime>(
+			(<Runtime as pallet_treasury::Config>::MaxApprovals::get() as usize).saturating_mul(4),
+		)?;
+
+		Ok(pallet_treasury::Pallet::<Runtime>::approvals().into_inner())
+	}
+
+	#[precompile::public("pot()")]
+	#[precompile::view]
+	fn pot(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		Ok(pallet_treasury::Pallet::<Runtime>::pot().into())
+	}
+}
@@ -0,0 +1,137 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+
+use frame_support::traits::ReservableCurrency;
+use precompile_utils::testing::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn funded() -> ExtBuilder {
+	ExtBuilder::default().with_balances(vec![(Alice.into(), 1000), (TreasuryAccount::get(), 1001)])
+}
+
+#[test]
+fn selectors() {
+	assert!(PCall::propose_spend_selectors().contains(&0x76fd21a6));
+	assert!(PCall::proposal_count_selectors().contains(&0xda35c664));
+	assert!(PCall::proposal_selectors().contains(&0xccf58d0d));
+	assert!(PCall::approvals_selectors().contains(&0xb05dba96));
+	assert!(PCall::pot_selectors().contains(&0x4ba2363a));
+}
+
+#[test]
+fn modifiers() {
+	funded().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(precompiles(), Alice, Precompile1);
+
+		tester.test_default_modifier(PCall::propose_spend_selectors());
+		tester.test_view_modifier(PCall::proposal_count_selectors());
+		tester.test_view_modifier(PCall::proposal_selectors());
+		tester.test_view_modifier(PCall::approvals_selectors());
+		tester.test_view_modifier(PCall::pot_selectors());
+	});
+}
+
+#[test]
+fn propose_spend() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::propose_spend { value: 100.into(), beneficiary: Address(Bob.into()) },
+			)
+			.expect_log(log4(
+				Precompile1,
+				SELECTOR_LOG_SPEND_PROPOSED,
+				H256::from_low_u64_be(0),
+				H160::from(Alice),
+				H160::from(Bob),
+				solidity::encode_event_data(U256::from(100)),
+			))
+			.execute_returns(0u32);
+
+		// The bond is the proposal bond minimum.
+		assert_eq!(Balances::reserved_balance(AccountId::from(Alice)), 10);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposal_count {})
+			.expect_no_logs()
+			.execute_returns(1u32);
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposal { proposal_index: 0 })
+			.expect_no_logs()
+			.execute_returns((
+				true,
+				Address(Alice.into()),
+				U256::from(100),
+				Address(Bob.into()),
+				U256::from(10),
+			));
+	});
+}
+
+#[test]
+fn propose_spend_value_too_large() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::propose_spend { value: U256::MAX, beneficiary: Address(Bob.into()) },
+			)
+			.execute_reverts(|output| output == b"value: Value is too large for balance type");
+	});
+}
+
+#[test]
+fn unknown_proposal() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::proposal { proposal_index: 7 })
+			.expect_no_logs()
+			.execute_returns((
+				false,
+				Address(H160::zero()),
+				U256::zero(),
+				Address(H160::zero()),
+				U256::zero(),
+			));
+	});
+}
+
+#[test]
+fn approvals() {
+	funded().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::propose_spend { value: 100.into(), beneficiary: Address(Bob.into()) },
+			)
+			.execute_returns(0u32);
+
+		#[allow(deprecated)]
+		Treasury::approve_proposal(RuntimeOrigin::root(), 0).expect("proposal exists");
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::approvals {})
+			.expect_no_logs()
+			.execute_returns(vec![0u32]);
+	});
+}
+
+#[test]
+fn pot() {
+	funded().build().execute_with(|| {
+		// The existential deposit of the treasury account is not spendable.
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::pot {})
+			.expect_no_logs()
+			.execute_returns(U256::from(1000));
+	});
+}
@@ -0,0 +1,139 @@
+//! EIP-712 signatures allowing a relayer to submit writes on behalf of their owner.
+//!
+//! A precompile describes its signing domain and the storage of its nonces with an
+//! [`Eip712Domain`], then builds the digests of its typed writes with
+//! [`Eip712::generate_digest`]. The nonce of the owner is part of every digest and is
+//! incremented once a signature is used, so a signature cannot be replayed.
+
+use crate::{
+	prelude::{revert, Address, PrecompileHandleExt, RuntimeHelper},
+	solidity, EvmResult,
+};
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	ensure,
+	storage::types::{StorageMap, ValueQuery},
+	traits::{Get, StorageInstance, Time},
+	Blake2_128Concat,
+};
+use precompile_utils_macro::keccak256;
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// EIP-712 domain used to compute the domain separator of a precompile.
+const EIP712_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// Size of a nonce entry: Blake2_128(16) + owner(20) + nonce(32).
+const NONCE_ENTRY_SIZE: usize = 68;
+
+/// Storage type used to store the nonces of the signed writes, protecting against replays.
+pub type NoncesStorage<Prefix> = StorageMap<
+	Prefix,
+	// Owner
+	Blake2_128Concat,
+	H160,
+	// Nonce
+	U256,
+	ValueQuery,
+>;
+
+/// The signing domain of a precompile.
+pub trait Eip712Domain {
+	/// Name of the domain, hashed into the domain separator.
+	const NAME: &'static str;
+
+	/// Storage prefix of the nonces of the precompile.
+	type Nonces: StorageInstance;
+}
+
+pub struct Eip712<Runtime, Domain>(PhantomData<(Runtime, Domain)>);
+
+impl<Runtime, Domain> Eip712<Runtime, Domain>
+where
+	Runtime: pallet_evm::Config,
+	Domain: Eip712Domain,
+{
+	pub fn compute_domain_separator(address: H160) -> [u8; 32] {
+		let name: H256 = keccak_256(Domain::NAME.as_bytes()).into();
+		let version: H256 = keccak256!("1").into();
+		let chain_id: U256 = Runtime::ChainId::get().into();
+
+		let domain_separator_inner = solidity::encode_arguments((
+			H256::from(EIP712_DOMAIN),
+			name,
+			version,
+			chain_id,
+			Address(address),
+		));
+
+		keccak_256(&domain_separator_inner)
+	}
+
+	/// Hashes the typed data, a struct hash, into the digest signed by the owner.
+	pub fn generate_digest(address: H160, struct_hash: [u8; 32]) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address);
+
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&struct_hash);
+		keccak_256(&pre_digest)
+	}
+
+	/// Checks the signature of `owner` over the digest built from its current nonce, then
+	/// increments the nonce so the signature cannot be replayed.
+	pub fn use_signature(
+		handle: &mut impl PrecompileHandle,
+		owner: H160,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+		digest: impl FnOnce(U256) -> [u8; 32],
+	) -> EvmResult {
+		handle.record_db_read::<Runtime>(NONCE_ENTRY_SIZE)?;
+
+		// Blockchain time is in ms while Ethereum use second timestamps.
+		let timestamp: u128 =
+			<Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		let timestamp: U256 = U256::from(timestamp / 1000);
+
+		ensure!(deadline >= timestamp, revert("Signature expired"));
+
+		let nonce = NoncesStorage::<Domain::Nonces>::get(owner);
+		let digest = digest(nonce);
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = v;
+
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &digest)
+			.map_err(|_| revert("Invalid signature"))?;
+		let signer = H160::from(H256::from_slice(keccak_256(&signer).as_slice()));
+
+		ensure!(signer != H160::zero() && signer == owner, revert("Invalid signature"));
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		NoncesStorage::<Domain::Nonces>::insert(owner, nonce + U256::one());
+
+		Ok(())
+	}
+
+	pub fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(NONCE_ENTRY_SIZE)?;
+
+		Ok(NoncesStorage::<Domain::Nonces>::get(H160::from(owner)))
+	}
+
+	pub fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		// ChainId
+		handle.record_db_read::<Runtime>(8)?;
+
+		Ok(Self::compute_domain_separator(handle.context().address).into())
+	}
+}
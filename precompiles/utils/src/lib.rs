@@ -22,6 +22,7 @@ extern crate alloc;
 // `precompile_utils` being in the list of imported crates.
 extern crate self as precompile_utils;
 
+pub mod eip712;
 pub mod evm;
 pub mod precompile_set;
 pub mod substrate;
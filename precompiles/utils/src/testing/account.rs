@@ -278,6 +278,9 @@ pub enum MockPeaqAccount {
 	EVMu1Account,
 	EVMu2Account,
 
+	/// Account with a known secret key, to sign messages such as EIP-2612 permits.
+	CryptoAlith,
+
 	AssetId(MockAssetId),
 }
 
@@ -299,6 +302,7 @@ impl From<MockPeaqAccount> for u64 {
 			MockPeaqAccount::David => 0x12,
 			MockPeaqAccount::EVMu1Account => 1_u64,
 			MockPeaqAccount::EVMu2Account => 2_u64,
+			MockPeaqAccount::CryptoAlith => 0x15,
 			MockPeaqAccount::AssetId(asset_id) => asset_id.0 as u64,
 			MockPeaqAccount::Bogus => 0,
 		}
@@ -316,6 +320,7 @@ impl From<MockPeaqAccount> for H160 {
 			MockPeaqAccount::David => H160::repeat_byte(0x12),
 			MockPeaqAccount::EVMu1Account => H160::from_low_u64_be(1),
 			MockPeaqAccount::EVMu2Account => H160::from_low_u64_be(2),
+			MockPeaqAccount::CryptoAlith => CryptoAlith.into(),
 			MockPeaqAccount::AssetId(asset_id) => {
 				let mut data = [0u8; 20];
 				let id_as_bytes = asset_id.0.to_be_bytes();
@@ -340,6 +345,7 @@ impl AddressMapping<MockPeaqAccount> for MockPeaqAccount {
 			a if a == H160::repeat_byte(0x12) => Self::David,
 			a if a == H160::from_low_u64_be(1) => Self::EVMu1Account,
 			a if a == H160::from_low_u64_be(2) => Self::EVMu2Account,
+			a if a == H160::from(CryptoAlith) => Self::CryptoAlith,
 			_ => {
 				let mut data = [0u8; 16];
 				let (prefix_part, id_part) = h160_account.as_fixed_bytes().split_at(4);
@@ -372,11 +378,30 @@ impl From<MockPeaqAccount> for [u8; 32] {
 			MockPeaqAccount::David => [0x12; 32],
 			MockPeaqAccount::EVMu1Account => [0x13; 32],
 			MockPeaqAccount::EVMu2Account => [0x14; 32],
+			MockPeaqAccount::CryptoAlith => [0x15; 32],
 			_ => Default::default(),
 		}
 	}
 }
 
+impl AsRef<[u8]> for MockPeaqAccount {
+	fn as_ref(&self) -> &[u8] {
+		match self {
+			MockPeaqAccount::Alice => &[0xAA; 32],
+			MockPeaqAccount::Bob => &[0xBB; 32],
+			MockPeaqAccount::Charlie => &[0xCC; 32],
+			MockPeaqAccount::SelfReserve => &[0xDD; 32],
+			MockPeaqAccount::ParentAccount => &[0xEE; 32],
+			MockPeaqAccount::SlibingParaAccount => &[0x11; 32],
+			MockPeaqAccount::David => &[0x12; 32],
+			MockPeaqAccount::EVMu1Account => &[0x13; 32],
+			MockPeaqAccount::EVMu2Account => &[0x14; 32],
+			MockPeaqAccount::CryptoAlith => &[0x15; 32],
+			_ => &[0; 32],
+		}
+	}
+}
+
 impl From<[u8; 32]> for MockPeaqAccount {
 	fn from(value: [u8; 32]) -> MockPeaqAccount {
 		match value {
@@ -389,6 +414,7 @@ impl From<[u8; 32]> for MockPeaqAccount {
 			a if a == [0x12; 32] => MockPeaqAccount::David,
 			a if a == [0x13; 32] => MockPeaqAccount::EVMu1Account,
 			a if a == [0x14; 32] => MockPeaqAccount::EVMu2Account,
+			a if a == [0x15; 32] => MockPeaqAccount::CryptoAlith,
 			_ => MockPeaqAccount::Bogus,
 		}
 	}
@@ -18,6 +18,7 @@ pub mod account;
 pub mod execution;
 pub mod handle;
 pub mod modifier;
+mod runtime;
 mod solidity;
 
 pub use account::*;
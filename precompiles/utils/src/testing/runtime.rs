@@ -0,0 +1,178 @@
+/// Declares the mock runtime of a precompile wrapping a peaq pallet.
+///
+/// The runtime has `System`, `Balances`, `Evm` and `Timestamp` along with the given pallets,
+/// whose configs are left to the caller, and serves the precompile at address `1`. It also
+/// declares `Precompiles`, `PrecompilesValue`, `PCall` and an `ExtBuilder` endowing accounts.
+///
+/// The calling crate needs `frame-support`, `frame-system`, `pallet-balances`, `pallet-evm`,
+/// `pallet-timestamp`, `sp-core`, `sp-io` and `sp-runtime`.
+#[macro_export]
+macro_rules! mock_peaq_runtime {
+	(
+		pallets: { $($name:ident: $pallet:ident),* $(,)? },
+		precompile: $precompile:ident,
+		call: $call:ident $(,)?
+	) => {
+		use frame_support::{construct_runtime, parameter_types, traits::Everything};
+		use pallet_evm::{EnsureAddressNever, EnsureAddressRoot};
+		use $crate::{precompile_set::*, testing::*};
+		use sp_runtime::{
+			traits::{BlakeTwo256, IdentityLookup},
+			BuildStorage,
+		};
+
+		pub type AccountId = MockPeaqAccount;
+		pub type Balance = u128;
+		pub type Block = frame_system::mocking::MockBlockU32<Runtime>;
+
+		construct_runtime!(
+			pub enum Runtime
+			{
+				System: frame_system,
+				Balances: pallet_balances,
+				Evm: pallet_evm,
+				Timestamp: pallet_timestamp,
+				$($name: $pallet,)*
+			}
+		);
+
+		parameter_types! {
+			pub const BlockHashCount: u32 = 250;
+			pub const SS58Prefix: u8 = 42;
+		}
+
+		impl frame_system::Config for Runtime {
+			type BaseCallFilter = Everything;
+			type DbWeight = ();
+			type RuntimeOrigin = RuntimeOrigin;
+			type RuntimeCall = RuntimeCall;
+			type Nonce = u64;
+			type Block = Block;
+			type Hash = sp_core::H256;
+			type Hashing = BlakeTwo256;
+			type AccountId = AccountId;
+			type Lookup = IdentityLookup<Self::AccountId>;
+			type RuntimeEvent = RuntimeEvent;
+			type BlockHashCount = BlockHashCount;
+			type Version = ();
+			type PalletInfo = PalletInfo;
+			type AccountData = pallet_balances::AccountData<Balance>;
+			type OnNewAccount = ();
+			type OnKilledAccount = ();
+			type SystemWeightInfo = ();
+			type BlockWeights = ();
+			type BlockLength = ();
+			type SS58Prefix = SS58Prefix;
+			type OnSetCode = ();
+			type MaxConsumers = frame_support::traits::ConstU32<16>;
+			type RuntimeTask = ();
+		}
+
+		parameter_types! {
+			pub const ExistentialDeposit: u128 = 1;
+		}
+
+		impl pallet_balances::Config for Runtime {
+			type MaxReserves = frame_support::traits::ConstU32<50>;
+			type ReserveIdentifier = [u8; 8];
+			type MaxLocks = ();
+			type Balance = Balance;
+			type RuntimeEvent = RuntimeEvent;
+			type DustRemoval = ();
+			type ExistentialDeposit = ExistentialDeposit;
+			type AccountStore = System;
+			type WeightInfo = ();
+			type FreezeIdentifier = ();
+			type MaxFreezes = ();
+			type RuntimeHoldReason = RuntimeHoldReason;
+			type RuntimeFreezeReason = ();
+		}
+
+		pub type Precompiles<R> =
+			PrecompileSetBuilder<R, (PrecompileAt<AddressU64<1>, $precompile<R>>,)>;
+
+		pub type PCall = $call<Runtime>;
+
+		const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+		/// Block storage limit in bytes. Set to 40 KB.
+		const BLOCK_STORAGE_LIMIT: u64 = 40 * 1024;
+
+		parameter_types! {
+			pub BlockGasLimit: sp_core::U256 = sp_core::U256::from(u64::MAX);
+			pub PrecompilesValue: Precompiles<Runtime> = Precompiles::new();
+			pub const WeightPerGas: frame_support::weights::Weight =
+				frame_support::weights::Weight::from_parts(1, 0);
+			pub GasLimitPovSizeRatio: u64 = {
+				let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+				block_gas_limit.saturating_div(MAX_POV_SIZE)
+			};
+			pub GasLimitStorageGrowthRatio: u64 = {
+				let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+				block_gas_limit.saturating_div(BLOCK_STORAGE_LIMIT)
+			};
+		}
+
+		impl pallet_evm::Config for Runtime {
+			type FeeCalculator = ();
+			type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+			type WeightPerGas = WeightPerGas;
+			type CallOrigin = EnsureAddressRoot<AccountId>;
+			type WithdrawOrigin = EnsureAddressNever<AccountId>;
+			type AddressMapping = AccountId;
+			type Currency = Balances;
+			type RuntimeEvent = RuntimeEvent;
+			type Runner = pallet_evm::runner::stack::Runner<Self>;
+			type PrecompilesValue = PrecompilesValue;
+			type PrecompilesType = Precompiles<Self>;
+			type ChainId = ();
+			type OnChargeTransaction = ();
+			type BlockGasLimit = BlockGasLimit;
+			type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+			type FindAuthor = ();
+			type OnCreate = ();
+			type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+			type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+			type Timestamp = Timestamp;
+			type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
+			type SuicideQuickClearLimit = ();
+		}
+
+		parameter_types! {
+			pub const MinimumPeriod: u64 = 5;
+		}
+
+		impl pallet_timestamp::Config for Runtime {
+			type Moment = u64;
+			type OnTimestampSet = ();
+			type MinimumPeriod = MinimumPeriod;
+			type WeightInfo = ();
+		}
+
+		#[derive(Default)]
+		pub(crate) struct ExtBuilder {
+			// endowed accounts with balances
+			balances: Vec<(AccountId, Balance)>,
+		}
+
+		impl ExtBuilder {
+			pub(crate) fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+				self.balances = balances;
+				self
+			}
+
+			pub(crate) fn build(self) -> sp_io::TestExternalities {
+				let mut t = frame_system::GenesisConfig::<Runtime>::default()
+					.build_storage()
+					.expect("Frame system builds valid default genesis config");
+
+				pallet_balances::GenesisConfig::<Runtime> { balances: self.balances }
+					.assimilate_storage(&mut t)
+					.expect("Pallet balances storage can be assimilated");
+
+				let mut ext = sp_io::TestExternalities::new(t);
+				ext.execute_with(|| System::set_block_number(1));
+				ext
+			}
+		}
+	};
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The XcmTransfer contract's address.
+address constant XCM_TRANSFER_ADDRESS = 0x000000000000000000000000000000000000080A;
+
+/// @dev The XcmTransfer contract's instance.
+XcmTransfer constant XCM_TRANSFER_CONTRACT = XcmTransfer(XCM_TRANSFER_ADDRESS);
+
+/// @author The Peaq Team
+/// @title Xcm Transfer Interface
+/// The interface through which solidity contracts will transfer assets through xcm with an
+/// explicit reserve
+/// @custom:address 0x000000000000000000000000000000000000080A
+interface XcmTransfer {
+    // A multilocation is defined by its number of parents and the encoded junctions (interior)
+    struct Multilocation {
+        uint8 parents;
+        bytes[] interior;
+    }
+
+    // A MultiAsset is defined by a multilocation and an amount
+    struct MultiAsset {
+        Multilocation location;
+        uint256 amount;
+    }
+
+    // Where the transferred assets are reserved
+    enum TransferType {
+        LocalReserve,
+        DestinationReserve
+    }
+
+    /// Transfer assets reserved on this chain or on the destination
+    /// @custom:selector 2da99b31
+    /// @param dest The destination chain
+    /// @param assets The assets to transfer, at most 2
+    /// @param transferType Whether the assets are reserved on this chain or on the destination
+    /// @param remoteFeesIdIndex The index in `assets` of the asset used to pay the fees
+    /// @param customXcmOnDest The scale encoded versioned xcm to execute on the destination,
+    /// after the assets are deposited to the holding register and the execution is bought
+    function transferAssetsUsingTypeAndThen(
+        Multilocation memory dest,
+        MultiAsset[] memory assets,
+        TransferType transferType,
+        uint8 remoteFeesIdIndex,
+        bytes memory customXcmOnDest
+    ) external;
+
+    /// Transfer assets reserved on a third chain, such as the relay or Asset Hub
+    /// @custom:selector 955dfecb
+    /// @param dest The destination chain
+    /// @param assets The assets to transfer, at most 2
+    /// @param remoteReserve The chain where the assets are reserved
+    /// @param remoteFeesIdIndex The index in `assets` of the asset used to pay the fees, half of
+    /// it pays for the execution on the reserve and the other half on the destination
+    /// @param customXcmOnDest The scale encoded versioned xcm to execute on the destination,
+    /// after the assets are deposited to the holding register and the execution is bought
+    function transferAssetsUsingRemoteReserveAndThen(
+        Multilocation memory dest,
+        MultiAsset[] memory assets,
+        Multilocation memory remoteReserve,
+        uint8 remoteFeesIdIndex,
+        bytes memory customXcmOnDest
+    ) external;
+}
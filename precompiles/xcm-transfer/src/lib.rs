@@ -0,0 +1,332 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Precompile to transfer assets through XCM with an explicit reserve via the EVM
+//!
+//! The caller states whether the assets are reserved on this chain, on the destination, or on a
+//! third chain, and provides the XCM to execute on the destination once the assets arrived there.
+//! The stated reserve must be the one the XCM executor determines from its `IsReserve` and
+//! `IsTeleporter` configuration, as pallet-xcm checks for its transfers; teleports are not
+//! supported. The local XCM program is built here, weighed with the pallet-xcm weigher, and
+//! executed by the XCM executor on behalf of the caller, which charges the delivery fees.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::PrecompileHandle;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::{ConstU32, Contains, EnsureOrigin, Get},
+};
+use pallet_evm::AddressMapping;
+use parity_scale_codec::{DecodeLimit, Encode};
+use precompile_utils::prelude::*;
+use sp_core::U256;
+use sp_runtime::traits::Dispatchable;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+use xcm::{latest::prelude::*, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+use xcm_executor::traits::{TransferType as XcmTransferType, WeightBounds, XcmAssetTransfers};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub type CallOf<Runtime> = <Runtime as pallet_xcm::Config>::RuntimeCall;
+pub const XCM_SIZE_LIMIT: u32 = 2u32.pow(16);
+type GetXcmSizeLimit = ConstU32<XCM_SIZE_LIMIT>;
+
+/// Maximum number of assets transferred at once, same as in pallet-xcm.
+pub const MAX_ASSETS_FOR_TRANSFER: u32 = 2;
+type GetMaxAssetsForTransfer = ConstU32<MAX_ASSETS_FOR_TRANSFER>;
+
+/// Where the transferred assets are reserved.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransferType {
+	/// The assets are reserved on this chain.
+	LocalReserve,
+	/// The assets are reserved on the destination.
+	DestinationReserve,
+	/// The assets are reserved on a third chain.
+	RemoteReserve(Location),
+}
+
+/// A precompile to transfer assets through XCM with an explicit reserve
+pub struct XcmTransferPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> XcmTransferPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config + frame_system::Config + pallet_xcm::Config,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+{
+	#[precompile::public(
+		"transferAssetsUsingTypeAndThen((uint8,bytes[]),((uint8,bytes[]),uint256)[],uint8,uint8,bytes)"
+	)]
+	#[precompile::public(
+		"transfer_assets_using_type_and_then((uint8,bytes[]),((uint8,bytes[]),uint256)[],uint8,uint8,bytes)"
+	)]
+	fn transfer_assets_using_type_and_then(
+		handle: &mut impl PrecompileHandle,
+		dest: Location,
+		assets: BoundedVec<EvmAsset, GetMaxAssetsForTransfer>,
+		transfer_type: u8,
+		remote_fees_id_index: u8,
+		custom_xcm_on_dest: BoundedBytes<GetXcmSizeLimit>,
+	) -> EvmResult {
+		let transfer_type = match transfer_type {
+			0 => TransferType::LocalReserve,
+			1 => TransferType::DestinationReserve,
+			_ =>
+				return Err(RevertReason::custom("Unknown transfer type")
+					.in_field("transferType")
+					.into()),
+		};
+
+		Self::transfer_assets(
+			handle,
+			dest,
+			assets.into(),
+			transfer_type,
+			remote_fees_id_index,
+			custom_xcm_on_dest.into(),
+		)
+	}
+
+	#[precompile::public(
+		"transferAssetsUsingRemoteReserveAndThen((uint8,bytes[]),((uint8,bytes[]),uint256)[],(uint8,bytes[]),uint8,bytes)"
+	)]
+	#[precompile::public(
+		"transfer_assets_using_remote_reserve_and_then((uint8,bytes[]),((uint8,bytes[]),uint256)[],(uint8,bytes[]),uint8,bytes)"
+	)]
+	fn transfer_assets_using_remote_reserve_and_then(
+		handle: &mut impl PrecompileHandle,
+		dest: Location,
+		assets: BoundedVec<EvmAsset, GetMaxAssetsForTransfer>,
+		remote_reserve: Location,
+		remote_fees_id_index: u8,
+		custom_xcm_on_dest: BoundedBytes<GetXcmSizeLimit>,
+	) -> EvmResult {
+		Self::transfer_assets(
+			handle,
+			dest,
+			assets.into(),
+			TransferType::RemoteReserve(remote_reserve),
+			remote_fees_id_index,
+			custom_xcm_on_dest.into(),
+		)
+	}
+
+	fn transfer_assets(
+		handle: &mut impl PrecompileHandle,
+		dest: Location,
+		assets: Vec<EvmAsset>,
+		transfer_type: TransferType,
+		remote_fees_id_index: u8,
+		custom_xcm_on_dest: Vec<u8>,
+	) -> EvmResult {
+		let custom_xcm_on_dest = VersionedXcm::<()>::decode_all_with_depth_limit(
+			MAX_XCM_DECODE_DEPTH,
+			&mut custom_xcm_on_dest.as_slice(),
+		)
+		.ok()
+		.and_then(|xcm| Xcm::<()>::try_from(xcm).ok())
+		.ok_or_else(|| RevertReason::custom("Failed xcm decoding").in_field("customXcmOnDest"))?;
+
+		// The fees are paid with the selected asset, as given by the caller.
+		let fees_id = assets
+			.get(remote_fees_id_index as usize)
+			.map(|asset| AssetId(asset.location.clone()))
+			.ok_or_else(|| {
+				RevertReason::custom("Index out of bounds").in_field("remoteFeesIdIndex")
+			})?;
+
+		let assets = assets
+			.into_iter()
+			.enumerate()
+			.map(|(index, asset)| {
+				let amount: u128 = asset.amount.try_into().map_err(|_| {
+					RevertReason::value_is_too_large("balance type")
+						.in_array(index)
+						.in_field("assets")
+				})?;
+				Ok(Asset::from((asset.location, amount)))
+			})
+			.collect::<EvmResult<Vec<_>>>()?;
+		let assets = Assets::from(assets);
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let signed_origin: <Runtime as frame_system::Config>::RuntimeOrigin =
+			frame_system::RawOrigin::Signed(origin).into();
+		let origin_location =
+			<Runtime as pallet_xcm::Config>::ExecuteXcmOrigin::ensure_origin(signed_origin.into())
+				.map_err(|_| revert("Failed origin conversion"))?;
+
+		if !<Runtime as pallet_xcm::Config>::XcmReserveTransferFilter::contains(&(
+			origin_location.clone(),
+			assets.clone().into_inner(),
+		)) {
+			return Err(revert("Transfer filtered"));
+		}
+
+		Self::ensure_reserve(&assets, &dest, &transfer_type)?;
+
+		let mut message =
+			Self::build_transfer_xcm(dest, assets, fees_id, transfer_type, custom_xcm_on_dest)?;
+
+		let weight = <Runtime as pallet_xcm::Config>::Weigher::weight(&mut message)
+			.map_err(|_| revert("Failed weighting"))?;
+		RuntimeHelper::<Runtime>::reocrd_external_cost(handle, weight, 0)?;
+
+		let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
+		let outcome = <Runtime as pallet_xcm::Config>::XcmExecutor::prepare_and_execute(
+			origin_location,
+			message,
+			&mut hash,
+			weight,
+			weight,
+		);
+		let used_weight = outcome.weight_used();
+		outcome.ensure_complete().map_err(|error| {
+			log::debug!(target: "xcm-transfer-precompile", "XCM execution failed: {:?}", error);
+			revert("XCM execution failed")
+		})?;
+
+		RuntimeHelper::<Runtime>::refund_weight_v2_cost(handle, weight, Some(used_weight))?;
+
+		Ok(())
+	}
+
+	/// Ensures `transfer_type` is where the XCM executor considers each of the `assets` to be
+	/// reserved when sent to `dest`, so they are not trapped or burnt on a chain which is not
+	/// their reserve.
+	fn ensure_reserve(assets: &Assets, dest: &Location, transfer_type: &TransferType) -> EvmResult {
+		for (index, asset) in assets.inner().iter().enumerate() {
+			let reserve = <Runtime as pallet_xcm::Config>::XcmExecutor::determine_for(asset, dest)
+				.map_err(|_| {
+					RevertReason::custom("Unknown reserve").in_array(index).in_field("assets")
+				})?;
+
+			let valid = match (reserve, transfer_type) {
+				(XcmTransferType::LocalReserve, TransferType::LocalReserve) |
+				(XcmTransferType::DestinationReserve, TransferType::DestinationReserve) => true,
+				(
+					XcmTransferType::RemoteReserve(reserve),
+					TransferType::RemoteReserve(expected),
+				) => Location::try_from(reserve).as_ref() == Ok(expected),
+				_ => false,
+			};
+			if !valid {
+				return Err(RevertReason::custom("Invalid reserve for the transfer type")
+					.in_array(index)
+					.in_field("assets")
+					.into());
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Builds the XCM executed locally to move `assets` to `dest`, depending on where they are
+	/// reserved. The execution on the destination is paid with the asset identified by `fees_id`.
+	fn build_transfer_xcm(
+		dest: Location,
+		assets: Assets,
+		fees_id: AssetId,
+		transfer_type: TransferType,
+		custom_xcm_on_dest: Xcm<()>,
+	) -> EvmResult<Xcm<CallOf<Runtime>>> {
+		let context = <Runtime as pallet_xcm::Config>::UniversalLocation::get();
+		let max_assets = assets.len() as u32;
+
+		let fees =
+			assets
+				.inner()
+				.iter()
+				.find(|asset| asset.id == fees_id)
+				.cloned()
+				.ok_or_else(|| {
+					RevertReason::custom("Fees asset not found").in_field("remoteFeesIdIndex")
+				})?;
+
+		let buy_execution_on = |fees: Asset, location: &Location| -> EvmResult<Instruction<()>> {
+			let fees = fees
+				.reanchored(location, &context)
+				.map_err(|_| revert("Failed reanchoring fees"))?;
+			Ok(BuyExecution { fees, weight_limit: Unlimited })
+		};
+		let then_on_dest = |buy_execution: Instruction<()>| {
+			let mut xcm = vec![buy_execution];
+			xcm.extend(custom_xcm_on_dest.0.clone());
+			Xcm(xcm)
+		};
+
+		let message = match transfer_type {
+			TransferType::LocalReserve => Xcm(vec![TransferReserveAsset {
+				xcm: then_on_dest(buy_execution_on(fees, &dest)?),
+				assets,
+				dest,
+			}]),
+			TransferType::DestinationReserve => Xcm(vec![
+				WithdrawAsset(assets),
+				InitiateReserveWithdraw {
+					assets: Wild(AllCounted(max_assets)),
+					xcm: then_on_dest(buy_execution_on(fees, &dest)?),
+					reserve: dest,
+				},
+			]),
+			TransferType::RemoteReserve(reserve) => {
+				// Half of the fees pays for the execution on the reserve, the other half for the
+				// execution on the destination, as done by pallet-xcm.
+				let (reserve_fees, dest_fees) = Self::halve_fees(fees)?;
+				let xcm_on_dest = then_on_dest(buy_execution_on(dest_fees, &dest)?);
+				let dest = dest
+					.reanchored(&reserve, &context)
+					.map_err(|_| RevertReason::custom("Failed reanchoring").in_field("dest"))?;
+
+				Xcm(vec![
+					WithdrawAsset(assets),
+					InitiateReserveWithdraw {
+						assets: Wild(AllCounted(max_assets)),
+						xcm: Xcm(vec![
+							buy_execution_on(reserve_fees, &reserve)?,
+							DepositReserveAsset {
+								assets: Wild(AllCounted(max_assets)),
+								dest,
+								xcm: xcm_on_dest,
+							},
+						]),
+						reserve,
+					},
+				])
+			},
+		};
+
+		Ok(message)
+	}
+
+	fn halve_fees(fees: Asset) -> EvmResult<(Asset, Asset)> {
+		match fees.fun {
+			Fungible(amount) => {
+				let half = amount.saturating_div(2);
+				let first = Asset { id: fees.id.clone(), fun: Fungible(half) };
+				let second = Asset { id: fees.id, fun: Fungible(amount.saturating_sub(half)) };
+				Ok((first, second))
+			},
+			NonFungible(_) => Err(RevertReason::custom("Fees must be fungible")
+				.in_field("remoteFeesIdIndex")
+				.into()),
+		}
+	}
+}
+
+#[derive(solidity::Codec)]
+pub struct EvmAsset {
+	location: Location,
+	amount: U256,
+}
+
+impl From<(Location, U256)> for EvmAsset {
+	fn from(tuple: (Location, U256)) -> Self {
+		EvmAsset { location: tuple.0, amount: tuple.1 }
+	}
+}
@@ -0,0 +1,305 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::mock::{
+	sent_xcm, ExtBuilder, PCall, Precompiles, PrecompilesValue, Runtime, SelfReserve,
+};
+use parity_scale_codec::Encode;
+use precompile_utils::testing::*;
+use sp_core::U256;
+use xcm::prelude::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn sibling() -> Location {
+	Location::new(1, [Parachain(2000)])
+}
+
+fn custom_xcm_on_dest() -> Xcm<()> {
+	Xcm(vec![DepositAsset {
+		assets: Wild(AllCounted(1)),
+		beneficiary: Location::new(0, [AccountKey20 { network: None, key: [1u8; 20] }]),
+	}])
+}
+
+fn encoded_custom_xcm_on_dest() -> Vec<u8> {
+	VersionedXcm::<()>::V4(custom_xcm_on_dest()).encode()
+}
+
+fn with_custom_xcm_on_dest(mut instructions: Vec<Instruction<()>>) -> Xcm<()> {
+	instructions.extend(custom_xcm_on_dest().0);
+	Xcm(instructions)
+}
+
+#[test]
+fn test_selector_enum() {
+	assert!(PCall::transfer_assets_using_type_and_then_selectors().contains(&0x2da99b31));
+	assert!(PCall::transfer_assets_using_remote_reserve_and_then_selectors().contains(&0x955dfecb));
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut tester = PrecompilesModifierTester::new(
+			precompiles(),
+			MockPeaqAccount::Alice,
+			MockPeaqAccount::EVMu1Account,
+		);
+
+		tester.test_default_modifier(PCall::transfer_assets_using_type_and_then_selectors());
+		tester
+			.test_default_modifier(PCall::transfer_assets_using_remote_reserve_and_then_selectors());
+	});
+}
+
+#[test]
+fn transfer_local_reserve_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: sibling(),
+			assets: vec![(SelfReserve::get(), U256::from(500)).into()].into(),
+			transfer_type: 0,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.expect_cost(1000)
+			.expect_no_logs()
+			.execute_returns(());
+
+		// The local asset seen from the sibling.
+		let asset: Asset = (Location::new(1, [Parachain(100), PalletInstance(1)]), 500).into();
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				sibling(),
+				with_custom_xcm_on_dest(vec![
+					ReserveAssetDeposited(asset.clone().into()),
+					ClearOrigin,
+					BuyExecution { fees: asset, weight_limit: Unlimited },
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn transfer_destination_reserve_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: Location::parent(),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			transfer_type: 1,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.expect_cost(2000)
+			.expect_no_logs()
+			.execute_returns(());
+
+		let asset: Asset = (Location::here(), 1000).into();
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				Location::parent(),
+				with_custom_xcm_on_dest(vec![
+					WithdrawAsset(asset.clone().into()),
+					ClearOrigin,
+					BuyExecution { fees: asset, weight_limit: Unlimited },
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn transfer_remote_reserve_asset_splits_fees() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_remote_reserve_and_then {
+			dest: sibling(),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			remote_reserve: Location::parent(),
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.expect_cost(2000)
+			.expect_no_logs()
+			.execute_returns(());
+
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				Location::parent(),
+				Xcm(vec![
+					WithdrawAsset((Location::here(), 1000).into()),
+					ClearOrigin,
+					BuyExecution { fees: (Location::here(), 500).into(), weight_limit: Unlimited },
+					DepositReserveAsset {
+						assets: Wild(AllCounted(1)),
+						dest: Location::new(0, [Parachain(2000)]),
+						xcm: with_custom_xcm_on_dest(vec![BuyExecution {
+							fees: (Location::parent(), 500).into(),
+							weight_limit: Unlimited,
+						}]),
+					},
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn transfer_fails_for_unknown_transfer_type() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: sibling(),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			transfer_type: 2,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"transferType: Unknown transfer type");
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn transfer_fails_if_reserve_does_not_match_transfer_type() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The relay asset is reserved on the relay, not here.
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: sibling(),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			transfer_type: 0,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| {
+				output == b"assets[0]: Invalid reserve for the transfer type"
+			});
+
+		// The local asset is not reserved on the destination.
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: sibling(),
+			assets: vec![(SelfReserve::get(), U256::from(1000)).into()].into(),
+			transfer_type: 1,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| {
+				output == b"assets[0]: Invalid reserve for the transfer type"
+			});
+
+		// The relay asset is not reserved on a sibling.
+		let input = PCall::transfer_assets_using_remote_reserve_and_then {
+			dest: Location::new(1, [Parachain(3000)]),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			remote_reserve: sibling(),
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| {
+				output == b"assets[0]: Invalid reserve for the transfer type"
+			});
+
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn transfer_fails_for_unknown_reserve() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: Location::parent(),
+			assets: vec![
+				(Location::new(1, [Parachain(3000), GeneralIndex(1)]), U256::from(1000)).into()
+			]
+			.into(),
+			transfer_type: 1,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"assets[0]: Unknown reserve");
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn transfer_fails_if_fees_index_out_of_bounds() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: Location::parent(),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			transfer_type: 1,
+			remote_fees_id_index: 1,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"remoteFeesIdIndex: Index out of bounds");
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn transfer_fails_if_amount_overflows() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: Location::parent(),
+			assets: vec![(Location::parent(), U256::MAX).into()].into(),
+			transfer_type: 1,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: encoded_custom_xcm_on_dest().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"assets[0]: Value is too large for balance type");
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn transfer_fails_if_custom_xcm_cannot_be_decoded() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transfer_assets_using_type_and_then {
+			dest: Location::parent(),
+			assets: vec![(Location::parent(), U256::from(1000)).into()].into(),
+			transfer_type: 1,
+			remote_fees_id_index: 0,
+			custom_xcm_on_dest: vec![0xffu8, 0x01].into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"customXcmOnDest: Failed xcm decoding");
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
+	check_precompile_implements_solidity_interfaces(&["XcmTransfer.sol"], PCall::supports_selector)
+}
@@ -18,6 +18,27 @@ interface XcmUtils {
         bytes[] interior;
     }
 
+    // A MultiAsset is defined by a multilocation and an amount
+    struct MultiAsset {
+        Multilocation location;
+        uint256 amount;
+    }
+
+    // A Weight is defined by its reference time and proof size
+    struct Weight {
+        uint64 refTime;
+        uint64 proofSize;
+    }
+
+    /// Get the address of the account derived for a multilocation
+    /// @custom:selector a008cd8a
+    /// @param multilocation The multilocation of the remote account
+    /// @return account The EVM address of the local account derived for the multilocation
+    function locationToAddress(Multilocation memory multilocation)
+        external
+        view
+        returns (address account);
+
      /// Get the weight that a message will consume in our chain
     /// @custom:selector 25d54154
     /// @param message scale encoded xcm mversioned xcm message
@@ -46,4 +67,37 @@ interface XcmUtils {
     /// @param dest The destination chain to which send this message
     /// @param message The versioned message to be sent scale-encoded
     function xcmSend(Multilocation memory dest, bytes memory message) external;
+
+    /// Dispatch a call in another chain from the account derived for the caller
+    /// @dev The fee asset is withdrawn from the derived account to buy execution, and unspent
+    /// fees are deposited back to it
+    /// @custom:selector 1a1838eb
+    /// @param dest The destination chain in which the call is dispatched
+    /// @param feeAsset The asset, as seen by this chain, and amount used to pay for execution
+    /// @param weight The maximum weight the call is allowed to consume
+    /// @param call The scale-encoded call of the destination chain
+    function transactThroughSigned(
+        Multilocation memory dest,
+        MultiAsset memory feeAsset,
+        Weight memory weight,
+        bytes memory call
+    ) external;
+
+    /// Dispatch a call in another chain from a derivative of the account derived for the caller
+    /// @dev The call is wrapped in `as_derivative` of the utility pallet of the destination chain
+    /// @custom:selector 8cf2386b
+    /// @param dest The destination chain in which the call is dispatched
+    /// @param utilityIndex The index of the utility pallet in the destination chain
+    /// @param index The index of the derivative account
+    /// @param feeAsset The asset, as seen by this chain, and amount used to pay for execution
+    /// @param weight The maximum weight the call is allowed to consume
+    /// @param call The scale-encoded call of the destination chain
+    function transactThroughDerivative(
+        Multilocation memory dest,
+        uint8 utilityIndex,
+        uint16 index,
+        MultiAsset memory feeAsset,
+        Weight memory weight,
+        bytes memory call
+    ) external;
 }
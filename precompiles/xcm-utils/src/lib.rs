@@ -18,37 +18,45 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use address_unification::EVMAddressMapping;
 use fp_evm::PrecompileHandle;
 use frame_support::{
 	dispatch::{GetDispatchInfo, PostDispatchInfo},
-	traits::ConstU32,
+	traits::{ConstU32, EnsureOrigin, Get},
 };
 use pallet_evm::AddressMapping;
-use parity_scale_codec::{Decode, DecodeLimit, MaxEncodedLen};
+use parity_scale_codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
 use precompile_utils::{precompile_set::SelectorFilter, prelude::*};
 use sp_core::{H160, U256};
 use sp_runtime::traits::Dispatchable;
 use sp_std::{boxed::Box, marker::PhantomData, vec, vec::Vec};
 use sp_weights::Weight;
 use xcm::{latest::prelude::*, VersionedXcm, MAX_XCM_DECODE_DEPTH};
-use xcm_executor::traits::{WeightBounds, WeightTrader};
+use xcm_executor::traits::{ConvertLocation, WeightBounds, WeightTrader};
 
 pub type CallOf<Runtime> = <Runtime as pallet_xcm::Config>::RuntimeCall;
 pub const XCM_SIZE_LIMIT: u32 = 2u32.pow(16);
 type GetXcmSizeLimit = ConstU32<XCM_SIZE_LIMIT>;
 
+/// Call index of `as_derivative` in the utility pallet.
+const AS_DERIVATIVE_CALL_INDEX: u8 = 1;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
 #[derive(Debug)]
-pub struct AllExceptXcmExecute<Runtime, XcmConfig>(PhantomData<(Runtime, XcmConfig)>);
+pub struct AllExceptXcmExecute<Runtime, XcmConfig, AddressMapper>(
+	PhantomData<(Runtime, XcmConfig, AddressMapper)>,
+);
 
-impl<Runtime, XcmConfig> SelectorFilter for AllExceptXcmExecute<Runtime, XcmConfig>
+impl<Runtime, XcmConfig, AddressMapper> SelectorFilter
+	for AllExceptXcmExecute<Runtime, XcmConfig, AddressMapper>
 where
 	Runtime: pallet_evm::Config + frame_system::Config + pallet_xcm::Config,
 	XcmConfig: xcm_executor::Config,
+	AddressMapper: EVMAddressMapping<Runtime::AccountId>,
 	<Runtime as frame_system::Config>::RuntimeCall:
 		Dispatchable<PostInfo = PostDispatchInfo> + Decode + GetDispatchInfo,
 	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
@@ -56,11 +64,11 @@ where
 	<Runtime as frame_system::Config>::RuntimeCall: From<pallet_xcm::Call<Runtime>>,
 {
 	fn is_allowed(_caller: H160, selector: Option<u32>) -> bool {
+		let xcm_execute_selectors =
+			XcmUtilsPrecompileCall::<Runtime, XcmConfig, AddressMapper>::xcm_execute_selectors();
 		match selector {
 			None => true,
-			Some(selector) =>
-				!XcmUtilsPrecompileCall::<Runtime, XcmConfig>::xcm_execute_selectors()
-					.contains(&selector),
+			Some(selector) => !xcm_execute_selectors.contains(&selector),
 		}
 	}
 
@@ -70,20 +78,41 @@ where
 }
 
 /// A precompile to wrap the functionality from xcm-utils
-pub struct XcmUtilsPrecompile<Runtime, XcmConfig>(PhantomData<(Runtime, XcmConfig)>);
+pub struct XcmUtilsPrecompile<Runtime, XcmConfig, AddressMapper>(
+	PhantomData<(Runtime, XcmConfig, AddressMapper)>,
+);
 
 #[precompile_utils::precompile]
-impl<Runtime, XcmConfig> XcmUtilsPrecompile<Runtime, XcmConfig>
+impl<Runtime, XcmConfig, AddressMapper> XcmUtilsPrecompile<Runtime, XcmConfig, AddressMapper>
 where
 	Runtime: pallet_evm::Config + frame_system::Config + pallet_xcm::Config,
 	XcmConfig: xcm_executor::Config,
+	AddressMapper: EVMAddressMapping<Runtime::AccountId>,
 	<Runtime as frame_system::Config>::RuntimeCall:
 		Dispatchable<PostInfo = PostDispatchInfo> + Decode + GetDispatchInfo,
 	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
 		From<Option<Runtime::AccountId>>,
 	<Runtime as frame_system::Config>::RuntimeCall: From<pallet_xcm::Call<Runtime>>,
 {
-	// Remove multilocationToAddress
+	#[precompile::public("locationToAddress((uint8,bytes[]))")]
+	#[precompile::public("location_to_address((uint8,bytes[]))")]
+	#[precompile::view]
+	fn location_to_address(
+		handle: &mut impl PrecompileHandle,
+		location: Location,
+	) -> EvmResult<Address> {
+		// storage item: EvmAddresses
+		// max encoded len: hash (16) + AccountId (32) + H160 (20)
+		handle.record_db_read::<Runtime>(68)?;
+
+		let account =
+			<Runtime as pallet_xcm::Config>::SovereignAccountOf::convert_location(&location)
+				.ok_or_else(|| {
+					RevertReason::custom("Failed location conversion").in_field("location")
+				})?;
+
+		Ok(Address(AddressMapper::get_evm_address_or_default(&account)))
+	}
 
 	#[precompile::public("getUnitsPerSecond((uint8,bytes[]))")]
 	#[precompile::public("get_units_per_second((uint8,bytes[]))")]
@@ -209,4 +238,108 @@ where
 
 		Ok(())
 	}
+
+	#[precompile::public(
+		"transactThroughSigned((uint8,bytes[]),((uint8,bytes[]),uint256),(uint64,uint64),bytes)"
+	)]
+	#[precompile::public(
+		"transact_through_signed((uint8,bytes[]),((uint8,bytes[]),uint256),(uint64,uint64),bytes)"
+	)]
+	fn transact_through_signed(
+		handle: &mut impl PrecompileHandle,
+		dest: Location,
+		fee_asset: EvmAsset,
+		weight: Weight,
+		call: BoundedBytes<GetXcmSizeLimit>,
+	) -> EvmResult {
+		Self::transact_through(handle, dest, fee_asset, weight, call.into())
+	}
+
+	#[precompile::public(
+		"transactThroughDerivative((uint8,bytes[]),uint8,uint16,((uint8,bytes[]),uint256),(uint64,uint64),bytes)"
+	)]
+	#[precompile::public(
+		"transact_through_derivative((uint8,bytes[]),uint8,uint16,((uint8,bytes[]),uint256),(uint64,uint64),bytes)"
+	)]
+	fn transact_through_derivative(
+		handle: &mut impl PrecompileHandle,
+		dest: Location,
+		utility_index: u8,
+		index: u16,
+		fee_asset: EvmAsset,
+		weight: Weight,
+		call: BoundedBytes<GetXcmSizeLimit>,
+	) -> EvmResult {
+		// `utility.as_derivative(index, call)` in the destination runtime
+		let mut derivative_call = (utility_index, AS_DERIVATIVE_CALL_INDEX, index).encode();
+		derivative_call.extend(Vec::<u8>::from(call));
+
+		Self::transact_through(handle, dest, fee_asset, weight, derivative_call)
+	}
+
+	/// Sends a message to `dest` which buys execution with `fee_asset` and dispatches `call`
+	/// from the account derived for the caller. Unspent fees are deposited back to that account.
+	fn transact_through(
+		handle: &mut impl PrecompileHandle,
+		dest: Location,
+		fee_asset: EvmAsset,
+		weight: Weight,
+		call: Vec<u8>,
+	) -> EvmResult {
+		let amount: u128 = fee_asset
+			.amount
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("feeAsset"))?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let universal_location = <Runtime as pallet_xcm::Config>::UniversalLocation::get();
+
+		let fees = Asset::from((fee_asset.location, amount))
+			.reanchored(&dest, &universal_location)
+			.map_err(|_| {
+				RevertReason::custom("Failed reanchoring fee asset").in_field("feeAsset")
+			})?;
+
+		// The location of the caller, as seen by the destination.
+		let signed_origin: <Runtime as frame_system::Config>::RuntimeOrigin =
+			frame_system::RawOrigin::Signed(origin.clone()).into();
+		let beneficiary =
+			<Runtime as pallet_xcm::Config>::SendXcmOrigin::ensure_origin(signed_origin.into())
+				.map_err(|_| revert("Failed origin conversion"))?
+				.reanchored(&dest, &universal_location)
+				.map_err(|_| RevertReason::custom("Failed reanchoring origin").in_field("dest"))?;
+
+		let message = Xcm::<()>(vec![
+			WithdrawAsset(fees.clone().into()),
+			BuyExecution { fees, weight_limit: Unlimited },
+			Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				require_weight_at_most: weight,
+				call: call.into(),
+			},
+			RefundSurplus,
+			DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+		]);
+
+		let call = pallet_xcm::Call::<Runtime>::send {
+			dest: Box::new(dest.into()),
+			message: Box::new(VersionedXcm::from(message)),
+		};
+
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+}
+
+#[derive(solidity::Codec)]
+pub struct EvmAsset {
+	location: Location,
+	amount: U256,
+}
+
+impl From<(Location, U256)> for EvmAsset {
+	fn from(tuple: (Location, U256)) -> Self {
+		EvmAsset { location: tuple.0, amount: tuple.1 }
+	}
 }
@@ -77,6 +77,30 @@ pub type LocationToAccountId = (
 	xcm_builder::AccountId32Aliases<LocalNetworkId, AccountId>,
 );
 
+/// Uses the EVM address embedded in the mocked accounts.
+pub struct MockAddressMapper;
+impl EVMAddressMapping<AccountId> for MockAddressMapper {
+	fn get_account_id_or_default(evm: &H160) -> AccountId {
+		AccountId::into_account_id(*evm)
+	}
+
+	fn get_detault_account_id(evm: &H160) -> AccountId {
+		AccountId::into_account_id(*evm)
+	}
+
+	fn get_evm_address_or_default(account_id: &AccountId) -> H160 {
+		(*account_id).into()
+	}
+
+	fn get_detault_evm_address(account_id: &AccountId) -> H160 {
+		(*account_id).into()
+	}
+
+	fn is_linked(account_id: &AccountId, evm: &H160) -> bool {
+		H160::from(*account_id) == *evm
+	}
+}
+
 pub struct AccountIdToLocation;
 impl sp_runtime::traits::Convert<AccountId, Location> for AccountIdToLocation {
 	fn convert(account: AccountId) -> Location {
@@ -176,7 +200,7 @@ impl pallet_xcm::Config for Runtime {
 	type Currency = Balances;
 	type CurrencyMatcher = IsConcrete<MatcherLocation>;
 	type TrustedLockers = ();
-	type SovereignAccountOf = ();
+	type SovereignAccountOf = LocationToAccountId;
 	type MaxLockers = ConstU32<8>;
 	type WeightInfo = pallet_xcm::TestWeightInfo;
 	type MaxRemoteLockConsumers = ConstU32<0>;
@@ -190,13 +214,13 @@ pub type Precompiles<R> = PrecompileSetBuilder<
 	(
 		PrecompileAt<
 			AddressU64<1>,
-			XcmUtilsPrecompile<R, XcmConfig>,
-			CallableByContract<AllExceptXcmExecute<R, XcmConfig>>,
+			XcmUtilsPrecompile<R, XcmConfig, MockAddressMapper>,
+			CallableByContract<AllExceptXcmExecute<R, XcmConfig, MockAddressMapper>>,
 		>,
 	),
 >;
 
-pub type PCall = XcmUtilsPrecompileCall<Runtime, XcmConfig>;
+pub type PCall = XcmUtilsPrecompileCall<Runtime, XcmConfig, MockAddressMapper>;
 
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 /// Block storage limit in bytes. Set to 40 KB.
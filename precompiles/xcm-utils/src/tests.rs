@@ -29,7 +29,7 @@ use crate::mock::{
 };
 use frame_support::{traits::PalletInfo, weights::Weight};
 use parity_scale_codec::Encode;
-use precompile_utils::testing::*;
+use precompile_utils::{prelude::Address, testing::*};
 use sp_core::{H160, U256};
 use xcm::prelude::*;
 
@@ -41,6 +41,9 @@ fn precompiles() -> Precompiles<Runtime> {
 fn test_selector_enum() {
 	assert!(PCall::weight_message_selectors().contains(&0x25d54154));
 	assert!(PCall::get_units_per_second_selectors().contains(&0x3f0f65db));
+	assert!(PCall::location_to_address_selectors().contains(&0xa008cd8a));
+	assert!(PCall::transact_through_signed_selectors().contains(&0x1a1838eb));
+	assert!(PCall::transact_through_derivative_selectors().contains(&0x8cf2386b));
 }
 
 #[test]
@@ -54,6 +57,9 @@ fn modifiers() {
 
 		tester.test_view_modifier(PCall::weight_message_selectors());
 		tester.test_view_modifier(PCall::get_units_per_second_selectors());
+		tester.test_view_modifier(PCall::location_to_address_selectors());
+		tester.test_default_modifier(PCall::transact_through_signed_selectors());
+		tester.test_default_modifier(PCall::transact_through_derivative_selectors());
 	});
 }
 
@@ -193,6 +199,119 @@ fn test_send_clear_origin() {
 	})
 }
 
+#[test]
+fn test_location_to_address() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::location_to_address { location: Location::parent() };
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.expect_cost(1)
+			.expect_no_logs()
+			.execute_returns(Address(MockPeaqAccount::ParentAccount.into()));
+	});
+}
+
+#[test]
+fn test_location_to_address_fails_for_unknown_location() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::location_to_address { location: Location::new(1, [Parachain(4000)]) };
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"location: Failed location conversion");
+	});
+}
+
+#[test]
+fn test_transact_through_signed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = vec![4u8, 0, 1, 2];
+		let weight = Weight::from_parts(1_000_000, 10_000);
+		let input = PCall::transact_through_signed {
+			dest: Location::parent(),
+			fee_asset: (Location::parent(), U256::from(500)).into(),
+			weight,
+			call: call.clone().into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.expect_no_logs()
+			.execute_returns(());
+
+		let sent_messages = sent_xcm();
+		let (dest, sent_message) = sent_messages.first().unwrap();
+		let fees: Asset = (Location::here(), 500u128).into();
+		// The caller, as seen by the relay chain.
+		let beneficiary =
+			Location::new(0, [Parachain(100), AccountId32 { network: None, id: [0xAA; 32] }]);
+
+		assert_eq!(*dest, Location::parent());
+		assert_eq!(
+			sent_message.0,
+			vec![
+				DescendOrigin(AccountId32 { network: None, id: [0xAA; 32] }.into()),
+				WithdrawAsset(fees.clone().into()),
+				BuyExecution { fees, weight_limit: Unlimited },
+				Transact {
+					origin_kind: OriginKind::SovereignAccount,
+					require_weight_at_most: weight,
+					call: call.into(),
+				},
+				RefundSurplus,
+				DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+			]
+		);
+	});
+}
+
+#[test]
+fn test_transact_through_derivative() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transact_through_derivative {
+			dest: Location::parent(),
+			utility_index: 24,
+			index: 3,
+			fee_asset: (Location::parent(), U256::from(500)).into(),
+			weight: Weight::from_parts(1_000_000, 10_000),
+			call: vec![4u8, 0, 1, 2].into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.expect_no_logs()
+			.execute_returns(());
+
+		let sent_messages = sent_xcm();
+		let (_, sent_message) = sent_messages.first().unwrap();
+		let transact_call = sent_message.0.iter().find_map(|instruction| match instruction {
+			Transact { call, .. } => Some(call.clone().into_encoded()),
+			_ => None,
+		});
+
+		// `as_derivative` call of the utility pallet, with the derivative index and inner call.
+		assert_eq!(transact_call, Some(vec![24u8, 1, 3, 0, 4, 0, 1, 2]));
+	});
+}
+
+#[test]
+fn test_transact_through_signed_fails_if_fee_amount_overflows() {
+	ExtBuilder::default().build().execute_with(|| {
+		let input = PCall::transact_through_signed {
+			dest: Location::parent(),
+			fee_asset: (Location::parent(), U256::MAX).into(),
+			weight: Weight::from_parts(1_000_000, 10_000),
+			call: vec![4u8, 0, 1, 2].into(),
+		};
+
+		precompiles()
+			.prepare_test(MockPeaqAccount::Alice, MockPeaqAccount::EVMu1Account, input)
+			.execute_reverts(|output| output == b"feeAsset: Value is too large for balance type");
+		assert!(sent_xcm().is_empty());
+	});
+}
+
 #[test]
 fn execute_fails_if_called_by_smart_contract() {
 	ExtBuilder::default()
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The ZenlinkDex contract's address.
+address constant ZENLINK_DEX_ADDRESS = 0x000000000000000000000000000000000000080B;
+
+/// @dev The ZenlinkDex contract's instance.
+ZenlinkDex constant ZENLINK_DEX_CONTRACT = ZenlinkDex(ZENLINK_DEX_ADDRESS);
+
+/// @author The Peaq Team
+/// @title Zenlink DEX Interface
+/// The interface through which solidity contracts will swap assets and provide liquidity on
+/// the Zenlink DEX. Assets are identified by their peaq asset id, 0 being the native token.
+/// @custom:address 0x000000000000000000000000000000000000080B
+interface ZenlinkDex {
+    /// Returns the amounts obtained along the path when selling `amountIn` of its first asset
+    /// @custom:selector f62d5f32
+    /// @param amountIn The amount of the first asset of the path
+    /// @param path The asset ids to swap through, at most 8
+    /// @return The amount of each asset along the path
+    function getAmountOutByPath(uint256 amountIn, uint64[] memory path)
+        external
+        view
+        returns (uint256[] memory);
+
+    /// Returns the amounts needed along the path to buy `amountOut` of its last asset
+    /// @custom:selector 00e69539
+    /// @param amountOut The amount of the last asset of the path
+    /// @param path The asset ids to swap through, at most 8
+    /// @return The amount of each asset along the path
+    function getAmountInByPath(uint256 amountOut, uint64[] memory path)
+        external
+        view
+        returns (uint256[] memory);
+
+    /// Returns the reserves of a pair, in the order the assets are given
+    /// @custom:selector 748445e4
+    /// @param asset0 The first asset id of the pair
+    /// @param asset1 The second asset id of the pair
+    function getReserves(uint64 asset0, uint64 asset1)
+        external
+        view
+        returns (uint256 reserve0, uint256 reserve1);
+
+    /// Sells an exact amount of the first asset of the path
+    /// @custom:selector 5d530d88
+    /// @param amountIn The amount of the first asset of the path to sell
+    /// @param amountOutMin The minimum amount of the last asset of the path to receive
+    /// @param path The asset ids to swap through, at most 8
+    /// @param recipient The account receiving the last asset of the path
+    /// @param deadline The block number after which the swap is rejected
+    /// @return The amount of each asset along the path
+    function swapExactAssetsForAssets(
+        uint256 amountIn,
+        uint256 amountOutMin,
+        uint64[] memory path,
+        address recipient,
+        uint32 deadline
+    ) external returns (uint256[] memory);
+
+    /// Buys an exact amount of the last asset of the path
+    /// @custom:selector c45c6d71
+    /// @param amountOut The amount of the last asset of the path to receive
+    /// @param amountInMax The maximum amount of the first asset of the path to sell
+    /// @param path The asset ids to swap through, at most 8
+    /// @param recipient The account receiving the last asset of the path
+    /// @param deadline The block number after which the swap is rejected
+    /// @return The amount of each asset along the path
+    function swapAssetsForExactAssets(
+        uint256 amountOut,
+        uint256 amountInMax,
+        uint64[] memory path,
+        address recipient,
+        uint32 deadline
+    ) external returns (uint256[] memory);
+
+    /// Provides liquidity to an existing pair
+    /// @custom:selector 62cb2e93
+    /// @param asset0 The first asset id of the pair
+    /// @param asset1 The second asset id of the pair
+    /// @param amount0Desired The amount of the first asset to provide if possible
+    /// @param amount1Desired The amount of the second asset to provide if possible
+    /// @param amount0Min The minimum amount of the first asset to provide
+    /// @param amount1Min The minimum amount of the second asset to provide
+    /// @param deadline The block number after which the call is rejected
+    function addLiquidity(
+        uint64 asset0,
+        uint64 asset1,
+        uint256 amount0Desired,
+        uint256 amount1Desired,
+        uint256 amount0Min,
+        uint256 amount1Min,
+        uint32 deadline
+    )
+        external
+        returns (
+            uint256 amount0,
+            uint256 amount1,
+            uint256 liquidity
+        );
+
+    /// Burns liquidity of a pair and withdraws the underlying assets
+    /// @custom:selector 98e5836d
+    /// @param asset0 The first asset id of the pair
+    /// @param asset1 The second asset id of the pair
+    /// @param liquidity The amount of liquidity to burn
+    /// @param amount0Min The minimum amount of the first asset to receive
+    /// @param amount1Min The minimum amount of the second asset to receive
+    /// @param recipient The account receiving the assets
+    /// @param deadline The block number after which the call is rejected
+    function removeLiquidity(
+        uint64 asset0,
+        uint64 asset1,
+        uint256 liquidity,
+        uint256 amount0Min,
+        uint256 amount1Min,
+        address recipient,
+        uint32 deadline
+    ) external returns (uint256 amount0, uint256 amount1);
+
+    /// @dev Emitted when assets are swapped
+    /// @param sender The account selling the assets
+    /// @param recipient The account receiving the assets
+    /// @param path The asset ids swapped through
+    /// @param amounts The amount of each asset along the path
+    event AssetSwap(
+        address indexed sender,
+        address indexed recipient,
+        uint64[] path,
+        uint256[] amounts
+    );
+
+    /// @dev Emitted when liquidity is provided
+    /// @param sender The account providing the liquidity
+    /// @param asset0 The first asset id of the pair
+    /// @param asset1 The second asset id of the pair
+    /// @param amount0 The amount of the first asset provided
+    /// @param amount1 The amount of the second asset provided
+    /// @param liquidity The amount of liquidity minted
+    event LiquidityAdded(
+        address indexed sender,
+        uint64 asset0,
+        uint64 asset1,
+        uint256 amount0,
+        uint256 amount1,
+        uint256 liquidity
+    );
+
+    /// @dev Emitted when liquidity is removed
+    /// @param sender The account burning the liquidity
+    /// @param recipient The account receiving the assets
+    /// @param asset0 The first asset id of the pair
+    /// @param asset1 The second asset id of the pair
+    /// @param amount0 The amount of the first asset withdrawn
+    /// @param amount1 The amount of the second asset withdrawn
+    /// @param liquidity The amount of liquidity burned
+    event LiquidityRemoved(
+        address indexed sender,
+        address indexed recipient,
+        uint64 asset0,
+        uint64 asset1,
+        uint256 amount0,
+        uint256 amount1,
+        uint256 liquidity
+    );
+}
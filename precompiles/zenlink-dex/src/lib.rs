@@ -0,0 +1,400 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Precompile to swap assets and provide liquidity on the Zenlink DEX via the EVM
+//!
+//! Assets are identified by their `StorageAssetId`, the same id used by the assets factory and
+//! the ERC-20 assets precompiles, and converted into Zenlink asset ids with
+//! `AssetIdToZenlinkId`. The native token is the asset `0`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::{Log, PrecompileHandle};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::ConstU32,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_evm::AddressMapping;
+use peaq_primitives_xcm::{AssetIdToZenlinkId, StorageAssetId};
+use precompile_utils::{keccak256, prelude::*};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{Convert, Dispatchable, StaticLookup};
+use sp_std::{marker::PhantomData, vec::Vec};
+use zenlink_protocol::{
+	AssetBalance, AssetId as ZenlinkAssetId, ExportZenlink, MultiAssetsHandler,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the AssetSwap log.
+pub const SELECTOR_LOG_ASSET_SWAP: [u8; 32] =
+	keccak256!("AssetSwap(address,address,uint64[],uint256[])");
+
+/// Solidity selector of the LiquidityAdded log.
+pub const SELECTOR_LOG_LIQUIDITY_ADDED: [u8; 32] =
+	keccak256!("LiquidityAdded(address,uint64,uint64,uint256,uint256,uint256)");
+
+/// Solidity selector of the LiquidityRemoved log.
+pub const SELECTOR_LOG_LIQUIDITY_REMOVED: [u8; 32] =
+	keccak256!("LiquidityRemoved(address,address,uint64,uint64,uint256,uint256,uint256)");
+
+/// Maximum number of assets in a swap path.
+pub const MAX_PATH_LEN: u32 = 8;
+type GetPathLimit = ConstU32<MAX_PATH_LEN>;
+
+/// Storage reads needed to price one hop of a path: the pair status and both reserves.
+const READS_PER_HOP: u64 = 3;
+
+/// A precompile to swap assets and provide liquidity on the Zenlink DEX
+pub struct ZenlinkDexPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> ZenlinkDexPrecompile<Runtime>
+where
+	Runtime: zenlink_protocol::Config<AssetId = ZenlinkAssetId> + pallet_evm::Config,
+	zenlink_protocol::Pallet<Runtime>: ExportZenlink<Runtime::AccountId, ZenlinkAssetId>,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<zenlink_protocol::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BlockNumberFor<Runtime>: From<u32>,
+{
+	#[precompile::public("getAmountOutByPath(uint256,uint64[])")]
+	#[precompile::public("get_amount_out_by_path(uint256,uint64[])")]
+	#[precompile::view]
+	fn get_amount_out_by_path(
+		handle: &mut impl PrecompileHandle,
+		amount_in: U256,
+		path: BoundedVec<u64, GetPathLimit>,
+	) -> EvmResult<Vec<U256>> {
+		let amount_in = Self::asset_balance(amount_in, "amountIn")?;
+		let path = Self::zenlink_path(handle, path.into())?;
+
+		Self::amounts_out(amount_in, &path)
+	}
+
+	#[precompile::public("getAmountInByPath(uint256,uint64[])")]
+	#[precompile::public("get_amount_in_by_path(uint256,uint64[])")]
+	#[precompile::view]
+	fn get_amount_in_by_path(
+		handle: &mut impl PrecompileHandle,
+		amount_out: U256,
+		path: BoundedVec<u64, GetPathLimit>,
+	) -> EvmResult<Vec<U256>> {
+		let amount_out = Self::asset_balance(amount_out, "amountOut")?;
+		let path = Self::zenlink_path(handle, path.into())?;
+
+		Self::amounts_in(amount_out, &path)
+	}
+
+	#[precompile::public("getReserves(uint64,uint64)")]
+	#[precompile::public("get_reserves(uint64,uint64)")]
+	#[precompile::view]
+	fn get_reserves(
+		handle: &mut impl PrecompileHandle,
+		asset0: u64,
+		asset1: u64,
+	) -> EvmResult<(U256, U256)> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * READS_PER_HOP)?;
+
+		let asset_0 = Self::zenlink_asset_id(asset0, "asset0")?;
+		let asset_1 = Self::zenlink_asset_id(asset1, "asset1")?;
+
+		let pair = zenlink_protocol::Pallet::<Runtime>::get_pair_by_asset_id(asset_0, asset_1)
+			.ok_or_else(|| revert("Pair not found"))?;
+
+		// The pair is stored with sorted assets, reserves are returned in the requested order.
+		let (reserve0, reserve1) = if pair.asset_0 == asset_0 {
+			(pair.reserve_0, pair.reserve_1)
+		} else {
+			(pair.reserve_1, pair.reserve_0)
+		};
+
+		Ok((reserve0.into(), reserve1.into()))
+	}
+
+	#[precompile::public("swapExactAssetsForAssets(uint256,uint256,uint64[],address,uint32)")]
+	#[precompile::public("swap_exact_assets_for_assets(uint256,uint256,uint64[],address,uint32)")]
+	fn swap_exact_assets_for_assets(
+		handle: &mut impl PrecompileHandle,
+		amount_in: U256,
+		amount_out_min: U256,
+		path: BoundedVec<u64, GetPathLimit>,
+		recipient: Address,
+		deadline: u32,
+	) -> EvmResult<Vec<U256>> {
+		let amount_in = Self::asset_balance(amount_in, "amountIn")?;
+		let amount_out_min = Self::asset_balance(amount_out_min, "amountOutMin")?;
+		let storage_path: Vec<u64> = path.into();
+		let path = Self::zenlink_path(handle, storage_path.clone())?;
+
+		let amounts = Self::amounts_out(amount_in, &path)?;
+		let event = Self::asset_swap_log(handle, recipient, storage_path, amounts.clone());
+		handle.record_log_costs(&[&event])?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let recipient = Runtime::AddressMapping::into_account_id(recipient.into());
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			zenlink_protocol::Call::<Runtime>::swap_exact_assets_for_assets {
+				amount_in,
+				amount_out_min,
+				path,
+				recipient: Runtime::Lookup::unlookup(recipient),
+				deadline: deadline.into(),
+			},
+			0,
+		)?;
+
+		event.record(handle)?;
+
+		Ok(amounts)
+	}
+
+	#[precompile::public("swapAssetsForExactAssets(uint256,uint256,uint64[],address,uint32)")]
+	#[precompile::public("swap_assets_for_exact_assets(uint256,uint256,uint64[],address,uint32)")]
+	fn swap_assets_for_exact_assets(
+		handle: &mut impl PrecompileHandle,
+		amount_out: U256,
+		amount_in_max: U256,
+		path: BoundedVec<u64, GetPathLimit>,
+		recipient: Address,
+		deadline: u32,
+	) -> EvmResult<Vec<U256>> {
+		let amount_out = Self::asset_balance(amount_out, "amountOut")?;
+		let amount_in_max = Self::asset_balance(amount_in_max, "amountInMax")?;
+		let storage_path: Vec<u64> = path.into();
+		let path = Self::zenlink_path(handle, storage_path.clone())?;
+
+		let amounts = Self::amounts_in(amount_out, &path)?;
+		let event = Self::asset_swap_log(handle, recipient, storage_path, amounts.clone());
+		handle.record_log_costs(&[&event])?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let recipient = Runtime::AddressMapping::into_account_id(recipient.into());
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			zenlink_protocol::Call::<Runtime>::swap_assets_for_exact_assets {
+				amount_out,
+				amount_in_max,
+				path,
+				recipient: Runtime::Lookup::unlookup(recipient),
+				deadline: deadline.into(),
+			},
+			0,
+		)?;
+
+		event.record(handle)?;
+
+		Ok(amounts)
+	}
+
+	#[precompile::public("addLiquidity(uint64,uint64,uint256,uint256,uint256,uint256,uint32)")]
+	#[precompile::public("add_liquidity(uint64,uint64,uint256,uint256,uint256,uint256,uint32)")]
+	#[allow(clippy::too_many_arguments)]
+	fn add_liquidity(
+		handle: &mut impl PrecompileHandle,
+		asset0: u64,
+		asset1: u64,
+		amount0_desired: U256,
+		amount1_desired: U256,
+		amount0_min: U256,
+		amount1_min: U256,
+		deadline: u32,
+	) -> EvmResult<(U256, U256, U256)> {
+		handle.record_log_costs_manual(2, 32 * 5)?;
+
+		let asset_0 = Self::zenlink_asset_id(asset0, "asset0")?;
+		let asset_1 = Self::zenlink_asset_id(asset1, "asset1")?;
+		let amount_0_desired = Self::asset_balance(amount0_desired, "amount0Desired")?;
+		let amount_1_desired = Self::asset_balance(amount1_desired, "amount1Desired")?;
+		let amount_0_min = Self::asset_balance(amount0_min, "amount0Min")?;
+		let amount_1_min = Self::asset_balance(amount1_min, "amount1Min")?;
+
+		// The amounts taken from the caller are only known once the liquidity is added.
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * READS_PER_HOP)?;
+		let lp_asset = zenlink_protocol::Pallet::<Runtime>::get_pair_by_asset_id(asset_0, asset_1)
+			.ok_or_else(|| revert("Pair not found"))?
+			.lp_asset_id;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let balances_before = Self::balances_of(handle, &origin, [asset_0, asset_1, lp_asset])?;
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin.clone()).into(),
+			zenlink_protocol::Call::<Runtime>::add_liquidity {
+				asset_0,
+				asset_1,
+				amount_0_desired,
+				amount_1_desired,
+				amount_0_min,
+				amount_1_min,
+				deadline: deadline.into(),
+			},
+			0,
+		)?;
+
+		let balances_after = Self::balances_of(handle, &origin, [asset_0, asset_1, lp_asset])?;
+		let amount0 = balances_before[0].saturating_sub(balances_after[0]);
+		let amount1 = balances_before[1].saturating_sub(balances_after[1]);
+		let liquidity = balances_after[2].saturating_sub(balances_before[2]);
+
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_LIQUIDITY_ADDED,
+			handle.context().caller,
+			solidity::encode_event_data((
+				asset0,
+				asset1,
+				U256::from(amount0),
+				U256::from(amount1),
+				U256::from(liquidity),
+			)),
+		)
+		.record(handle)?;
+
+		Ok((amount0.into(), amount1.into(), liquidity.into()))
+	}
+
+	#[precompile::public("removeLiquidity(uint64,uint64,uint256,uint256,uint256,address,uint32)")]
+	#[precompile::public("remove_liquidity(uint64,uint64,uint256,uint256,uint256,address,uint32)")]
+	#[allow(clippy::too_many_arguments)]
+	fn remove_liquidity(
+		handle: &mut impl PrecompileHandle,
+		asset0: u64,
+		asset1: u64,
+		liquidity: U256,
+		amount0_min: U256,
+		amount1_min: U256,
+		recipient: Address,
+		deadline: u32,
+	) -> EvmResult<(U256, U256)> {
+		handle.record_log_costs_manual(3, 32 * 5)?;
+
+		let asset_0 = Self::zenlink_asset_id(asset0, "asset0")?;
+		let asset_1 = Self::zenlink_asset_id(asset1, "asset1")?;
+		let liquidity = Self::asset_balance(liquidity, "liquidity")?;
+		let amount_0_min = Self::asset_balance(amount0_min, "amount0Min")?;
+		let amount_1_min = Self::asset_balance(amount1_min, "amount1Min")?;
+
+		// The amounts sent to the recipient are only known once the liquidity is removed.
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let recipient_address: H160 = recipient.into();
+		let recipient = Runtime::AddressMapping::into_account_id(recipient_address);
+		let balances_before = Self::balances_of(handle, &recipient, [asset_0, asset_1])?;
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			zenlink_protocol::Call::<Runtime>::remove_liquidity {
+				asset_0,
+				asset_1,
+				liquidity,
+				amount_0_min,
+				amount_1_min,
+				recipient: Runtime::Lookup::unlookup(recipient.clone()),
+				deadline: deadline.into(),
+			},
+			0,
+		)?;
+
+		let balances_after = Self::balances_of(handle, &recipient, [asset_0, asset_1])?;
+		let amount0 = balances_after[0].saturating_sub(balances_before[0]);
+		let amount1 = balances_after[1].saturating_sub(balances_before[1]);
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_LIQUIDITY_REMOVED,
+			handle.context().caller,
+			recipient_address,
+			solidity::encode_event_data((
+				asset0,
+				asset1,
+				U256::from(amount0),
+				U256::from(amount1),
+				U256::from(liquidity),
+			)),
+		)
+		.record(handle)?;
+
+		Ok((amount0.into(), amount1.into()))
+	}
+
+	fn zenlink_asset_id(id: StorageAssetId, field: &str) -> MayRevert<ZenlinkAssetId> {
+		AssetIdToZenlinkId::<<Runtime as zenlink_protocol::Config>::SelfParaId>::convert(id)
+			.ok_or_else(|| RevertReason::custom("Invalid asset id").in_field(field))
+	}
+
+	fn zenlink_path(
+		handle: &mut impl PrecompileHandle,
+		path: Vec<StorageAssetId>,
+	) -> EvmResult<Vec<ZenlinkAssetId>> {
+		if path.len() < 2 {
+			return Err(RevertReason::custom("Path too short").in_field("path").into());
+		}
+		let hops = path.len() as u64 - 1;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * READS_PER_HOP * hops)?;
+
+		path.into_iter()
+			.enumerate()
+			.map(|(index, id)| {
+				AssetIdToZenlinkId::<<Runtime as zenlink_protocol::Config>::SelfParaId>::convert(id)
+					.ok_or_else(|| {
+						RevertReason::custom("Invalid asset id")
+							.in_array(index)
+							.in_field("path")
+							.into()
+					})
+			})
+			.collect()
+	}
+
+	fn asset_balance(value: U256, field: &str) -> MayRevert<AssetBalance> {
+		value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field(field))
+	}
+
+	fn amounts_out(amount_in: AssetBalance, path: &[ZenlinkAssetId]) -> EvmResult<Vec<U256>> {
+		let amounts = zenlink_protocol::Pallet::<Runtime>::get_amount_out_by_path(amount_in, path)
+			.map_err(|_| revert("Cannot compute amounts for path"))?;
+		Ok(amounts.into_iter().map(Into::into).collect())
+	}
+
+	fn amounts_in(amount_out: AssetBalance, path: &[ZenlinkAssetId]) -> EvmResult<Vec<U256>> {
+		let amounts = zenlink_protocol::Pallet::<Runtime>::get_amount_in_by_path(amount_out, path)
+			.map_err(|_| revert("Cannot compute amounts for path"))?;
+		Ok(amounts.into_iter().map(Into::into).collect())
+	}
+
+	fn balances_of<const N: usize>(
+		handle: &mut impl PrecompileHandle,
+		who: &Runtime::AccountId,
+		assets: [ZenlinkAssetId; N],
+	) -> EvmResult<[AssetBalance; N]> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * N as u64)?;
+		Ok(assets.map(|asset| {
+			<Runtime as zenlink_protocol::Config>::MultiAssetsHandler::balance_of(asset, who)
+		}))
+	}
+
+	fn asset_swap_log(
+		handle: &mut impl PrecompileHandle,
+		recipient: Address,
+		path: Vec<StorageAssetId>,
+		amounts: Vec<U256>,
+	) -> Log {
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_ASSET_SWAP,
+			handle.context().caller,
+			recipient.0,
+			solidity::encode_event_data((path, amounts)),
+		)
+	}
+}
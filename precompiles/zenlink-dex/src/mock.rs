@@ -0,0 +1,320 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+//! Test utilities
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU32, Everything},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use pallet_evm::{EnsureAddressNever, EnsureAddressRoot};
+use peaq_primitives_xcm::{AssetId as PeaqAssetId, NATIVE_ASSET_ID};
+use precompile_utils::precompile_set::*;
+use runtime_common::{
+	LocalAssetAdaptor, PeaqAssetZenlinkLpGenerate, PeaqMultiCurrenciesWrapper,
+	PeaqNativeCurrencyWrapper,
+};
+use sp_core::{crypto::AccountId32, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+use zenlink_protocol::ZenlinkMultiAssets;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+
+/// Local token with the `StorageAssetId` 1.
+pub const TOKEN: StorageAssetId = 1;
+/// Zenlink pair fee rate, 0.3%.
+pub const FEE_RATE: u128 = 30;
+
+pub fn alice() -> H160 {
+	H160::repeat_byte(0xAA)
+}
+
+pub fn bob() -> H160 {
+	H160::repeat_byte(0xBB)
+}
+
+pub fn precompile_address() -> H160 {
+	H160::from_low_u64_be(1)
+}
+
+/// Maps an EVM address to the account whose first 20 bytes are the address.
+pub struct MockAddressMapping;
+impl AddressMapping<AccountId> for MockAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		let mut data = [0u8; 32];
+		data[0..20].copy_from_slice(address.as_bytes());
+		AccountId::new(data)
+	}
+}
+
+pub fn account(address: H160) -> AccountId {
+	MockAddressMapping::into_account_id(address)
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type RuntimeTask = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+	type MaxLocks = ();
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 0;
+	pub const AssetAccountDeposit: Balance = 0;
+	pub const ApprovalDeposit: Balance = 0;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 0;
+	pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = StorageAssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	type RemoveItemsLimit = ConstU32<0>;
+	type AssetIdParameter = StorageAssetId;
+	type CallbackHandle = ();
+}
+
+parameter_types! {
+	pub const SelfParaId: u32 = 2000;
+	pub const ZenlinkDexPalletId: PalletId = PalletId(*b"zenlkpro");
+	pub const GetNativeAssetId: StorageAssetId = NATIVE_ASSET_ID;
+	pub AssetAdmin: AccountId = account(H160::repeat_byte(0xAD));
+}
+
+type MultiCurrencies = PeaqMultiCurrenciesWrapper<
+	Runtime,
+	Assets,
+	PeaqNativeCurrencyWrapper<Balances>,
+	GetNativeAssetId,
+>;
+
+pub type MultiAssets = ZenlinkMultiAssets<
+	ZenlinkProtocol,
+	Balances,
+	LocalAssetAdaptor<MultiCurrencies, PeaqAssetId, StorageAssetId>,
+>;
+
+impl zenlink_protocol::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkDexPalletId;
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PeaqAssetZenlinkLpGenerate<Self, Assets, ExistentialDeposit, AssetAdmin>;
+	type TargetChains = ();
+	type SelfParaId = SelfParaId;
+	type WeightInfo = ();
+	type ControlOrigin = EnsureRoot<AccountId>;
+}
+
+const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+/// Block storage limit in bytes. Set to 40 KB.
+const BLOCK_STORAGE_LIMIT: u64 = 40 * 1024;
+
+pub type PCall = ZenlinkDexPrecompileCall<Runtime>;
+
+pub type Precompiles<R> =
+	PrecompileSetBuilder<R, (PrecompileAt<AddressU64<1>, ZenlinkDexPrecompile<R>>,)>;
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(u64::MAX);
+	pub PrecompilesValue: Precompiles<Runtime> = Precompiles::new();
+
+	pub WeightPerGas: Weight = Weight::from_parts(1, 0);
+	pub GasLimitPovSizeRatio: u64 = {
+		let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+		block_gas_limit.saturating_div(MAX_POV_SIZE)
+	};
+	pub GasLimitStorageGrowthRatio: u64 = {
+		let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
+		block_gas_limit.saturating_div(BLOCK_STORAGE_LIMIT)
+	};
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type CallOrigin = EnsureAddressRoot<AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<AccountId>;
+	type AddressMapping = MockAddressMapping;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type PrecompilesType = Precompiles<Self>;
+	type PrecompilesValue = PrecompilesValue;
+	type ChainId = ();
+	type OnChargeTransaction = ();
+	type BlockGasLimit = BlockGasLimit;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type FindAuthor = ();
+	type OnCreate = ();
+	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
+	type Timestamp = Timestamp;
+	type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
+	type SuicideQuickClearLimit = ();
+}
+
+construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		Evm: pallet_evm,
+		Timestamp: pallet_timestamp,
+		ZenlinkProtocol: zenlink_protocol,
+	}
+);
+
+pub(crate) struct ExtBuilder {
+	// endowed accounts with native balances
+	balances: Vec<(AccountId, Balance)>,
+	// endowed accounts with balances of `TOKEN`
+	token_balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![(account(alice()), 10_000_000), (account(bob()), 10_000_000)],
+			token_balances: vec![(account(alice()), 10_000_000), (account(bob()), 10_000_000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.expect("Frame system builds valid default genesis config");
+
+		pallet_balances::GenesisConfig::<Runtime> { balances: self.balances }
+			.assimilate_storage(&mut t)
+			.expect("Pallet balances storage can be assimilated");
+
+		pallet_assets::GenesisConfig::<Runtime> {
+			assets: vec![(TOKEN, AssetAdmin::get(), true, 1)],
+			metadata: vec![],
+			accounts: self
+				.token_balances
+				.into_iter()
+				.map(|(who, amount)| (TOKEN, who, amount))
+				.collect(),
+		}
+		.assimilate_storage(&mut t)
+		.expect("Pallet assets storage can be assimilated");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+/// Zenlink id of a `StorageAssetId`.
+pub fn zenlink_id(id: StorageAssetId) -> ZenlinkAssetId {
+	AssetIdToZenlinkId::<SelfParaId>::convert(id).expect("valid asset id")
+}
+
+/// Creates the native/`TOKEN` pair and provides its initial liquidity from Alice.
+pub fn create_pool(native_amount: Balance, token_amount: Balance) {
+	let native = zenlink_id(NATIVE_ASSET_ID);
+	let token = zenlink_id(TOKEN);
+
+	ZenlinkProtocol::create_pair(RuntimeOrigin::root(), native, token, FEE_RATE)
+		.expect("pair can be created");
+	ZenlinkProtocol::add_liquidity(
+		RuntimeOrigin::signed(account(alice())),
+		native,
+		token,
+		native_amount,
+		token_amount,
+		0,
+		0,
+		100,
+	)
+	.expect("liquidity can be added");
+}
+
+/// Balance of `who` in the asset `id`, as seen by Zenlink.
+pub fn balance_of(id: StorageAssetId, who: H160) -> Balance {
+	MultiAssets::balance_of(zenlink_id(id), &account(who))
+}
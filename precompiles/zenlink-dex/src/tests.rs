@@ -0,0 +1,397 @@
+// Copyright (C) 2020-2024 Peaq Foundation.
+
+use crate::{mock::*, *};
+use peaq_primitives_xcm::{AssetId as PeaqAssetId, NATIVE_ASSET_ID};
+use precompile_utils::testing::*;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn lp_token() -> StorageAssetId {
+	PeaqAssetId::LPToken(0, 1).try_into().unwrap()
+}
+
+#[test]
+fn test_selector_enum() {
+	assert!(PCall::get_amount_out_by_path_selectors().contains(&0xf62d5f32));
+	assert!(PCall::get_amount_in_by_path_selectors().contains(&0x00e69539));
+	assert!(PCall::get_reserves_selectors().contains(&0x748445e4));
+	assert!(PCall::swap_exact_assets_for_assets_selectors().contains(&0x5d530d88));
+	assert!(PCall::swap_assets_for_exact_assets_selectors().contains(&0xc45c6d71));
+	assert!(PCall::add_liquidity_selectors().contains(&0x62cb2e93));
+	assert!(PCall::remove_liquidity_selectors().contains(&0x98e5836d));
+}
+
+#[test]
+fn modifiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut tester =
+			PrecompilesModifierTester::new(precompiles(), alice(), precompile_address());
+
+		tester.test_view_modifier(PCall::get_amount_out_by_path_selectors());
+		tester.test_view_modifier(PCall::get_amount_in_by_path_selectors());
+		tester.test_view_modifier(PCall::get_reserves_selectors());
+		tester.test_default_modifier(PCall::swap_exact_assets_for_assets_selectors());
+		tester.test_default_modifier(PCall::swap_assets_for_exact_assets_selectors());
+		tester.test_default_modifier(PCall::add_liquidity_selectors());
+		tester.test_default_modifier(PCall::remove_liquidity_selectors());
+	});
+}
+
+#[test]
+fn get_reserves_follows_requested_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_reserves { asset0: NATIVE_ASSET_ID, asset1: TOKEN },
+			)
+			.expect_no_logs()
+			.execute_returns((U256::from(1_000_000), U256::from(2_000_000)));
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_reserves { asset0: TOKEN, asset1: NATIVE_ASSET_ID },
+			)
+			.expect_no_logs()
+			.execute_returns((U256::from(2_000_000), U256::from(1_000_000)));
+	});
+}
+
+#[test]
+fn get_reserves_fails_without_pair() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_reserves { asset0: NATIVE_ASSET_ID, asset1: TOKEN },
+			)
+			.execute_reverts(|output| output == b"Pair not found");
+	});
+}
+
+#[test]
+fn get_amounts_by_path_match_zenlink() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+		let path = vec![zenlink_id(NATIVE_ASSET_ID), zenlink_id(TOKEN)];
+
+		let amounts_out: Vec<U256> = ZenlinkProtocol::get_amount_out_by_path(1_000, &path)
+			.unwrap()
+			.into_iter()
+			.map(Into::into)
+			.collect();
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_amount_out_by_path {
+					amount_in: 1_000.into(),
+					path: vec![NATIVE_ASSET_ID, TOKEN].into(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(amounts_out);
+
+		let amounts_in: Vec<U256> = ZenlinkProtocol::get_amount_in_by_path(1_000, &path)
+			.unwrap()
+			.into_iter()
+			.map(Into::into)
+			.collect();
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_amount_in_by_path {
+					amount_out: 1_000.into(),
+					path: vec![NATIVE_ASSET_ID, TOKEN].into(),
+				},
+			)
+			.expect_no_logs()
+			.execute_returns(amounts_in);
+	});
+}
+
+#[test]
+fn get_amounts_by_path_fails_for_invalid_path() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_amount_out_by_path {
+					amount_in: 1_000.into(),
+					path: vec![NATIVE_ASSET_ID].into(),
+				},
+			)
+			.execute_reverts(|output| output == b"path: Path too short");
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_amount_out_by_path {
+					amount_in: 1_000.into(),
+					path: vec![NATIVE_ASSET_ID, u64::MAX].into(),
+				},
+			)
+			.execute_reverts(|output| output == b"path[1]: Invalid asset id");
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::get_amount_out_by_path {
+					amount_in: U256::MAX,
+					path: vec![NATIVE_ASSET_ID, TOKEN].into(),
+				},
+			)
+			.execute_reverts(|output| output == b"amountIn: Value is too large for balance type");
+	});
+}
+
+#[test]
+fn swap_exact_assets_for_assets_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+		let path = vec![zenlink_id(NATIVE_ASSET_ID), zenlink_id(TOKEN)];
+		let amounts = ZenlinkProtocol::get_amount_out_by_path(1_000, &path).unwrap();
+		let amounts: Vec<U256> = amounts.into_iter().map(Into::into).collect();
+		let bob_token = balance_of(TOKEN, bob());
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::swap_exact_assets_for_assets {
+					amount_in: 1_000.into(),
+					amount_out_min: 1.into(),
+					path: vec![NATIVE_ASSET_ID, TOKEN].into(),
+					recipient: Address(bob()),
+					deadline: 100,
+				},
+			)
+			.expect_log(log3(
+				precompile_address(),
+				SELECTOR_LOG_ASSET_SWAP,
+				alice(),
+				bob(),
+				solidity::encode_event_data((vec![NATIVE_ASSET_ID, TOKEN], amounts.clone())),
+			))
+			.execute_returns(amounts.clone());
+
+		assert_eq!(U256::from(balance_of(TOKEN, bob()) - bob_token), amounts[1]);
+	});
+}
+
+#[test]
+fn swap_assets_for_exact_assets_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+		let path = vec![zenlink_id(TOKEN), zenlink_id(NATIVE_ASSET_ID)];
+		let amounts = ZenlinkProtocol::get_amount_in_by_path(1_000, &path).unwrap();
+		let amounts: Vec<U256> = amounts.into_iter().map(Into::into).collect();
+		let alice_token = balance_of(TOKEN, alice());
+		let bob_native = balance_of(NATIVE_ASSET_ID, bob());
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::swap_assets_for_exact_assets {
+					amount_out: 1_000.into(),
+					amount_in_max: 10_000.into(),
+					path: vec![TOKEN, NATIVE_ASSET_ID].into(),
+					recipient: Address(bob()),
+					deadline: 100,
+				},
+			)
+			.expect_log(log3(
+				precompile_address(),
+				SELECTOR_LOG_ASSET_SWAP,
+				alice(),
+				bob(),
+				solidity::encode_event_data((vec![TOKEN, NATIVE_ASSET_ID], amounts.clone())),
+			))
+			.execute_returns(amounts.clone());
+
+		assert_eq!(U256::from(alice_token - balance_of(TOKEN, alice())), amounts[0]);
+		assert_eq!(balance_of(NATIVE_ASSET_ID, bob()) - bob_native, 1_000);
+	});
+}
+
+#[test]
+fn swap_fails_when_amount_in_exceeds_max() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::swap_assets_for_exact_assets {
+					amount_out: 1_000.into(),
+					amount_in_max: 1.into(),
+					path: vec![TOKEN, NATIVE_ASSET_ID].into(),
+					recipient: Address(bob()),
+					deadline: 100,
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	});
+}
+
+#[test]
+fn add_liquidity_returns_provided_amounts() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+		let bob_lp = balance_of(lp_token(), bob());
+
+		precompiles()
+			.prepare_test(
+				bob(),
+				precompile_address(),
+				PCall::add_liquidity {
+					asset0: NATIVE_ASSET_ID,
+					asset1: TOKEN,
+					amount0_desired: 1_000.into(),
+					amount1_desired: 2_000.into(),
+					amount0_min: 0.into(),
+					amount1_min: 0.into(),
+					deadline: 100,
+				},
+			)
+			.execute_some();
+
+		let liquidity = balance_of(lp_token(), bob()) - bob_lp;
+		assert!(liquidity > 0);
+		assert_eq!(
+			ZenlinkProtocol::get_pair_by_asset_id(zenlink_id(NATIVE_ASSET_ID), zenlink_id(TOKEN))
+				.map(|pair| (pair.reserve_0, pair.reserve_1)),
+			Some((1_001_000, 2_002_000))
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_emits_log() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+		let total_liquidity =
+			ZenlinkProtocol::get_pair_by_asset_id(zenlink_id(NATIVE_ASSET_ID), zenlink_id(TOKEN))
+				.unwrap()
+				.total_liquidity;
+		// Liquidity is minted pro rata of the reserves.
+		let liquidity = U256::from(total_liquidity / 1_000);
+
+		precompiles()
+			.prepare_test(
+				bob(),
+				precompile_address(),
+				PCall::add_liquidity {
+					asset0: NATIVE_ASSET_ID,
+					asset1: TOKEN,
+					amount0_desired: 1_000.into(),
+					amount1_desired: 2_000.into(),
+					amount0_min: 0.into(),
+					amount1_min: 0.into(),
+					deadline: 100,
+				},
+			)
+			.expect_log(log2(
+				precompile_address(),
+				SELECTOR_LOG_LIQUIDITY_ADDED,
+				bob(),
+				solidity::encode_event_data((
+					NATIVE_ASSET_ID,
+					TOKEN,
+					U256::from(1_000),
+					U256::from(2_000),
+					liquidity,
+				)),
+			))
+			.execute_returns((U256::from(1_000), U256::from(2_000), liquidity));
+	});
+}
+
+#[test]
+fn remove_liquidity_sends_assets_to_recipient() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_pool(1_000_000, 2_000_000);
+		let liquidity = balance_of(lp_token(), alice()) / 2;
+		let pair =
+			ZenlinkProtocol::get_pair_by_asset_id(zenlink_id(NATIVE_ASSET_ID), zenlink_id(TOKEN))
+				.unwrap();
+		let amount0 = liquidity * pair.reserve_0 / pair.total_liquidity;
+		let amount1 = liquidity * pair.reserve_1 / pair.total_liquidity;
+		let bob_native = balance_of(NATIVE_ASSET_ID, bob());
+		let bob_token = balance_of(TOKEN, bob());
+
+		precompiles()
+			.prepare_test(
+				alice(),
+				precompile_address(),
+				PCall::remove_liquidity {
+					asset0: NATIVE_ASSET_ID,
+					asset1: TOKEN,
+					liquidity: liquidity.into(),
+					amount0_min: 0.into(),
+					amount1_min: 0.into(),
+					recipient: Address(bob()),
+					deadline: 100,
+				},
+			)
+			.expect_log(log3(
+				precompile_address(),
+				SELECTOR_LOG_LIQUIDITY_REMOVED,
+				alice(),
+				bob(),
+				solidity::encode_event_data((
+					NATIVE_ASSET_ID,
+					TOKEN,
+					U256::from(amount0),
+					U256::from(amount1),
+					U256::from(liquidity),
+				)),
+			))
+			.execute_returns((U256::from(amount0), U256::from(amount1)));
+
+		assert_eq!(balance_of(NATIVE_ASSET_ID, bob()) - bob_native, amount0);
+		assert_eq!(balance_of(TOKEN, bob()) - bob_token, amount1);
+	});
+}
+
+#[test]
+fn liquidity_fails_without_pair() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				bob(),
+				precompile_address(),
+				PCall::add_liquidity {
+					asset0: NATIVE_ASSET_ID,
+					asset1: TOKEN,
+					amount0_desired: 1_000.into(),
+					amount1_desired: 2_000.into(),
+					amount0_min: 0.into(),
+					amount1_min: 0.into(),
+					deadline: 100,
+				},
+			)
+			.execute_reverts(|output| output == b"Pair not found");
+	});
+}
+
+#[test]
+fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
+	check_precompile_implements_solidity_interfaces(&["ZenlinkDex.sol"], PCall::supports_selector)
+}
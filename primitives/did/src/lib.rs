@@ -0,0 +1,171 @@
+//! W3C-style documents of peaq DIDs.
+//!
+//! The document is assembled from the attributes of a DID, each attribute being listed as a
+//! service whose type is the attribute name and whose endpoint is the attribute value. It is
+//! shared by the DID precompile and the node RPC so both resolve a DID to the same bytes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::vec::Vec;
+
+/// The context of every document.
+pub const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+
+/// The prefix of a peaq DID, followed by the hex of the encoded account.
+pub const DID_PREFIX: &str = "did:peaq:0x";
+
+/// An attribute of a DID, as stored by the DID pallet.
+pub struct DocumentAttribute<'a> {
+	pub name: &'a [u8],
+	pub value: &'a [u8],
+	pub validity: u32,
+	pub created: u128,
+}
+
+/// Whether an attribute is still valid at block `now`.
+///
+/// The DID pallet stores the last block an attribute is valid at, `BlockNumber::max_value()`
+/// meaning it never expires.
+pub fn is_valid(validity: u32, now: u32) -> bool {
+	now <= validity
+}
+
+/// Assembles the JSON document of the DID of `account`, the encoded account id.
+///
+/// Names and values which are not UTF-8 are written as `0x`-prefixed hex.
+pub fn document<'a>(
+	account: &[u8],
+	attributes: impl IntoIterator<Item = DocumentAttribute<'a>>,
+) -> Vec<u8> {
+	let mut did = DID_PREFIX.as_bytes().to_vec();
+	write_hex(&mut did, account);
+
+	let mut out = Vec::new();
+	out.extend_from_slice(b"{\"@context\":");
+	write_string(&mut out, DID_CONTEXT.as_bytes());
+	out.extend_from_slice(b",\"id\":");
+	write_string(&mut out, &did);
+	out.extend_from_slice(b",\"service\":[");
+	for (index, attribute) in attributes.into_iter().enumerate() {
+		if index > 0 {
+			out.push(b',');
+		}
+		let mut id = did.clone();
+		id.push(b'#');
+		id.extend_from_slice(attribute.name);
+
+		out.extend_from_slice(b"{\"id\":");
+		write_string(&mut out, &id);
+		out.extend_from_slice(b",\"type\":");
+		write_string(&mut out, attribute.name);
+		out.extend_from_slice(b",\"serviceEndpoint\":");
+		write_string(&mut out, attribute.value);
+		out.extend_from_slice(b",\"validity\":");
+		write_number(&mut out, attribute.validity.into());
+		out.extend_from_slice(b",\"created\":");
+		write_number(&mut out, attribute.created);
+		out.push(b'}');
+	}
+	out.extend_from_slice(b"]}");
+	out
+}
+
+fn write_hex(out: &mut Vec<u8>, bytes: &[u8]) {
+	const HEX: &[u8; 16] = b"0123456789abcdef";
+	for byte in bytes {
+		out.push(HEX[(byte >> 4) as usize]);
+		out.push(HEX[(byte & 0xf) as usize]);
+	}
+}
+
+fn write_number(out: &mut Vec<u8>, mut number: u128) {
+	let start = out.len();
+	loop {
+		out.push(b'0' + (number % 10) as u8);
+		number /= 10;
+		if number == 0 {
+			break;
+		}
+	}
+	out[start..].reverse();
+}
+
+fn write_string(out: &mut Vec<u8>, bytes: &[u8]) {
+	out.push(b'"');
+	match sp_std::str::from_utf8(bytes) {
+		Ok(text) =>
+			for c in text.chars() {
+				match c {
+					'"' => out.extend_from_slice(b"\\\""),
+					'\\' => out.extend_from_slice(b"\\\\"),
+					c if (c as u32) < 0x20 => {
+						out.extend_from_slice(b"\\u00");
+						write_hex(out, &[c as u8]);
+					},
+					c => {
+						let mut buf = [0u8; 4];
+						out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+					},
+				}
+			},
+		Err(_) => {
+			out.extend_from_slice(b"0x");
+			write_hex(out, bytes);
+		},
+	}
+	out.push(b'"');
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty_document() {
+		assert_eq!(
+			document(&[0xab, 0x01], []),
+			br#"{"@context":"https://www.w3.org/ns/did/v1","id":"did:peaq:0xab01","service":[]}"#
+				.to_vec()
+		);
+	}
+
+	#[test]
+	fn attributes_are_services() {
+		let attributes = [
+			DocumentAttribute {
+				name: b"endpoint",
+				value: b"https://peaq.network",
+				validity: 10,
+				created: 0,
+			},
+			DocumentAttribute {
+				name: b"key",
+				value: &[0xff, 0x00],
+				validity: u32::MAX,
+				created: 1234,
+			},
+		];
+		assert_eq!(
+			sp_std::str::from_utf8(&document(&[0x01], attributes)).unwrap(),
+			concat!(
+				r#"{"@context":"https://www.w3.org/ns/did/v1","id":"did:peaq:0x01","service":["#,
+				r#"{"id":"did:peaq:0x01#endpoint","type":"endpoint","serviceEndpoint":"https://peaq.network","validity":10,"created":0},"#,
+				r#"{"id":"did:peaq:0x01#key","type":"key","serviceEndpoint":"0xff00","validity":4294967295,"created":1234}"#,
+				"]}",
+			)
+		);
+	}
+
+	#[test]
+	fn strings_are_escaped() {
+		let mut out = Vec::new();
+		write_string(&mut out, b"a\"b\\c\n");
+		assert_eq!(out, br#""a\"b\\c\u000a""#.to_vec());
+	}
+
+	#[test]
+	fn validity_is_inclusive() {
+		assert!(is_valid(10, 10));
+		assert!(!is_valid(10, 11));
+	}
+}
@@ -28,7 +28,9 @@ use sp_runtime_interface::runtime_interface;
 use parity_scale_codec::Decode;
 use sp_std::vec::Vec;
 
-use evm_tracing_events::{Event, EvmEvent, GasometerEvent, RuntimeEvent, StepEventFilter};
+use evm_tracing_events::{
+	Event, EvmEvent, GasometerEvent, RuntimeEvent, StepEventFilter, TraceOrigin,
+};
 
 #[allow(clippy::unnecessary_mut_passed)]
 #[runtime_interface]
@@ -80,4 +82,16 @@ pub trait PeaqExt {
 	fn call_list_new(&mut self) {
 		Event::CallListNew().emit();
 	}
+
+	/// An event to create a new CallList for the EVM executions of the given part of the block
+	/// being traced.
+	fn call_list_new_origin(&mut self, origin: TraceOrigin) {
+		Event::CallListNewOrigin(origin).emit();
+	}
+
+	/// Whether the deadline of the trace being computed is exceeded, in which case the runtime
+	/// aborts the traced execution.
+	fn trace_deadline_exceeded(&self) -> bool {
+		evm_tracing_events::deadline_exceeded()
+	}
 }
@@ -0,0 +1,111 @@
+//! Fees paid by the extrinsics, and the runtime API replaying a block to report how the fee of
+//! one of its extrinsics was paid, possibly in another asset than the native token, and
+//! distributed by the block reward pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{Block as BlockT, Saturating},
+	RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// Split of an amount between the beneficiaries of the block reward pallet.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct RewardDistribution<Balance> {
+	pub treasury: Balance,
+	pub collators_delegators: Balance,
+	pub coretime: Balance,
+	pub subsidization_pool: Balance,
+	pub depin_staking: Balance,
+	pub depin_incentivization: Balance,
+}
+
+impl<Balance: Saturating> RewardDistribution<Balance> {
+	pub fn saturating_add(self, other: Self) -> Self {
+		Self {
+			treasury: self.treasury.saturating_add(other.treasury),
+			collators_delegators: self
+				.collators_delegators
+				.saturating_add(other.collators_delegators),
+			coretime: self.coretime.saturating_add(other.coretime),
+			subsidization_pool: self.subsidization_pool.saturating_add(other.subsidization_pool),
+			depin_staking: self.depin_staking.saturating_add(other.depin_staking),
+			depin_incentivization: self
+				.depin_incentivization
+				.saturating_add(other.depin_incentivization),
+		}
+	}
+}
+
+/// Swap of another asset into the native token through Zenlink, to pay a fee.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct FeeSwap<Balance> {
+	/// Amount of the fee asset swapped.
+	pub amount_in: Balance,
+	/// Amount of the native token received.
+	pub amount_out: Balance,
+}
+
+/// Fee paid through the transaction payment pallet.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct FeePayment<AccountId, AssetId, Balance> {
+	/// Account which paid the fee.
+	pub payer: AccountId,
+	/// Asset the fee was paid with.
+	pub fee_asset: AssetId,
+	/// Swap of the fee asset into the native token, if it is not the native token.
+	pub swap: Option<FeeSwap<Balance>>,
+	/// Fee withdrawn before dispatch, including the tip and the EoT fee.
+	pub withdrawn: Balance,
+	/// Fee charged after dispatch, including the tip but not the EoT fee.
+	pub fee: Balance,
+	pub tip: Balance,
+	/// Economy-of-Things fee charged on top of the fee.
+	pub eot_fee: Balance,
+	/// Part of the withdrawn fee refunded after dispatch.
+	pub refund: Balance,
+}
+
+/// How the fee of an extrinsic was paid and distributed.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct FeeDetails<AccountId, AssetId, Balance> {
+	/// Index of the extrinsic in its block.
+	pub extrinsic_index: u32,
+	/// Fee paid through the transaction payment pallet, `None` for the extrinsics which do not
+	/// pay through it, e.g. inherents and Ethereum transactions.
+	pub payment: Option<FeePayment<AccountId, AssetId, Balance>>,
+	/// Fees distributed by the block reward pallet while applying the extrinsic.
+	pub distributed: Balance,
+	/// Split of the distributed fees between the beneficiaries.
+	pub distribution: RewardDistribution<Balance>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Replays a block to report how the fee of one of its extrinsics was paid and distributed.
+	pub trait FeeDetailsApi<AccountId, AssetId, Balance>
+	where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Fee details of the extrinsic at the given index of the block `header`, `None` if
+		/// there is no such extrinsic.
+		fn fee_details(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			header: &<Block as BlockT>::Header,
+		) -> Option<FeeDetails<AccountId, AssetId, Balance>>;
+
+		/// Fee details of the extrinsic of the block `header` applying the given Ethereum
+		/// transaction, `None` if there is no such extrinsic.
+		fn ethereum_fee_details(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			header: &<Block as BlockT>::Header,
+		) -> Option<FeeDetails<AccountId, AssetId, Balance>>;
+	}
+}
@@ -17,12 +17,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use ethereum::{TransactionV0 as LegacyTransaction, TransactionV2 as Transaction};
-use ethereum_types::H256;
+use ethereum_types::{H160, H256, U256};
 use parity_scale_codec::{Decode, Encode};
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	// Api version is virtually 5.
+	// Api version is virtually 7.
 	//
 	// We realized that even using runtime overrides, using the ApiExt interface reads the api
 	// versions from the state runtime, meaning we cannot just reset the versioning as we see fit.
@@ -30,7 +30,7 @@ sp_api::decl_runtime_apis! {
 	// In order to be able to use ApiExt as part of the RPC handler logic we need to be always
 	// above the version that exists on chain for this Api, even if this Api is only meant
 	// to be used overridden.
-	#[api_version(5)]
+	#[api_version(7)]
 	pub trait DebugRuntimeApi {
 		#[changed_in(5)]
 		fn trace_transaction(
@@ -56,11 +56,30 @@ sp_api::decl_runtime_apis! {
 			known_transactions: Vec<H256>,
 		) -> Result<(), sp_runtime::DispatchError>;
 
+		/// Traces the EVM executions of the block. From version 7 the block initialization, all
+		/// the extrinsics and the block finalization are traced, each preceded by its origin,
+		/// instead of the known Ethereum transactions only.
 		fn trace_block(
 			extrinsics: Vec<Block::Extrinsic>,
 			known_transactions: Vec<H256>,
 			header: &Block::Header,
 		) -> Result<(), sp_runtime::DispatchError>;
+
+		/// Traces a call executed in the context of the block `header`, on top of the state of
+		/// its parent, without a transaction. Without `to`, `data` is the init code of a contract
+		/// creation.
+		fn trace_call(
+			header: &Block::Header,
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<(), sp_runtime::DispatchError>;
 	}
 }
 
@@ -69,6 +88,8 @@ pub enum TracerInput {
 	None,
 	Blockscout,
 	CallTracer,
+	Prestate,
+	FourByte,
 }
 
 /// DebugRuntimeApi V2 result. Trace response is stored in client and runtime api call response is
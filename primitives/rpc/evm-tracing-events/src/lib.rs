@@ -64,6 +64,37 @@ pub enum Event {
 	Gasometer(gasometer::GasometerEvent),
 	Runtime(runtime::RuntimeEvent),
 	CallListNew(),
+	/// Emitted before each part of a traced block which may execute the EVM, instead of
+	/// `CallListNew` which only precedes Ethereum transactions.
+	CallListNewOrigin(TraceOrigin),
+}
+
+/// Part of a block an EVM execution originates from, mirroring `frame_system::Phase`.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Encode, Decode, PassByCodec)]
+pub enum TraceOrigin {
+	/// Block initialization, e.g. XCM messages serviced by the message queue.
+	Initialization,
+	/// Ethereum transaction of `pallet_ethereum`, at the given extrinsic index.
+	EthereumTransaction(u32),
+	/// Any other extrinsic, at the given index, e.g. `pallet_evm::call` or a call to a
+	/// precompile from a substrate extrinsic.
+	Extrinsic(u32),
+	/// Block finalization, e.g. XCM messages serviced when the block is idle.
+	Finalization,
+}
+
+impl TraceOrigin {
+	/// Index of the originating extrinsic in the block, if any.
+	pub fn extrinsic_index(&self) -> Option<u32> {
+		match self {
+			Self::EthereumTransaction(index) | Self::Extrinsic(index) => Some(*index),
+			Self::Initialization | Self::Finalization => None,
+		}
+	}
+
+	pub fn is_ethereum_transaction(&self) -> bool {
+		matches!(self, Self::EthereumTransaction(_))
+	}
 }
 
 impl Event {
@@ -95,6 +126,36 @@ pub fn step_event_filter() -> Option<StepEventFilter> {
 	filter
 }
 
+/// Deadline of the traced runtime execution, see `using_deadline`.
+#[cfg(feature = "std")]
+struct Deadline {
+	at: std::time::Instant,
+	exceeded: bool,
+}
+
+#[cfg(feature = "std")]
+environmental::environmental!(deadline: Deadline);
+
+/// Runs `f` with a deadline, after which the traced runtime execution aborts itself.
+/// Returns whether the runtime has been told the deadline is exceeded alongside the result of
+/// `f`.
+#[cfg(feature = "std")]
+pub fn using_deadline<R, F: FnOnce() -> R>(at: std::time::Instant, f: F) -> (R, bool) {
+	let mut state = Deadline { at, exceeded: false };
+	let result = deadline::using(&mut state, f);
+	(result, state.exceeded)
+}
+
+/// Whether the deadline of the traced runtime execution is exceeded, `false` without deadline.
+#[cfg(feature = "std")]
+pub fn deadline_exceeded() -> bool {
+	deadline::with(|state| {
+		state.exceeded |= std::time::Instant::now() >= state.at;
+		state.exceeded
+	})
+	.unwrap_or(false)
+}
+
 #[derive(Clone, Debug, Encode, Decode, PartialEq, Eq)]
 pub struct Context {
 	/// Execution address.
@@ -0,0 +1,28 @@
+//! Runtime APIs related to XCM.
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use xcm::VersionedLocation;
+
+/// Reasons a location cannot be converted into a local account.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum LocationToAccountError {
+	/// The location cannot be converted into the latest XCM version.
+	VersionedConversionFailed,
+	/// The runtime does not derive an account for this location.
+	Unsupported,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Computes the local account a location is converted into by the runtime's
+	/// `LocationToAccountId`, e.g. the account a remote user operates through XCM `Transact`.
+	pub trait LocationToAccountApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		fn convert_location(
+			location: VersionedLocation,
+		) -> Result<AccountId, LocationToAccountError>;
+	}
+}
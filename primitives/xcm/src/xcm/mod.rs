@@ -27,6 +27,7 @@
 //! - `FixedRateOfForeignAsset` - weight trader for execution payment in foreign asset
 //! - `ReserveAssetFilter` - used to check whether asset/origin are a valid reserve location
 //! - `XcmFungibleFeeHandler` - used to handle XCM fee execution fees
+//! - `HashedLocationToAccountId` - derives local accounts for users of remote chains
 //!
 //! Please refer to implementation below for more info.
 
@@ -41,7 +42,7 @@ use sp_std::marker::PhantomData;
 
 // Polkadot imports
 use xcm::latest::{prelude::*, Weight};
-use xcm_builder::TakeRevenue;
+use xcm_builder::{DescribeAllTerminal, DescribeFamily, HashedDescription, TakeRevenue};
 use xcm_executor::traits::{MatchesFungibles, WeightTrader};
 
 // ORML imports
@@ -259,6 +260,15 @@ impl<
 	}
 }
 
+/// Used to derive a local account for a location on another chain.
+///
+/// The account is the hash of the location description, so accounts (`AccountId32` or
+/// `AccountKey20`), pallets and plurality bodies of the relay chain and sibling parachains get
+/// their own account on peaq, which they can operate through XCM `Transact`. This is the
+/// `HashedDescription` converter the runtimes already used, so derived accounts are unchanged.
+pub type HashedLocationToAccountId<AccountId> =
+	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>;
+
 /// Convert `AccountId` to `Location`.
 pub struct AccountIdToMultiLocation;
 impl Convert<AccountId, Location> for AccountIdToMultiLocation {
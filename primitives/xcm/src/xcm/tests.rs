@@ -397,3 +397,47 @@ fn hashed_description_sanity_check() {
 		])
 	);
 }
+
+#[test]
+fn hashed_location_to_account_id_derives_remote_accounts() {
+	let derive = |parents: u8, interior: Junctions| {
+		HashedLocationToAccountId::<[u8; 32]>::convert_location(&Location { parents, interior })
+	};
+	let account_id_32 = AccountId32 { network: None, id: [3; 32] };
+	let account_key_20 = AccountKey20 { network: None, key: [7; 20] };
+
+	// Relay chain accounts.
+	assert_eq!(
+		derive(1, [account_id_32].into()),
+		Some([
+			44, 225, 141, 90, 40, 194, 192, 122, 242, 63, 220, 172, 150, 86, 183, 129, 37, 188, 23,
+			140, 60, 91, 203, 4, 180, 252, 19, 75, 116, 4, 238, 81
+		])
+	);
+	assert_eq!(
+		derive(1, [account_key_20].into()),
+		Some([
+			6, 219, 162, 15, 192, 254, 126, 102, 192, 8, 189, 116, 93, 105, 81, 169, 143, 161, 215,
+			167, 118, 182, 56, 154, 177, 218, 173, 176, 112, 83, 10, 230
+		])
+	);
+
+	// Sibling parachain accounts.
+	assert_eq!(
+		derive(1, [Parachain(1000), account_id_32].into()),
+		Some([
+			198, 21, 114, 24, 25, 136, 155, 82, 50, 190, 200, 23, 165, 36, 95, 227, 236, 249, 253,
+			217, 69, 32, 106, 21, 183, 61, 11, 188, 122, 71, 203, 28
+		])
+	);
+	assert_eq!(
+		derive(1, [Parachain(2000), account_key_20].into()),
+		Some([
+			0, 220, 49, 246, 69, 101, 241, 117, 50, 146, 179, 228, 37, 115, 236, 157, 110, 143, 84,
+			115, 65, 160, 102, 143, 73, 204, 147, 174, 86, 167, 89, 27
+		])
+	);
+
+	// Locations outside of the relay chain family are not supported.
+	assert_eq!(derive(2, Junctions::Here), None);
+}
@@ -0,0 +1,164 @@
+use frame_support::{
+	dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	storage::{with_transaction, TransactionOutcome},
+	traits::Get,
+};
+use frame_system::{Config as SysConfig, Phase};
+use pallet_block_reward::{Config as BlockRewardConfig, Event as BlockRewardEvent};
+use pallet_transaction_payment::{Config as TransPayConfig, Event as TransPayEvent};
+use parity_scale_codec::Encode;
+use sp_runtime::{
+	traits::{Dispatchable, SaturatedConversion, Saturating, Zero},
+	ApplyExtrinsicResult, DispatchError, FixedPointOperand, Perbill,
+};
+use sp_std::vec::Vec;
+
+use crate::{eot_adjusted_fee, PeaqMultiCurrenciesPaymentConvert};
+use peaq_primitives_fee::{FeeDetails, FeePayment, FeeSwap, RewardDistribution};
+use peaq_primitives_xcm::Balance;
+
+/// Replays the extrinsics of a block up to the one at `index` and reports how its fee was paid
+/// and distributed. The block must have been initialized beforehand.
+///
+/// `PCPC` and `FEE` must be the ones of the runtime's `PeaqMultiCurrenciesOnChargeTransaction`,
+/// to find out the asset the fee was paid with and the Economy-of-Things fee.
+pub fn fee_details<T, PCPC, FEE, E>(
+	extrinsics: Vec<E>,
+	index: u32,
+	apply: impl Fn(E) -> ApplyExtrinsicResult,
+) -> Option<FeeDetails<T::AccountId, PCPC::AssetId, Balance>>
+where
+	T: SysConfig + TransPayConfig + BlockRewardConfig,
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	T::RuntimeEvent: TryInto<TransPayEvent<T>> + TryInto<BlockRewardEvent<T>>,
+	pallet_transaction_payment::BalanceOf<T>: FixedPointOperand,
+	PCPC: PeaqMultiCurrenciesPaymentConvert<AccountId = T::AccountId>,
+	FEE: Get<Perbill>,
+	E: GetDispatchInfo + Encode,
+{
+	let Replayed { inclusion_fee, paid, distributed, distribution } =
+		replay::<T, E>(extrinsics, index, apply)?;
+
+	let payment = paid.map(|(payer, fee, tip)| {
+		let native_id = PCPC::NativeAssetId::get();
+		let total_fee = inclusion_fee.saturating_add(tip);
+		if total_fee.is_zero() {
+			return FeePayment {
+				payer,
+				fee_asset: native_id,
+				swap: None,
+				withdrawn: Zero::zero(),
+				fee,
+				tip,
+				eot_fee: Zero::zero(),
+				refund: Zero::zero(),
+			};
+		}
+
+		// Same Economy-of-Things fee adjustments as `PeaqMultiCurrenciesOnChargeTransaction`,
+		// before dispatch on the predicted fee and after dispatch on the actual fee.
+		let (_, withdrawn) = eot_adjusted_fee::<FEE, _>(total_fee, tip);
+		let (eot_fee, charged) = eot_adjusted_fee::<FEE, _>(fee, tip);
+		let refund = withdrawn.saturating_sub(charged);
+
+		let (fee_asset, swap) =
+			match PCPC::check_currencies_n_priorities(&payer, withdrawn.saturated_into()) {
+				Ok((asset_id, info)) => (
+					asset_id,
+					info.map(|info| FeeSwap {
+						amount_in: info.amount_in.saturated_into(),
+						amount_out: info.amount_out.saturated_into(),
+					}),
+				),
+				Err(_) => (native_id, None),
+			};
+
+		FeePayment { payer, fee_asset, swap, withdrawn, fee, tip, eot_fee, refund }
+	});
+
+	Some(FeeDetails { extrinsic_index: index, payment, distributed, distribution })
+}
+
+/// Fees of an extrinsic found by replaying it.
+pub(crate) struct Replayed<AccountId> {
+	/// Inclusion fee computed before dispatch, without the tip.
+	pub inclusion_fee: Balance,
+	/// Payer, fee and tip paid through the transaction payment pallet.
+	pub paid: Option<(AccountId, Balance, Balance)>,
+	/// Fees distributed by the block reward pallet.
+	pub distributed: Balance,
+	pub distribution: RewardDistribution<Balance>,
+}
+
+/// Applies the extrinsics before the one at `index`, then applies it in a storage transaction
+/// which is rolled back once its fees are read from its events.
+pub(crate) fn replay<T, E>(
+	extrinsics: Vec<E>,
+	index: u32,
+	apply: impl Fn(E) -> ApplyExtrinsicResult,
+) -> Option<Replayed<T::AccountId>>
+where
+	T: SysConfig + TransPayConfig + BlockRewardConfig,
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	T::RuntimeEvent: TryInto<TransPayEvent<T>> + TryInto<BlockRewardEvent<T>>,
+	pallet_transaction_payment::BalanceOf<T>: FixedPointOperand,
+	E: GetDispatchInfo + Encode,
+{
+	let mut extrinsics = extrinsics.into_iter();
+	for extrinsic in extrinsics.by_ref().take(index as usize) {
+		let _ = apply(extrinsic);
+	}
+	let extrinsic = extrinsics.next()?;
+
+	// The inclusion fee is computed as by the transaction payment pallet before dispatch.
+	let info = extrinsic.get_dispatch_info();
+	let len = extrinsic.encoded_size() as u32;
+	let inclusion_fee: Balance =
+		pallet_transaction_payment::Pallet::<T>::compute_fee(len, &info, Zero::zero())
+			.saturated_into();
+
+	// The asset the fee is paid with depends on the balances before the extrinsic is applied,
+	// so the extrinsic is applied in a storage transaction rolled back once its events are read.
+	let (paid, distributed, distribution) = with_transaction(|| {
+		let _ = apply(extrinsic);
+		TransactionOutcome::Rollback(Ok::<_, DispatchError>(extrinsic_fees::<T>(index)))
+	})
+	.ok()?;
+
+	Some(Replayed { inclusion_fee, paid, distributed, distribution })
+}
+
+type FeesOf<T> =
+	(Option<(<T as SysConfig>::AccountId, Balance, Balance)>, Balance, RewardDistribution<Balance>);
+
+/// Collects the fee paid through the transaction payment pallet and the fees distributed by the
+/// block reward pallet, from the events of the extrinsic at `index`.
+fn extrinsic_fees<T>(index: u32) -> FeesOf<T>
+where
+	T: SysConfig + TransPayConfig + BlockRewardConfig,
+	T::RuntimeEvent: TryInto<TransPayEvent<T>> + TryInto<BlockRewardEvent<T>>,
+{
+	let reward_config = pallet_block_reward::Pallet::<T>::reward_config();
+	let mut paid = None;
+	let mut distributed: Balance = Zero::zero();
+	let mut distribution = RewardDistribution::default();
+
+	for record in frame_system::Pallet::<T>::read_events_no_consensus() {
+		if record.phase != Phase::ApplyExtrinsic(index) {
+			continue;
+		}
+		if let Ok(TransPayEvent::TransactionFeePaid { who, actual_fee, tip }) =
+			TryInto::<TransPayEvent<T>>::try_into(record.event.clone())
+		{
+			paid = Some((who, actual_fee.saturated_into(), tip.saturated_into()));
+		} else if let Ok(BlockRewardEvent::TransactionFeesDistributed(value)) =
+			TryInto::<BlockRewardEvent<T>>::try_into(record.event)
+		{
+			let value: Balance = value.saturated_into();
+			distributed = distributed.saturating_add(value);
+			distribution = distribution.saturating_add(reward_config.split(value));
+		}
+	}
+
+	(paid, distributed, distribution)
+}
@@ -10,8 +10,11 @@ use zenlink_protocol::{AssetBalance, AssetId as ZenlinkAssetId, LocalAssetHandle
 
 use peaq_primitives_xcm::{AccountId, Balance};
 
+pub mod fee_details;
 pub mod payment;
 pub use payment::*;
+pub mod precompiles;
+pub use precompiles::*;
 pub mod xcm_impls;
 pub use xcm_impls::*;
 pub mod wrapper;
@@ -19,6 +22,11 @@ pub use wrapper::*;
 pub mod zenlink;
 pub use zenlink::*;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 // Contracts price units.
 pub const TOKEN_DECIMALS: u32 = 18;
 pub const NANOCENTS: Balance = 10_u128.pow(TOKEN_DECIMALS - 2 - 9);
@@ -0,0 +1,302 @@
+use crate::Balance;
+
+use frame_support::{
+	construct_runtime,
+	dispatch::{DispatchInfo, GetDispatchInfo},
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, ConstU8, Currency},
+	weights::IdentityFee,
+};
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
+use pallet_balances::NegativeImbalance;
+use pallet_transaction_payment::{ChargeTransactionPayment, CurrencyAdapter};
+use parity_scale_codec::Encode;
+use peaq_primitives_xcm::RbacEntityId;
+use sp_core::H256;
+use sp_io::TestExternalities;
+use sp_runtime::{
+	traits::{BlakeTwo256, Dispatchable, IdentityLookup, SignedExtension},
+	ApplyExtrinsicResult, BuildStorage,
+};
+
+pub(crate) type AccountId = u64;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const TREASURY: AccountId = 100;
+pub(crate) const COLLATORS_DELEGATORS: AccountId = 101;
+
+construct_runtime!(
+	pub enum TestRuntime
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		TransactionPayment: pallet_transaction_payment,
+		BlockReward: pallet_block_reward,
+		Timestamp: pallet_timestamp,
+		Utility: pallet_utility,
+		Assets: pallet_assets,
+		PeaqDid: peaq_pallet_did,
+		PeaqRbac: peaq_pallet_rbac,
+		PeaqStorage: peaq_pallet_storage,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for TestRuntime {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+	type RuntimeTask = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+}
+
+impl pallet_transaction_payment::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction = CurrencyAdapter<Balances, BlockReward>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<Balance>;
+	type LengthToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
+// The treasury receives all the shares of the fees but the one of the collators and delegators.
+pub struct BeneficiaryPayout;
+impl pallet_block_reward::BeneficiaryPayout<NegativeImbalance<TestRuntime>> for BeneficiaryPayout {
+	fn treasury(reward: NegativeImbalance<TestRuntime>) {
+		Balances::resolve_creating(&TREASURY, reward);
+	}
+
+	fn collators_delegators(reward: NegativeImbalance<TestRuntime>) {
+		Balances::resolve_creating(&COLLATORS_DELEGATORS, reward);
+	}
+
+	fn coretime(reward: NegativeImbalance<TestRuntime>) {
+		Balances::resolve_creating(&TREASURY, reward);
+	}
+
+	fn subsidization_pool(reward: NegativeImbalance<TestRuntime>) {
+		Balances::resolve_creating(&TREASURY, reward);
+	}
+
+	fn depin_staking(reward: NegativeImbalance<TestRuntime>) {
+		Balances::resolve_creating(&TREASURY, reward);
+	}
+
+	fn depin_incentivization(reward: NegativeImbalance<TestRuntime>) {
+		Balances::resolve_creating(&TREASURY, reward);
+	}
+}
+
+impl pallet_block_reward::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BeneficiaryPayout = BeneficiaryPayout;
+	type WeightInfo = pallet_block_reward::weights::WeightInfo<TestRuntime>;
+}
+
+impl pallet_timestamp::Config for TestRuntime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
+impl pallet_utility::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<1000>;
+}
+
+parameter_types! {
+	pub const StorageDepositBase: Balance = 10;
+	pub const StorageDepositPerByte: Balance = 1;
+	pub const DidReserveIdentifier: [u8; 8] = *b"peaq_did";
+	pub const RbacReserveIdentifier: [u8; 8] = *b"peaqrbac";
+	pub const StorageReserveIdentifier: [u8; 8] = *b"peaqstor";
+}
+
+impl peaq_pallet_did::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type Time = Timestamp;
+	type WeightInfo = peaq_pallet_did::weights::WeightInfo<TestRuntime>;
+	type Currency = Balances;
+	type StorageDepositBase = StorageDepositBase;
+	type StorageDepositPerByte = StorageDepositPerByte;
+	type ReserveIdentifier = DidReserveIdentifier;
+}
+
+impl peaq_pallet_rbac::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type EntityId = RbacEntityId;
+	type BoundedDataLen = ConstU32<256>;
+	type WeightInfo = peaq_pallet_rbac::weights::WeightInfo<TestRuntime>;
+	type Currency = Balances;
+	type StorageDepositBase = StorageDepositBase;
+	type StorageDepositPerByte = StorageDepositPerByte;
+	type ReserveIdentifier = RbacReserveIdentifier;
+}
+
+impl peaq_pallet_storage::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = peaq_pallet_storage::weights::WeightInfo<TestRuntime>;
+	type BoundedDataLen = ConstU32<256>;
+	type Currency = Balances;
+	type StorageDepositBase = StorageDepositBase;
+	type StorageDepositPerByte = StorageDepositPerByte;
+	type ReserveIdentifier = StorageReserveIdentifier;
+}
+
+/// Extrinsic of the tests, paying its fee through the transaction payment pallet if signed.
+#[derive(Clone, Encode)]
+pub(crate) struct TestXt {
+	pub signer: Option<AccountId>,
+	pub call: RuntimeCall,
+	pub tip: Balance,
+}
+
+impl TestXt {
+	pub(crate) fn signed(signer: AccountId, tip: Balance) -> Self {
+		Self { signer: Some(signer), call: remark(), tip }
+	}
+
+	pub(crate) fn unsigned() -> Self {
+		Self { signer: None, call: remark(), tip: 0 }
+	}
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![0; 10] })
+}
+
+impl GetDispatchInfo for TestXt {
+	fn get_dispatch_info(&self) -> DispatchInfo {
+		self.call.get_dispatch_info()
+	}
+}
+
+/// Applies the extrinsic as the executive does: charges its fee, dispatches it, then refunds
+/// the overcharged fee.
+pub(crate) fn apply(xt: TestXt) -> ApplyExtrinsicResult {
+	let info = xt.get_dispatch_info();
+	let len = xt.encoded_size();
+	let (origin, pre) = match xt.signer {
+		Some(signer) => (
+			RawOrigin::Signed(signer),
+			Some(
+				ChargeTransactionPayment::<TestRuntime>::from(xt.tip)
+					.pre_dispatch(&signer, &xt.call, &info, len)?,
+			),
+		),
+		None => (RawOrigin::None, None),
+	};
+
+	let result = xt.call.dispatch(origin.into());
+	let post_info = result.unwrap_or_else(|e| e.post_info);
+	let dispatch_result = result.map(|_| ()).map_err(|e| e.error);
+	ChargeTransactionPayment::<TestRuntime>::post_dispatch(
+		pre,
+		&info,
+		&post_info,
+		len,
+		&dispatch_result,
+	)?;
+	System::note_applied_extrinsic(&result, info);
+
+	Ok(dispatch_result)
+}
+
+pub(crate) struct ExtBuilder;
+
+impl ExtBuilder {
+	pub(crate) fn build() -> TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+
+		pallet_balances::GenesisConfig::<TestRuntime> {
+			balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		pallet_block_reward::GenesisConfig::<TestRuntime> {
+			reward_config: pallet_block_reward::RewardDistributionConfig::default(),
+			_phantom: Default::default(),
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		let mut ext = TestExternalities::from(storage);
+		ext.execute_with(|| {
+			// Events are only stored from the first block, along with the extrinsic applying them.
+			System::set_block_number(1);
+			System::note_finished_initialize();
+		});
+		ext
+	}
+}
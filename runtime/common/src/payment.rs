@@ -11,8 +11,8 @@ use orml_traits::MultiCurrency;
 use pallet_transaction_payment::{Config as TransPayConfig, OnChargeTransaction};
 use sp_runtime::{
 	traits::{
-		Convert, DispatchInfoOf, MaybeDisplay, Member, PostDispatchInfoOf, SaturatedConversion,
-		Saturating, Zero,
+		AtLeast32BitUnsigned, Convert, DispatchInfoOf, MaybeDisplay, Member, PostDispatchInfoOf,
+		SaturatedConversion, Saturating, Zero,
 	},
 	Perbill,
 };
@@ -29,6 +29,18 @@ type BalanceOf<C, T> = <C as Currency<<T as SysConfig>::AccountId>>::Balance;
 type BalanceOfA<C, A> = <C as Currency<A>>::Balance;
 type NegativeImbalanceOf<C, T> = <C as Currency<<T as SysConfig>::AccountId>>::NegativeImbalance;
 
+/// Applies the Peaq Economy-of-Things fee adjustment to `total_fee`, which includes the `tip`:
+/// the EoT fee is a share `FEE` of the inclusion fee, charged on top of the total fee.
+/// Returns the EoT fee and the adjusted fee.
+pub fn eot_adjusted_fee<FEE, Balance>(total_fee: Balance, tip: Balance) -> (Balance, Balance)
+where
+	FEE: Get<Perbill>,
+	Balance: AtLeast32BitUnsigned + Copy,
+{
+	let eot_fee = FEE::get() * total_fee.saturating_sub(tip);
+	(eot_fee, total_fee.saturating_add(eot_fee))
+}
+
 /// Peaq's Currency Adapter to apply EoT-Fee and to enable withdrawal from foreign currencies.
 pub struct PeaqMultiCurrenciesOnChargeTransaction<C, OU, PCPC, FEE>(
 	PhantomData<(C, OU, PCPC, FEE)>,
@@ -60,8 +72,6 @@ where
 		if total_fee.is_zero() {
 			return Ok(None);
 		}
-		let inclusion_fee = total_fee - tip;
-
 		let withdraw_reason = if tip.is_zero() {
 			WithdrawReasons::TRANSACTION_PAYMENT
 		} else {
@@ -69,8 +79,7 @@ where
 		};
 
 		// Apply Peaq Economy-of-Things Fee adjustment.
-		let eot_fee = FEE::get() * inclusion_fee;
-		let tx_fee = total_fee.saturating_add(eot_fee);
+		let (_, tx_fee) = eot_adjusted_fee::<FEE, _>(total_fee, tip);
 
 		// Check if user can withdraw in any valid currency.
 		let currency_id = PCPC::ensure_can_withdraw(who, tx_fee)?;
@@ -104,9 +113,7 @@ where
 	) -> Result<(), TransactionValidityError> {
 		if let Some(paid) = already_withdrawn {
 			// Apply same Peaq Economy-of-Things Fee adjustment as above
-			let cor_inclusion_fee = cor_total_fee - tip;
-			let cor_eot_fee = FEE::get() * cor_inclusion_fee;
-			let cor_tx_fee = cor_total_fee.saturating_add(cor_eot_fee);
+			let (_, cor_tx_fee) = eot_adjusted_fee::<FEE, _>(cor_total_fee, tip);
 
 			// Calculate how much refund we should return
 			let refund_amount = paid.peek().saturating_sub(cor_tx_fee);
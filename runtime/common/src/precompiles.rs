@@ -0,0 +1,70 @@
+use fp_evm::{ExitError, PrecompileFailure};
+use frame_support::{
+	dispatch::{DispatchClass, GetDispatchInfo, Pays},
+	traits::{Contains, IsSubType},
+};
+use pallet_evm_precompile_dispatch::DispatchValidateT;
+use sp_std::marker::PhantomData;
+
+type RuntimeCallOf<Runtime> = <Runtime as frame_system::Config>::RuntimeCall;
+
+/// Calls which can be dispatched through the precompiles dispatching runtime calls: the calls of
+/// the DID, RBAC, storage and assets pallets, and the utility batches made of them only.
+pub struct WhitelistedCalls<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Contains<RuntimeCallOf<Runtime>> for WhitelistedCalls<Runtime>
+where
+	Runtime: pallet_utility::Config<RuntimeCall = RuntimeCallOf<Runtime>>
+		+ peaq_pallet_did::Config
+		+ peaq_pallet_rbac::Config
+		+ peaq_pallet_storage::Config
+		+ pallet_assets::Config,
+	RuntimeCallOf<Runtime>: IsSubType<pallet_utility::Call<Runtime>>
+		+ IsSubType<peaq_pallet_did::Call<Runtime>>
+		+ IsSubType<peaq_pallet_rbac::Call<Runtime>>
+		+ IsSubType<peaq_pallet_storage::Call<Runtime>>
+		+ IsSubType<pallet_assets::Call<Runtime>>,
+{
+	fn contains(call: &RuntimeCallOf<Runtime>) -> bool {
+		if let Some(call) = IsSubType::<pallet_utility::Call<Runtime>>::is_sub_type(call) {
+			return match call {
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls } => calls.iter().all(Self::contains),
+				_ => false,
+			}
+		}
+
+		IsSubType::<peaq_pallet_did::Call<Runtime>>::is_sub_type(call).is_some() ||
+			IsSubType::<peaq_pallet_rbac::Call<Runtime>>::is_sub_type(call).is_some() ||
+			IsSubType::<peaq_pallet_storage::Call<Runtime>>::is_sub_type(call).is_some() ||
+			IsSubType::<pallet_assets::Call<Runtime>>::is_sub_type(call).is_some()
+	}
+}
+
+/// Only lets through paid, normal calls accepted by `Filter`.
+pub struct DispatchFilterValidate<Call, Filter>(PhantomData<(Call, Filter)>);
+
+impl<AccountId, Call, Filter> DispatchValidateT<AccountId, Call>
+	for DispatchFilterValidate<Call, Filter>
+where
+	Call: GetDispatchInfo,
+	Filter: Contains<Call>,
+{
+	fn validate_before_dispatch(_origin: &AccountId, call: &Call) -> Option<PrecompileFailure> {
+		let info = call.get_dispatch_info();
+		let paid_normal_call = info.pays_fee == Pays::Yes && info.class == DispatchClass::Normal;
+		if !paid_normal_call {
+			return Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("invalid call".into()),
+			})
+		}
+		if Filter::contains(call) {
+			None
+		} else {
+			Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("call filtered out".into()),
+			})
+		}
+	}
+}
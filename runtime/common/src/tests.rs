@@ -0,0 +1,154 @@
+use crate::{
+	eot_adjusted_fee,
+	fee_details::{replay, Replayed},
+	mock::*,
+	DispatchFilterValidate, WhitelistedCalls,
+};
+use frame_support::{assert_ok, dispatch::GetDispatchInfo, parameter_types};
+use frame_system::Phase;
+use pallet_evm_precompile_dispatch::DispatchValidateT;
+use parity_scale_codec::Encode;
+use sp_runtime::{traits::Dispatchable, Perbill};
+
+parameter_types! {
+	pub const EoTFee: Perbill = Perbill::from_percent(10);
+}
+
+fn inclusion_fee(xt: &TestXt) -> u128 {
+	TransactionPayment::compute_fee(xt.encoded_size() as u32, &xt.get_dispatch_info(), 0)
+}
+
+fn replayed(xts: Vec<TestXt>, index: u32) -> Option<Replayed<AccountId>> {
+	replay::<TestRuntime, _>(xts, index, apply)
+}
+
+#[test]
+fn eot_fee_is_a_share_of_the_inclusion_fee() {
+	assert_eq!(eot_adjusted_fee::<EoTFee, u128>(110, 10), (10, 120));
+	assert_eq!(eot_adjusted_fee::<EoTFee, u128>(10, 10), (0, 10));
+	assert_eq!(eot_adjusted_fee::<EoTFee, u128>(0, 0), (0, 0));
+}
+
+#[test]
+fn replay_reports_the_fee_of_the_extrinsic_at_index() {
+	ExtBuilder::build().execute_with(|| {
+		let xts = vec![TestXt::signed(ALICE, 0), TestXt::signed(BOB, 7), TestXt::signed(ALICE, 0)];
+		let fee = inclusion_fee(&xts[1]);
+
+		let Replayed { inclusion_fee, paid, distributed, distribution } = replayed(xts, 1).unwrap();
+
+		assert_eq!(inclusion_fee, fee);
+		assert_eq!(paid, Some((BOB, fee + 7, 7)));
+		assert_eq!(distributed, fee + 7);
+		assert_eq!(distribution, BlockReward::reward_config().split(fee + 7));
+	});
+}
+
+#[test]
+fn replay_rolls_back_the_extrinsic_at_index_only() {
+	ExtBuilder::build().execute_with(|| {
+		let xts = vec![TestXt::signed(ALICE, 0), TestXt::signed(BOB, 0)];
+		let alice_fee = inclusion_fee(&xts[0]);
+
+		assert!(replayed(xts, 1).is_some());
+
+		// The previous extrinsics stay applied, as when the block was executed.
+		assert_eq!(Balances::free_balance(ALICE), 1_000_000 - alice_fee);
+		assert_eq!(Balances::free_balance(BOB), 1_000_000);
+		assert!(System::events().iter().all(|record| record.phase == Phase::ApplyExtrinsic(0)));
+	});
+}
+
+#[test]
+fn replay_ignores_extrinsics_not_paying_fees() {
+	ExtBuilder::build().execute_with(|| {
+		let xts = vec![TestXt::signed(ALICE, 0), TestXt::unsigned()];
+
+		let Replayed { paid, distributed, .. } = replayed(xts, 1).unwrap();
+
+		assert_eq!(paid, None);
+		assert_eq!(distributed, 0);
+	});
+}
+
+#[test]
+fn replay_of_a_missing_extrinsic_is_none() {
+	ExtBuilder::build().execute_with(|| {
+		assert!(replayed(vec![TestXt::signed(ALICE, 0)], 1).is_none());
+		assert!(replayed(vec![], 0).is_none());
+	});
+}
+
+type Validate = DispatchFilterValidate<RuntimeCall, WhitelistedCalls<TestRuntime>>;
+
+fn add_item(item_type: &[u8]) -> RuntimeCall {
+	RuntimeCall::PeaqStorage(peaq_pallet_storage::Call::add_item {
+		item_type: item_type.to_vec().try_into().unwrap(),
+		item: b"item".to_vec().try_into().unwrap(),
+	})
+}
+
+fn transfer() -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death { dest: BOB, value: 1 })
+}
+
+fn validate(call: &RuntimeCall) -> bool {
+	Validate::validate_before_dispatch(&ALICE, call).is_none()
+}
+
+#[test]
+fn whitelisted_calls_are_dispatched() {
+	ExtBuilder::build().execute_with(|| {
+		let call = add_item(b"single");
+		assert!(validate(&call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(ALICE)));
+
+		let call = RuntimeCall::Utility(pallet_utility::Call::batch_all {
+			calls: vec![
+				add_item(b"batched"),
+				RuntimeCall::Utility(pallet_utility::Call::batch {
+					calls: vec![add_item(b"nested")],
+				}),
+			],
+		});
+		assert!(validate(&call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(ALICE)));
+	});
+}
+
+#[test]
+fn calls_not_whitelisted_are_rejected() {
+	ExtBuilder::build().execute_with(|| {
+		assert!(!validate(&transfer()));
+		assert!(!validate(&RuntimeCall::System(frame_system::Call::remark {
+			remark: b"remark".to_vec(),
+		})));
+		assert!(!validate(&RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+			index: 0,
+			call: Box::new(add_item(b"derived")),
+		})));
+	});
+}
+
+#[test]
+fn calls_not_whitelisted_are_rejected_in_batches() {
+	ExtBuilder::build().execute_with(|| {
+		let calls = vec![add_item(b"batched"), transfer()];
+
+		assert!(!validate(&RuntimeCall::Utility(pallet_utility::Call::batch {
+			calls: calls.clone()
+		})));
+		assert!(!validate(&RuntimeCall::Utility(pallet_utility::Call::batch_all {
+			calls: calls.clone()
+		})));
+		assert!(!validate(&RuntimeCall::Utility(pallet_utility::Call::force_batch {
+			calls: calls.clone()
+		})));
+		assert!(!validate(&RuntimeCall::Utility(pallet_utility::Call::batch_all {
+			calls: vec![
+				add_item(b"batched"),
+				RuntimeCall::Utility(pallet_utility::Call::batch { calls }),
+			],
+		})));
+	});
+}
@@ -25,6 +25,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod tracer {
+	pub use evm_tracing_events::TraceOrigin;
 	use evm_tracing_events::{EvmEvent, GasometerEvent, RuntimeEvent, StepEventFilter};
 	use parity_scale_codec::Encode;
 
@@ -52,8 +53,12 @@ pub mod tracer {
 		}
 	}
 
+	/// Number of events proxied to the host between two checks of the tracing deadline.
+	const DEADLINE_CHECK_INTERVAL: u32 = 1024;
+
 	pub struct EvmTracer {
 		step_event_filter: StepEventFilter,
+		events: u32,
 	}
 
 	impl Default for EvmTracer {
@@ -64,7 +69,10 @@ pub mod tracer {
 
 	impl EvmTracer {
 		pub fn new() -> Self {
-			Self { step_event_filter: peaq_primitives_ext::peaq_ext::step_event_filter() }
+			Self {
+				step_event_filter: peaq_primitives_ext::peaq_ext::step_event_filter(),
+				events: 0,
+			}
 		}
 
 		/// Setup event listeners and execute provided closure.
@@ -90,6 +98,26 @@ pub mod tracer {
 		pub fn emit_new() {
 			peaq_primitives_ext::peaq_ext::call_list_new();
 		}
+
+		pub fn emit_new_origin(origin: TraceOrigin) {
+			Self::check_deadline();
+			peaq_primitives_ext::peaq_ext::call_list_new_origin(origin);
+		}
+
+		/// Aborts the traced execution once the deadline of the trace is exceeded, the node
+		/// giving up on it anyway.
+		fn check_deadline() {
+			if peaq_primitives_ext::peaq_ext::trace_deadline_exceeded() {
+				panic!("EVM tracing deadline exceeded");
+			}
+		}
+
+		fn count_event(&mut self) {
+			self.events = self.events.wrapping_add(1);
+			if self.events % DEADLINE_CHECK_INTERVAL == 0 {
+				Self::check_deadline();
+			}
+		}
 	}
 
 	impl EvmListener for EvmTracer {
@@ -98,6 +126,7 @@ pub mod tracer {
 			let event: EvmEvent = event.into();
 			let message = event.encode();
 			peaq_primitives_ext::peaq_ext::evm_event(message);
+			self.count_event();
 		}
 	}
 
@@ -107,6 +136,7 @@ pub mod tracer {
 			let event: GasometerEvent = event.into();
 			let message = event.encode();
 			peaq_primitives_ext::peaq_ext::gasometer_event(message);
+			self.count_event();
 		}
 	}
 
@@ -116,6 +146,7 @@ pub mod tracer {
 			let event = RuntimeEvent::from_evm_event(event, self.step_event_filter);
 			let message = event.encode();
 			peaq_primitives_ext::peaq_ext::runtime_event(message);
+			self.count_event();
 		}
 	}
 }
@@ -1078,6 +1078,7 @@ construct_runtime!(
 		XcAssetConfig: xc_asset_config::{Pallet, Call, Storage, Event<T>} = 40,
 		AddressUnification: address_unification::{Pallet, Call, Storage, Event<T>} = 41,
 		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>} = 42,
+		EthereumXcm: ethereum_xcm::{Pallet, Call, Event<T>} = 43,
 
 		Vesting: pallet_vesting = 50,
 
@@ -1418,30 +1419,36 @@ impl_runtime_apis! {
 			sp_runtime::DispatchError,
 		> {
 			{
-				use peaq_evm_tracer::tracer::EvmTracer;
+				use peaq_evm_tracer::tracer::{EvmTracer, TraceOrigin};
 
 				// We need to follow the order when replaying the transactions.
 				// Block initialize happens first then apply_extrinsic.
-				Executive::initialize_block(header);
-
-				// Apply all extrinsics. Ethereum extrinsics are traced.
-				for ext in extrinsics.into_iter() {
-					match &ext.0.function {
+				// The block initialization services the XCM messages, which may execute the EVM.
+				EvmTracer::emit_new_origin(TraceOrigin::Initialization);
+				EvmTracer::new().trace(|| Executive::initialize_block(header));
+
+				// Apply and trace all extrinsics, as substrate extrinsics may execute the EVM too.
+				// Each extrinsic is a new call stack, annotated with its index in the block.
+				for (index, ext) in extrinsics.into_iter().enumerate() {
+					let index = index as u32;
+					let origin = match &ext.0.function {
 						RuntimeCall::Ethereum(pallet_ethereum::Call::transact { transaction }) => {
-							if known_transactions.contains(&transaction.hash()) {
-								// Each known extrinsic is a new call stack.
-								EvmTracer::emit_new();
-								EvmTracer::new().trace(|| Executive::apply_extrinsic(ext));
-							} else {
+							if !known_transactions.contains(&transaction.hash()) {
 								let _ = Executive::apply_extrinsic(ext);
+								continue;
 							}
+							TraceOrigin::EthereumTransaction(index)
 						}
-						_ => {
-							let _ = Executive::apply_extrinsic(ext);
-						}
+						_ => TraceOrigin::Extrinsic(index),
 					};
+					EvmTracer::emit_new_origin(origin);
+					EvmTracer::new().trace(|| Executive::apply_extrinsic(ext));
 				}
 
+				// The block finalization services the remaining XCM messages when idle.
+				EvmTracer::emit_new_origin(TraceOrigin::Finalization);
+				EvmTracer::new().trace(Executive::finalize_block);
+
 				Ok(())
 			}
 		}
@@ -1459,6 +1466,90 @@ impl_runtime_apis! {
 				"Missing `evm-tracing` compile time feature flag.",
 			))
 		}
+
+		#[cfg(feature = "evm-tracing")]
+		fn trace_call(
+			header: &<Block as BlockT>::Header,
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<(), sp_runtime::DispatchError> {
+			use peaq_evm_tracer::tracer::EvmTracer;
+
+			// Initialize block: calls the "on_initialize" hook on every pallet
+			// in AllPalletsWithSystem.
+			Executive::initialize_block(header);
+
+			EvmTracer::new().trace(|| {
+				let is_transactional = false;
+				let validate = true;
+				let gas_limit = gas_limit.min(u64::MAX.into()).low_u64();
+				let access_list = access_list.unwrap_or_default();
+				let config = <Runtime as pallet_evm::Config>::config();
+				match to {
+					Some(to) => {
+						let _ = <Runtime as pallet_evm::Config>::Runner::call(
+							from,
+							to,
+							data,
+							value,
+							gas_limit,
+							max_fee_per_gas,
+							max_priority_fee_per_gas,
+							nonce,
+							access_list,
+							is_transactional,
+							validate,
+							None,
+							None,
+							config,
+						);
+					},
+					None => {
+						let _ = <Runtime as pallet_evm::Config>::Runner::create(
+							from,
+							data,
+							value,
+							gas_limit,
+							max_fee_per_gas,
+							max_priority_fee_per_gas,
+							nonce,
+							access_list,
+							is_transactional,
+							validate,
+							None,
+							None,
+							config,
+						);
+					},
+				}
+			});
+			Ok(())
+		}
+
+		#[cfg(not(feature = "evm-tracing"))]
+		fn trace_call(
+			_header: &<Block as BlockT>::Header,
+			_from: H160,
+			_to: Option<H160>,
+			_data: Vec<u8>,
+			_value: U256,
+			_gas_limit: U256,
+			_max_fee_per_gas: Option<U256>,
+			_max_priority_fee_per_gas: Option<U256>,
+			_nonce: Option<U256>,
+			_access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<(), sp_runtime::DispatchError> {
+			Err(sp_runtime::DispatchError::Other(
+				"Missing `evm-tracing` compile time feature flag.",
+			))
+		}
 	}
 
 	impl peaq_rpc_primitives_txpool::TxPoolRuntimeApi<Block> for Runtime {
@@ -1936,6 +2027,57 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl peaq_primitives_xcm::runtime_api::LocationToAccountApi<Block, AccountId> for Runtime {
+		fn convert_location(
+			location: xcm::VersionedLocation,
+		) -> Result<AccountId, peaq_primitives_xcm::runtime_api::LocationToAccountError> {
+			use peaq_primitives_xcm::runtime_api::LocationToAccountError;
+			use xcm_executor::traits::ConvertLocation;
+
+			let location: xcm::latest::Location = location
+				.try_into()
+				.map_err(|_| LocationToAccountError::VersionedConversionFailed)?;
+			xcm_config::LocationToAccountId::convert_location(&location)
+				.ok_or(LocationToAccountError::Unsupported)
+		}
+	}
+
+	impl peaq_primitives_fee::FeeDetailsApi<Block, AccountId, StorageAssetId, Balance> for Runtime {
+		fn fee_details(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			header: &<Block as BlockT>::Header,
+		) -> Option<peaq_primitives_fee::FeeDetails<AccountId, StorageAssetId, Balance>> {
+			Executive::initialize_block(header);
+			runtime_common::fee_details::fee_details::<Runtime, PeaqCPC, EoTFeeFactor, _>(
+				extrinsics,
+				index,
+				Executive::apply_extrinsic,
+			)
+		}
+
+		fn ethereum_fee_details(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			header: &<Block as BlockT>::Header,
+		) -> Option<peaq_primitives_fee::FeeDetails<AccountId, StorageAssetId, Balance>> {
+			let index = extrinsics.iter().position(|ext| {
+				matches!(
+					&ext.0.function,
+					RuntimeCall::Ethereum(transact { transaction })
+						if transaction.hash() == transaction_hash
+				)
+			})?;
+
+			Executive::initialize_block(header);
+			runtime_common::fee_details::fee_details::<Runtime, PeaqCPC, EoTFeeFactor, _>(
+				extrinsics,
+				index as u32,
+				Executive::apply_extrinsic,
+			)
+		}
+	}
+
 	impl zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId, ZenlinkAssetId> for Runtime {
 		fn get_balance(asset_id: ZenlinkAssetId, owner: AccountId) -> AssetBalance {
 			<Runtime as zenlink_protocol::Config>::MultiAssetsHandler::balance_of(asset_id, &owner)
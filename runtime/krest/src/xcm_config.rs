@@ -13,11 +13,13 @@ use frame_support::{
 use frame_system::EnsureRoot;
 use orml_traits::location::{RelativeReserveProvider, Reserve};
 use orml_xcm_support::{DisabledParachainFee, MultiNativeAsset};
-use pallet_xcm::XcmPassthrough;
+use pallet_evm::GasWeightMapping;
+use pallet_xcm::{EnsureXcm, XcmPassthrough};
 use parachains_common::{
 	message_queue::{NarrowOriginToSibling, ParaIdToSibling},
 	xcm_config::ParentRelayOrSiblingParachains,
 };
+use peaq_primitives_xcm::xcm::HashedLocationToAccountId;
 use polkadot_parachain::primitives::Sibling;
 use polkadot_runtime_common::xcm_sender::NoPriceForMessageDelivery;
 use runtime_common::{AccountIdToLocation, FixedRateOfForeignAsset};
@@ -29,13 +31,12 @@ use sp_weights::Weight;
 use xcm::latest::{prelude::*, Asset};
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
-	AllowTopLevelPaidExecutionFrom, ConvertedConcreteId, DescribeAllTerminal, DescribeFamily,
-	EnsureXcmOrigin, FixedWeightBounds, FrameTransactionalProcessor, FungibleAdapter,
-	FungiblesAdapter, HashedDescription, IsConcrete, NoChecking, ParentAsSuperuser, ParentIsPreset,
-	RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
-	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeRevenue,
-	TakeWeightCredit, TrailingSetTopicAsId, UsingComponents, WithComputedOrigin,
-	XcmFeeManagerFromComponents, XcmFeeToAccount,
+	AllowTopLevelPaidExecutionFrom, ConvertedConcreteId, EnsureXcmOrigin, FixedWeightBounds,
+	FrameTransactionalProcessor, FungibleAdapter, FungiblesAdapter, IsConcrete, NoChecking,
+	ParentAsSuperuser, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
+	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeRevenue, TakeWeightCredit, TrailingSetTopicAsId,
+	UsingComponents, WithComputedOrigin, XcmFeeManagerFromComponents, XcmFeeToAccount,
 };
 use xcm_executor::{traits::JustTry, XcmExecutor};
 
@@ -65,7 +66,7 @@ pub type LocationToAccountId = (
 	// Straight up local `AccountId32` origins just alias directly to `AccountId`.
 	AccountId32Aliases<RelayNetwork, AccountId>,
 	// Generate remote accounts according to polkadot standards
-	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+	HashedLocationToAccountId<AccountId>,
 );
 
 /// XCM from myself to myself
@@ -175,7 +176,8 @@ pub type XcmOriginToTransactDispatchOrigin = (
 pub struct SafeCallFilter;
 impl SafeCallFilter {
 	// 1. RuntimeCall::EVM(..) & RuntimeCall::Ethereum(..) have to be prohibited since we cannot
-	//    measure PoV size properly
+	//    measure PoV size properly. EVM calls go through RuntimeCall::EthereumXcm(..) instead,
+	//    which derives the weight, including the proof size, from the given gas limit.
 	// 2. RuntimeCall::Contracts(..) can be allowed, but it hasn't been tested properly yet.
 
 	/// Checks whether the base (non-composite) call is allowed to be executed via `Transact` XCM
@@ -189,6 +191,7 @@ impl SafeCallFilter {
 				RuntimeCall::Assets(..) |
 				RuntimeCall::PolkadotXcm(..) |
 				RuntimeCall::Session(..) |
+				RuntimeCall::EthereumXcm(..) |
 				RuntimeCall::Multisig(
 					pallet_multisig::Call::approve_as_multi { .. } |
 						pallet_multisig::Call::cancel_as_multi { .. },
@@ -476,3 +479,18 @@ impl pallet_message_queue::Config for Runtime {
 	type WeightInfo = ();
 	type ServiceWeight = MessageQueueServiceWeight;
 }
+
+parameter_types! {
+	/// EVM calls executed through XCM cannot use more gas than the message queue can service in
+	/// a block.
+	pub MaxXcmEvmGasLimit: u64 = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+		MessageQueueServiceWeight::get(),
+	);
+}
+
+impl ethereum_xcm::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type XcmOrigin = EnsureXcm<Everything>;
+	type LocationToAccountId = LocationToAccountId;
+	type MaxGasLimit = MaxXcmEvmGasLimit;
+}
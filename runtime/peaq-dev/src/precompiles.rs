@@ -1,22 +1,33 @@
-use crate::xcm_config::XcmConfig;
-use frame_support::parameter_types;
+use crate::{
+	xcm_config::XcmConfig, CouncilCollective, PotCoretimeId, PotDepinStakingId, PotMorId,
+	PotStakeId, PotSubsidizationId, PotTreasuryId, Runtime, RuntimeCall,
+};
+use frame_support::{parameter_types, PalletId};
 use pallet_evm_precompile_assets_erc20::Erc20AssetsPrecompileSet;
 use pallet_evm_precompile_assets_factory::AssetsFactoryPrecompile;
 use pallet_evm_precompile_balances_erc20::{Erc20BalancesPrecompile, Erc20Metadata};
 use pallet_evm_precompile_batch::BatchPrecompile;
 use pallet_evm_precompile_blake2::Blake2F;
 use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
+use pallet_evm_precompile_collective::CollectivePrecompile;
+use pallet_evm_precompile_dispatch::Dispatch;
+use pallet_evm_precompile_inflation::InflationPrecompile;
 use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_multisig::MultisigPrecompile;
 use pallet_evm_precompile_parachain_staking::ParachainStakingPrecompile;
 use pallet_evm_precompile_peaq_did::PeaqDIDPrecompile;
 use pallet_evm_precompile_peaq_rbac::PeaqRbacPrecompile;
 use pallet_evm_precompile_peaq_storage::PeaqStoragePrecompile;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use pallet_evm_precompile_treasury::TreasuryPrecompile;
 use pallet_evm_precompile_vesting::VestingPrecompile;
+use pallet_evm_precompile_xcm_transfer::XcmTransferPrecompile;
 use pallet_evm_precompile_xcm_utils::XcmUtilsPrecompile;
 use pallet_evm_precompile_xtokens::XtokensPrecompile;
+use pallet_evm_precompile_zenlink_dex::ZenlinkDexPrecompile;
 use precompile_utils::precompile_set::*;
+use runtime_common::{DispatchFilterValidate, WhitelistedCalls};
 
 type EthereumPrecompilesChecks = (AcceptDelegateCall, CallableByContract, CallableByPrecompile);
 
@@ -49,6 +60,15 @@ impl Erc20Metadata for NativeErc20Metadata {
 const ASSET_PRECOMPILE_ADDRESS_PREFIX: &[u8] = &[255u8; 4];
 parameter_types! {
 	pub EVMAssetPrefix: &'static [u8] = ASSET_PRECOMPILE_ADDRESS_PREFIX;
+	/// Pots receiving the block rewards, in the order of the reward distribution config.
+	pub const RewardPotIds: [PalletId; 6] = [
+		PotTreasuryId::get(),
+		PotStakeId::get(),
+		PotCoretimeId::get(),
+		PotSubsidizationId::get(),
+		PotDepinStakingId::get(),
+		PotMorId::get(),
+	];
 }
 
 /// The following distribution has been decided for the precompiles
@@ -78,7 +98,12 @@ pub type PeaqPrecompiles<R> = PrecompileSetBuilder<
 					Sha3FIPS256,
 					(CallableByContract, CallableByPrecompile),
 				>,
-				// PrecompileAt<AddressU64<1025>, Dispatch<R>>,
+				PrecompileAt<
+					AddressU64<1025>,
+					Dispatch<R, DispatchFilterValidate<RuntimeCall, WhitelistedCalls<Runtime>>>,
+					// Batch is the only precompile allowed to call Dispatch.
+					(CallableByContract, CallableByPrecompile<OnlyFrom<AddressU64<2053>>>),
+				>,
 				PrecompileAt<
 					AddressU64<1026>,
 					ECRecoverPublicKey,
@@ -106,9 +131,13 @@ pub type PeaqPrecompiles<R> = PrecompileSetBuilder<
 				>,
 				PrecompileAt<
 					AddressU64<2052>,
-					XcmUtilsPrecompile<R, XcmConfig>,
+					XcmUtilsPrecompile<R, XcmConfig, address_unification::Pallet<R>>,
 					CallableByContract<
-						pallet_evm_precompile_xcm_utils::AllExceptXcmExecute<R, XcmConfig>,
+						pallet_evm_precompile_xcm_utils::AllExceptXcmExecute<
+							R,
+							XcmConfig,
+							address_unification::Pallet<R>,
+						>,
 					>,
 				>,
 				PrecompileAt<
@@ -122,8 +151,8 @@ pub type PeaqPrecompiles<R> = PrecompileSetBuilder<
 				>,
 				PrecompileAt<
 					AddressU64<2054>,
-					AssetsFactoryPrecompile<R>,
-					(AcceptDelegateCall, CallableByContract),
+					AssetsFactoryPrecompile<R, address_unification::Pallet<R>>,
+					CallableByContract,
 				>,
 				PrecompileAt<
 					AddressU64<2055>,
@@ -140,6 +169,32 @@ pub type PeaqPrecompiles<R> = PrecompileSetBuilder<
 					Erc20BalancesPrecompile<R, NativeErc20Metadata>,
 					(AcceptDelegateCall, CallableByContract),
 				>,
+				PrecompileAt<AddressU64<2058>, XcmTransferPrecompile<R>, CallableByContract>,
+				PrecompileAt<AddressU64<2059>, ZenlinkDexPrecompile<R>, CallableByContract>,
+				PrecompileAt<
+					AddressU64<2060>,
+					MultisigPrecompile<
+						R,
+						address_unification::Pallet<R>,
+						WhitelistedCalls<Runtime>,
+					>,
+					(CallableByContract, CallableByPrecompile<OnlyFrom<AddressU64<2053>>>),
+				>,
+				PrecompileAt<
+					AddressU64<2061>,
+					CollectivePrecompile<R, address_unification::Pallet<R>, CouncilCollective>,
+					(CallableByContract, CallableByPrecompile<OnlyFrom<AddressU64<2053>>>),
+				>,
+				PrecompileAt<
+					AddressU64<2062>,
+					TreasuryPrecompile<R, address_unification::Pallet<R>>,
+					(CallableByContract, CallableByPrecompile<OnlyFrom<AddressU64<2053>>>),
+				>,
+				PrecompileAt<
+					AddressU64<2063>,
+					InflationPrecompile<R, RewardPotIds>,
+					(CallableByContract, CallableByPrecompile),
+				>,
 			),
 		>,
 		PrecompileSetStartingWith<